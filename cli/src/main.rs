@@ -0,0 +1,123 @@
+use crate::normalizer::Normalizer;
+use crate::progress::Progress;
+use crate::table::{Column, Format, Reader, Writer};
+use clap::{Parser, ValueEnum};
+use japanese_address_parser::parser::ParseOptions;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+
+mod normalizer;
+mod progress;
+mod table;
+
+/// CSV・TSV・JSON Lines形式のファイルに含まれる住所を正規化します
+///
+/// 入力の各行に prefecture, city, town, rest, latitude, longitude, error の列を追加して出力します。
+/// 入力は一定の行数ごとに読み込んで処理するため、巨大なファイルでもメモリ使用量は一定に保たれます。
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Args {
+    /// 入力ファイル(省略した場合は標準入力)
+    input: Option<PathBuf>,
+    /// 出力ファイル(省略した場合は標準出力)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// 入力の形式(省略した場合は入力ファイルの拡張子から判定し、判定できなければCSVとみなす)
+    #[arg(short, long, value_enum)]
+    format: Option<Format>,
+    /// 住所が含まれる列の名前、または0から始まる列番号
+    #[arg(short, long)]
+    column: Column,
+    /// CSV・TSVの1行目をヘッダーとして扱わない
+    #[arg(long)]
+    no_header: bool,
+    /// 使用する住所データ
+    #[arg(long, value_enum, default_value_t = DataSource::Geolonia)]
+    data_source: DataSource,
+    /// 都道府県名が省略されている場合に市区町村名から推定する(Geoloniaのみ)
+    #[arg(long)]
+    infer_prefecture: bool,
+    /// 郡名が省略された市区町村名を補正しない
+    #[arg(long)]
+    no_city_name_correction: bool,
+    /// 町名以降の「1-2」のような表記を「1番2号」の形に整形する
+    #[arg(long)]
+    format_house_number: bool,
+    /// 住所に含まれる空白文字を取り除く
+    #[arg(long)]
+    eliminate_whitespaces: bool,
+    /// 住所に含まれる半角カタカナを全角カタカナに変換する
+    #[arg(long)]
+    fix_halfwidth_katakana: bool,
+    /// 住所マスタを同時に取得する数
+    #[arg(short, long, default_value_t = 8)]
+    jobs: usize,
+    /// 一度に読み込んで処理する行数
+    #[arg(long, default_value_t = 1000)]
+    batch_size: usize,
+    /// 進捗を表示しない
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum DataSource {
+    /// Geolonia 住所データ
+    Geolonia,
+    /// ChimeiRuiju 住所データ
+    ChimeiRuiju,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let format = args
+        .format
+        .or_else(|| args.input.as_deref().and_then(Format::from_path))
+        .unwrap_or(Format::Csv);
+    let input: Box<dyn Read> = match &args.input {
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+        None => Box::new(std::io::stdin().lock()),
+    };
+    let output: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(std::io::stdout().lock())),
+    };
+    let mut reader = Reader::new(input, format, &args.column, !args.no_header)?;
+    let mut writer = Writer::new(output, format);
+    if let Some(headers) = reader.headers() {
+        writer.write_headers(headers)?;
+    }
+    let options = ParseOptions::default()
+        .infer_prefecture(args.infer_prefecture)
+        .city_name_correction(!args.no_city_name_correction)
+        .format_house_number(args.format_house_number)
+        .eliminate_whitespaces(args.eliminate_whitespaces)
+        .fix_halfwidth_katakana(args.fix_halfwidth_katakana);
+    let normalizer = match args.data_source {
+        DataSource::Geolonia => Normalizer::geolonia(options, args.jobs),
+        DataSource::ChimeiRuiju => {
+            Normalizer::chimei_ruiju(options, !args.no_city_name_correction, args.jobs)
+        }
+    };
+    let mut progress = Progress::new(args.quiet);
+
+    loop {
+        let rows = reader.read_rows(args.batch_size.max(1))?;
+        if rows.is_empty() {
+            break;
+        }
+        let count = rows.len();
+        let addresses: Vec<Option<&str>> = rows.iter().map(|row| row.address()).collect();
+        let normalized = normalizer.normalize(&addresses).await;
+        for (row, normalized) in rows.into_iter().zip(normalized) {
+            writer.write_row(row, normalized)?;
+        }
+        writer.flush()?;
+        progress.advance(count);
+    }
+    progress.finish();
+    Ok(())
+}
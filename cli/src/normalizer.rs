@@ -0,0 +1,269 @@
+use futures::StreamExt;
+use japanese_address_parser::domain::geolonia::error::ErrorKind;
+use japanese_address_parser::experimental;
+use japanese_address_parser::experimental::parser::{DataSource, ParsedAddress, ParserOptions};
+use japanese_address_parser::http::cached_client::CachedApiClient;
+use japanese_address_parser::http::reqwest_client::ReqwestApiClient;
+use japanese_address_parser::parser::{ParseOptions, ParseResult, Parser};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// 住所の列が空、または存在しない場合のエラー
+const MISSING_ADDRESS: &str = "住所が入力されていません";
+
+/// 住所マスタを保持する期間
+const CACHE_TTL: Duration = Duration::from_secs(3600);
+
+/// 住所マスタを保持する件数
+///
+/// 大きなファイルでは全国の市区町村(約1,900件)や町名ごとの住所マスタを参照するため、
+/// `InMemoryCache`の初期値よりも多く保持し、バッチをまたいで取得し直さないようにする。
+const CACHE_ENTRIES: usize = 4096;
+
+/// 1行分の正規化結果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Normalized {
+    pub(crate) prefecture: String,
+    pub(crate) city: String,
+    pub(crate) town: String,
+    pub(crate) rest: String,
+    pub(crate) latitude: Option<f64>,
+    pub(crate) longitude: Option<f64>,
+    pub(crate) error: Option<String>,
+}
+
+impl Normalized {
+    /// 出力に追加する列の名前
+    pub(crate) const COLUMNS: [&'static str; 7] = [
+        "prefecture",
+        "city",
+        "town",
+        "rest",
+        "latitude",
+        "longitude",
+        "error",
+    ];
+
+    /// `COLUMNS`と同じ順に値を返す
+    pub(crate) fn values(&self) -> [String; 7] {
+        let to_string = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+        [
+            self.prefecture.clone(),
+            self.city.clone(),
+            self.town.clone(),
+            self.rest.clone(),
+            to_string(self.latitude),
+            to_string(self.longitude),
+            self.error.clone().unwrap_or_default(),
+        ]
+    }
+
+    fn missing_address() -> Self {
+        Self {
+            error: Some(MISSING_ADDRESS.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ParseResult> for Normalized {
+    fn from(value: ParseResult) -> Self {
+        Self {
+            prefecture: value.address.prefecture,
+            city: value.address.city,
+            town: value.address.town,
+            rest: value.address.rest,
+            latitude: value.coordinate.map(|coordinate| coordinate.latitude),
+            longitude: value.coordinate.map(|coordinate| coordinate.longitude),
+            error: value.error.map(|error| error.error_message),
+        }
+    }
+}
+
+impl From<ParsedAddress> for Normalized {
+    fn from(value: ParsedAddress) -> Self {
+        // 住所マスタの取得に失敗した場合はそのエラーを、
+        // そうでなければどこまで検出できたかから住所が誤っている旨のエラーを組み立てる
+        let error = match (value.metadata.error, value.metadata.depth) {
+            (Some(error), _) => Some(error.error_message),
+            (None, 0) => Some(ErrorKind::PrefectureNotFound.to_string()),
+            (None, 1) => Some(ErrorKind::CityNotFound.to_string()),
+            (None, 2) => Some(ErrorKind::TownNotFound.to_string()),
+            (None, _) => None,
+        };
+        Self {
+            prefecture: value.prefecture,
+            city: value.city,
+            town: value.town,
+            rest: value.rest,
+            latitude: value.metadata.latitude,
+            longitude: value.metadata.longitude,
+            error,
+        }
+    }
+}
+
+/// 住所データに応じたパーサー
+pub(crate) enum Normalizer {
+    Geolonia(Parser<CachedApiClient<ReqwestApiClient>>),
+    ChimeiRuiju {
+        parser: experimental::parser::Parser<CachedApiClient<ReqwestApiClient>>,
+        options: ParserOptions,
+        jobs: usize,
+    },
+}
+
+impl Normalizer {
+    /// 全てのバッチで同じ`CachedApiClient`を共有し、住所マスタを一度だけ取得する
+    pub(crate) fn geolonia(options: ParseOptions, jobs: usize) -> Self {
+        let api_client = CachedApiClient::with_config(CACHE_TTL, CACHE_ENTRIES);
+        Self::Geolonia(
+            Parser::with_api_client(api_client)
+                .options(options)
+                .max_concurrency(jobs),
+        )
+    }
+
+    /// 全ての住所で同じ`CachedApiClient`を共有し、住所マスタを一度だけ取得する
+    pub(crate) fn chimei_ruiju(
+        options: ParseOptions,
+        city_name_correction: bool,
+        jobs: usize,
+    ) -> Self {
+        let api_client = CachedApiClient::with_config(CACHE_TTL, CACHE_ENTRIES);
+        Self::ChimeiRuiju {
+            parser: experimental::parser::Parser::with_api_client(api_client).options(options),
+            options: ParserOptions {
+                data_source: DataSource::ChimeiRuiju,
+                correct_incomplete_city_names: city_name_correction,
+                verbose: false,
+            },
+            jobs: jobs.max(1),
+        }
+    }
+
+    /// 住所を正規化し、入力と同じ順に結果を返す
+    ///
+    /// 住所が`None`の行は解析せず、エラーとして扱う。
+    pub(crate) async fn normalize(&self, addresses: &[Option<&str>]) -> Vec<Normalized> {
+        let results = match self {
+            Self::Geolonia(parser) => {
+                let mut results = parser
+                    .parse_many(addresses.iter().flatten().copied())
+                    .await
+                    .into_iter();
+                addresses
+                    .iter()
+                    .map(|address| match address {
+                        Some(_) => results.next().map(Normalized::from),
+                        None => None,
+                    })
+                    .collect::<Vec<_>>()
+            }
+            Self::ChimeiRuiju {
+                parser,
+                options,
+                jobs,
+            } => {
+                // 同じ住所が複数の行に含まれている場合は一度だけ解析する
+                let mut unique_addresses = vec![];
+                let mut indices = HashMap::new();
+                for address in addresses.iter().flatten() {
+                    indices.entry(*address).or_insert_with(|| {
+                        unique_addresses.push(*address);
+                        unique_addresses.len() - 1
+                    });
+                }
+                let normalized = futures::stream::iter(unique_addresses)
+                    .map(|address| async move {
+                        Normalized::from(parser.parse_with_options(address, options).await)
+                    })
+                    .buffered(*jobs)
+                    .collect::<Vec<_>>()
+                    .await;
+                addresses
+                    .iter()
+                    .map(|address| address.map(|address| normalized[indices[address]].clone()))
+                    .collect::<Vec<_>>()
+            }
+        };
+        results
+            .into_iter()
+            .map(|result| result.unwrap_or_else(Normalized::missing_address))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::normalizer::Normalized;
+    use japanese_address_parser::domain::geolonia::error::{Error, ErrorKind};
+    use japanese_address_parser::experimental::parser::{Metadata, ParsedAddress};
+    use japanese_address_parser::http::error::ApiClientError;
+
+    #[test]
+    fn values_列の順に値を返す() {
+        let normalized = Normalized {
+            prefecture: "東京都".to_string(),
+            city: "千代田区".to_string(),
+            town: "千代田".to_string(),
+            rest: "1-1".to_string(),
+            latitude: Some(35.68391),
+            longitude: None,
+            error: None,
+        };
+        assert_eq!(
+            normalized.values(),
+            ["東京都", "千代田区", "千代田", "1-1", "35.68391", "", ""]
+        );
+    }
+
+    #[test]
+    fn from_parsed_address_町名を検出できなかった場合() {
+        let parsed_address = ParsedAddress {
+            prefecture: "東京都".to_string(),
+            city: "千代田区".to_string(),
+            town: "".to_string(),
+            rest: "無名町1-1".to_string(),
+            rest_components: Default::default(),
+            metadata: Metadata {
+                latitude: None,
+                longitude: None,
+                depth: 2,
+                addressing_system: None,
+                error: None,
+            },
+        };
+        let normalized = Normalized::from(parsed_address);
+        assert_eq!(normalized.city, "千代田区");
+        assert_eq!(normalized.error, Some(ErrorKind::TownNotFound.to_string()));
+    }
+
+    #[test]
+    fn from_parsed_address_住所マスタの取得に失敗した場合() {
+        let error = Error::from(ErrorKind::Fetch {
+            url: "https://example.com/東京都/千代田区.json".to_string(),
+            source: Box::new(ApiClientError::Request {
+                url: "https://example.com/東京都/千代田区.json".to_string(),
+                message: "timeout".to_string(),
+            }),
+        });
+        let parsed_address = ParsedAddress {
+            prefecture: "東京都".to_string(),
+            city: "千代田区".to_string(),
+            town: "".to_string(),
+            rest: "千代田1-1".to_string(),
+            rest_components: Default::default(),
+            metadata: Metadata {
+                latitude: None,
+                longitude: None,
+                depth: 2,
+                addressing_system: None,
+                error: Some(error.clone()),
+            },
+        };
+        let normalized = Normalized::from(parsed_address);
+        assert_eq!(normalized.error, Some(error.error_message));
+        assert_ne!(normalized.error, Some(ErrorKind::TownNotFound.to_string()));
+    }
+}
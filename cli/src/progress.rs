@@ -0,0 +1,44 @@
+use std::io::Write;
+use std::time::Instant;
+
+/// 処理済みの行数と処理速度を標準エラー出力に表示する
+pub(crate) struct Progress {
+    enabled: bool,
+    count: usize,
+    started_at: Instant,
+}
+
+impl Progress {
+    pub(crate) fn new(quiet: bool) -> Self {
+        Self {
+            enabled: !quiet,
+            count: 0,
+            started_at: Instant::now(),
+        }
+    }
+
+    pub(crate) fn advance(&mut self, count: usize) {
+        self.count += count;
+        if self.enabled {
+            let elapsed = self.started_at.elapsed().as_secs_f64();
+            let rate = if elapsed > 0.0 {
+                self.count as f64 / elapsed
+            } else {
+                0.0
+            };
+            let mut stderr = std::io::stderr().lock();
+            let _ = write!(stderr, "\r{}件処理しました({:.0}件/秒)", self.count, rate);
+            let _ = stderr.flush();
+        }
+    }
+
+    pub(crate) fn finish(&self) {
+        if self.enabled {
+            eprintln!(
+                "\r{}件処理しました({:.1}秒)",
+                self.count,
+                self.started_at.elapsed().as_secs_f64()
+            );
+        }
+    }
+}
@@ -0,0 +1,463 @@
+use crate::normalizer::Normalized;
+use anyhow::{anyhow, bail};
+use clap::ValueEnum;
+use csv::StringRecord;
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// 入出力の形式
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub(crate) enum Format {
+    /// カンマ区切り
+    Csv,
+    /// タブ区切り
+    Tsv,
+    /// 1行に1つのJSONを記載したもの
+    Jsonl,
+}
+
+impl Format {
+    /// ファイルの拡張子から形式を判定する
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "tsv" | "tab" => Some(Self::Tsv),
+            "jsonl" | "ndjson" => Some(Self::Jsonl),
+            _ => None,
+        }
+    }
+
+    fn delimiter(&self) -> u8 {
+        match self {
+            Self::Tsv => b'\t',
+            _ => b',',
+        }
+    }
+}
+
+/// 住所が含まれる列
+///
+/// 数字のみからなる場合は列番号、それ以外は列の名前(JSON Linesの場合はキー)として扱う。
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Column {
+    Name(String),
+    Index(usize),
+}
+
+impl FromStr for Column {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.parse::<usize>() {
+            Ok(index) => Self::Index(index),
+            Err(_) => Self::Name(s.to_string()),
+        })
+    }
+}
+
+/// 入力の1行
+pub(crate) enum Row {
+    Delimited {
+        record: StringRecord,
+        column_index: usize,
+    },
+    Json {
+        value: Value,
+        column: Column,
+    },
+}
+
+impl Row {
+    /// 住所が含まれる列の値を返す
+    pub(crate) fn address(&self) -> Option<&str> {
+        let address = match self {
+            Self::Delimited {
+                record,
+                column_index,
+            } => record.get(*column_index),
+            Self::Json { value, column } => match column {
+                Column::Name(name) => value.get(name).and_then(Value::as_str),
+                Column::Index(index) => value.get(index).and_then(Value::as_str),
+            },
+        };
+        address.filter(|address| !address.trim().is_empty())
+    }
+}
+
+/// 入力を一定の行数ずつ読み込む
+pub(crate) enum Reader {
+    Delimited {
+        reader: csv::Reader<Box<dyn Read>>,
+        headers: Option<StringRecord>,
+        column_index: usize,
+    },
+    JsonLines {
+        lines: std::io::Lines<BufReader<Box<dyn Read>>>,
+        column: Column,
+    },
+}
+
+impl Reader {
+    pub(crate) fn new(
+        input: Box<dyn Read>,
+        format: Format,
+        column: &Column,
+        has_headers: bool,
+    ) -> anyhow::Result<Self> {
+        if format == Format::Jsonl {
+            return Ok(Self::JsonLines {
+                lines: BufReader::new(input).lines(),
+                column: column.clone(),
+            });
+        }
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(format.delimiter())
+            .has_headers(has_headers)
+            .flexible(true)
+            .from_reader(input);
+        let headers = if has_headers {
+            Some(reader.headers()?.clone())
+        } else {
+            None
+        };
+        let column_index = match (column, &headers) {
+            (Column::Index(index), _) => *index,
+            (Column::Name(name), Some(headers)) => headers
+                .iter()
+                .position(|header| header == name)
+                .ok_or_else(|| anyhow!("列「{}」が見つかりません", name))?,
+            (Column::Name(name), None) => {
+                bail!("ヘッダーがない場合は列番号で指定してください: {}", name)
+            }
+        };
+        Ok(Self::Delimited {
+            reader,
+            headers,
+            column_index,
+        })
+    }
+
+    /// CSV・TSVのヘッダーを返す
+    pub(crate) fn headers(&self) -> Option<&StringRecord> {
+        match self {
+            Self::Delimited { headers, .. } => headers.as_ref(),
+            Self::JsonLines { .. } => None,
+        }
+    }
+
+    /// 最大`limit`行を読み込む。入力の終わりに達した場合は空のベクタを返す
+    pub(crate) fn read_rows(&mut self, limit: usize) -> anyhow::Result<Vec<Row>> {
+        let mut rows = Vec::with_capacity(limit);
+        match self {
+            Self::Delimited {
+                reader,
+                column_index,
+                ..
+            } => {
+                let mut records = reader.records();
+                while rows.len() < limit {
+                    let Some(record) = records.next() else {
+                        break;
+                    };
+                    rows.push(Row::Delimited {
+                        record: record?,
+                        column_index: *column_index,
+                    });
+                }
+            }
+            Self::JsonLines { lines, column } => {
+                while rows.len() < limit {
+                    let Some(line) = lines.next() else {
+                        break;
+                    };
+                    let line = line?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    rows.push(Row::Json {
+                        value: serde_json::from_str(&line)?,
+                        column: column.clone(),
+                    });
+                }
+            }
+        }
+        Ok(rows)
+    }
+}
+
+/// 正規化結果を追加した行を書き出す
+pub(crate) enum Writer {
+    Delimited {
+        writer: Box<csv::Writer<Box<dyn Write>>>,
+        /// 正規化結果を追加する前のヘッダーの列数
+        ///
+        /// 列数が足りない行は、正規化結果が正しい列に並ぶよう空の値で埋める。
+        width: Option<usize>,
+    },
+    JsonLines(Box<dyn Write>),
+}
+
+impl Writer {
+    pub(crate) fn new(output: Box<dyn Write>, format: Format) -> Self {
+        match format {
+            Format::Jsonl => Self::JsonLines(output),
+            _ => Self::Delimited {
+                writer: Box::new(
+                    csv::WriterBuilder::new()
+                        .delimiter(format.delimiter())
+                        .flexible(true)
+                        .from_writer(output),
+                ),
+                width: None,
+            },
+        }
+    }
+
+    pub(crate) fn write_headers(&mut self, headers: &StringRecord) -> anyhow::Result<()> {
+        if let Self::Delimited { writer, width } = self {
+            width.replace(headers.len());
+            let mut headers = headers.clone();
+            headers.extend(Normalized::COLUMNS);
+            writer.write_record(&headers)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_row(&mut self, row: Row, normalized: Normalized) -> anyhow::Result<()> {
+        match (self, row) {
+            (Self::Delimited { writer, width }, Row::Delimited { mut record, .. }) => {
+                for _ in record.len()..width.unwrap_or_default() {
+                    record.push_field("");
+                }
+                record.extend(normalized.values());
+                writer.write_record(&record)?;
+            }
+            (Self::JsonLines(writer), Row::Json { value, .. }) => {
+                serde_json::to_writer(&mut *writer, &append(value, normalized))?;
+                writer.write_all(b"\n")?;
+            }
+            _ => unreachable!("input and output formats must be the same"),
+        }
+        Ok(())
+    }
+
+    pub(crate) fn flush(&mut self) -> anyhow::Result<()> {
+        match self {
+            Self::Delimited { writer, .. } => writer.flush()?,
+            Self::JsonLines(writer) => writer.flush()?,
+        }
+        Ok(())
+    }
+}
+
+/// JSONのオブジェクトには正規化結果をキーとともに、配列には値のみを追加する
+fn append(value: Value, normalized: Normalized) -> Value {
+    let values = [
+        Value::from(normalized.prefecture),
+        Value::from(normalized.city),
+        Value::from(normalized.town),
+        Value::from(normalized.rest),
+        Value::from(normalized.latitude),
+        Value::from(normalized.longitude),
+        Value::from(normalized.error),
+    ];
+    match value {
+        Value::Object(mut object) => {
+            for (key, value) in Normalized::COLUMNS.into_iter().zip(values) {
+                object.insert(key.to_string(), value);
+            }
+            Value::Object(object)
+        }
+        Value::Array(mut array) => {
+            array.extend(values);
+            Value::Array(array)
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::normalizer::Normalized;
+    use crate::table::{Column, Format, Reader, Writer};
+    use std::io::{Cursor, Write};
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+
+    /// 書き込まれた内容を後から確認するための出力先
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    fn normalized() -> Normalized {
+        Normalized {
+            prefecture: "東京都".to_string(),
+            city: "千代田区".to_string(),
+            town: "千代田".to_string(),
+            rest: "1-1".to_string(),
+            latitude: Some(35.5),
+            longitude: Some(139.5),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn format_from_path_拡張子から判定する() {
+        assert_eq!(Format::from_path(Path::new("a.CSV")), Some(Format::Csv));
+        assert_eq!(Format::from_path(Path::new("a.tsv")), Some(Format::Tsv));
+        assert_eq!(
+            Format::from_path(Path::new("a.ndjson")),
+            Some(Format::Jsonl)
+        );
+        assert_eq!(Format::from_path(Path::new("a.txt")), None);
+    }
+
+    #[test]
+    fn column_from_str() {
+        assert_eq!("2".parse::<Column>().unwrap(), Column::Index(2));
+        assert_eq!(
+            "住所".parse::<Column>().unwrap(),
+            Column::Name("住所".to_string())
+        );
+    }
+
+    #[test]
+    fn csv_列名で指定した列に正規化結果を追加する() {
+        let input = "id,住所\n1,東京都千代田区千代田1-1\n2,\n";
+        let mut reader = Reader::new(
+            Box::new(Cursor::new(input)),
+            Format::Csv,
+            &Column::Name("住所".to_string()),
+            true,
+        )
+        .unwrap();
+        let output = SharedBuffer::default();
+        let mut writer = Writer::new(Box::new(output.clone()), Format::Csv);
+        writer.write_headers(reader.headers().unwrap()).unwrap();
+
+        let rows = reader.read_rows(10).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].address(), Some("東京都千代田区千代田1-1"));
+        assert_eq!(rows[1].address(), None);
+        for row in rows {
+            writer.write_row(row, normalized()).unwrap();
+        }
+        writer.flush().unwrap();
+        assert!(reader.read_rows(10).unwrap().is_empty());
+
+        let mut lines = output
+            .contents()
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines.remove(0),
+            "id,住所,prefecture,city,town,rest,latitude,longitude,error"
+        );
+        assert_eq!(
+            lines.remove(0),
+            "1,東京都千代田区千代田1-1,東京都,千代田区,千代田,1-1,35.5,139.5,"
+        );
+    }
+
+    #[test]
+    fn csv_列数が足りない行はヘッダーの列数まで埋める() {
+        let input = "住所,id,備考\n東京都千代田区千代田1-1,1\n";
+        let mut reader = Reader::new(
+            Box::new(Cursor::new(input)),
+            Format::Csv,
+            &Column::Index(0),
+            true,
+        )
+        .unwrap();
+        let output = SharedBuffer::default();
+        let mut writer = Writer::new(Box::new(output.clone()), Format::Csv);
+        writer.write_headers(reader.headers().unwrap()).unwrap();
+        for row in reader.read_rows(10).unwrap() {
+            writer.write_row(row, normalized()).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let lines = output
+            .contents()
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "住所,id,備考,prefecture,city,town,rest,latitude,longitude,error",
+                "東京都千代田区千代田1-1,1,,東京都,千代田区,千代田,1-1,35.5,139.5,"
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_存在しない列名を指定した場合() {
+        let result = Reader::new(
+            Box::new(Cursor::new("id,address\n")),
+            Format::Csv,
+            &Column::Name("住所".to_string()),
+            true,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn tsv_ヘッダーがない場合() {
+        let mut reader = Reader::new(
+            Box::new(Cursor::new("1\t東京都千代田区千代田1-1\n")),
+            Format::Tsv,
+            &Column::Index(1),
+            false,
+        )
+        .unwrap();
+        assert!(reader.headers().is_none());
+        let rows = reader.read_rows(10).unwrap();
+        assert_eq!(rows[0].address(), Some("東京都千代田区千代田1-1"));
+    }
+
+    #[test]
+    fn jsonl_オブジェクトに正規化結果を追加する() {
+        let input = "{\"id\": 1, \"address\": \"東京都千代田区千代田1-1\"}\n\n";
+        let mut reader = Reader::new(
+            Box::new(Cursor::new(input)),
+            Format::Jsonl,
+            &Column::Name("address".to_string()),
+            true,
+        )
+        .unwrap();
+        let rows = reader.read_rows(10).unwrap();
+        assert_eq!(rows.len(), 1);
+
+        let output = SharedBuffer::default();
+        let mut writer = Writer::new(Box::new(output.clone()), Format::Jsonl);
+        for row in rows {
+            writer.write_row(row, normalized()).unwrap();
+        }
+        writer.flush().unwrap();
+        let value: serde_json::Value = serde_json::from_str(output.contents().trim()).unwrap();
+        assert_eq!(value["id"], 1);
+        assert_eq!(value["town"], "千代田");
+        assert_eq!(value["latitude"], 35.5);
+        assert!(value["error"].is_null());
+    }
+}
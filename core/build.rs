@@ -0,0 +1,31 @@
+//! `embedded-master`フィーチャーが有効な場合に、バイナリに埋め込む住所マスタを`OUT_DIR`に用意する
+
+use std::path::PathBuf;
+
+/// 埋め込む住所マスタのパスを指定する環境変数
+const EMBEDDED_MASTER_ENV_KEY: &str = "JAPANESE_ADDRESS_PARSER_EMBEDDED_MASTER";
+
+fn main() {
+    println!("cargo:rerun-if-env-changed={}", EMBEDDED_MASTER_ENV_KEY);
+    println!("cargo:rerun-if-env-changed=DOCS_RS");
+    if std::env::var_os("CARGO_FEATURE_EMBEDDED_MASTER").is_none() {
+        return;
+    }
+
+    let out_path = PathBuf::from(std::env::var_os("OUT_DIR").unwrap()).join("embedded-master.bin");
+    match std::env::var_os(EMBEDDED_MASTER_ENV_KEY) {
+        Some(path) => {
+            let path = PathBuf::from(path);
+            println!("cargo:rerun-if-changed={}", path.display());
+            if let Err(error) = std::fs::copy(&path, &out_path) {
+                panic!("failed to read {}: {}", path.display(), error);
+            }
+        }
+        // docs.rsでは住所マスタを用意できないため、ドキュメントの生成に限り空のデータでビルドする
+        None if std::env::var_os("DOCS_RS").is_some() => std::fs::write(&out_path, []).unwrap(),
+        None => panic!(
+            "{} must be set to the path of the master data when the `embedded-master` feature is enabled",
+            EMBEDDED_MASTER_ENV_KEY
+        ),
+    }
+}
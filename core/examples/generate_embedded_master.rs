@@ -0,0 +1,38 @@
+//! Generates embedded master data from a snapshot
+//!
+//! `snapshot::create_geolonia_snapshot`で保存したスナップショットから、`embedded-master`フィーチャーで埋め込むデータを生成します。
+//!
+//! ```sh
+//! cargo run --example generate_embedded_master -- ./snapshots/geolonia-1700000000 ./embedded-master.bin
+//! JAPANESE_ADDRESS_PARSER_EMBEDDED_MASTER=$PWD/embedded-master.bin cargo build --features embedded-master
+//! ```
+
+use japanese_address_parser::http::local_directory_client::LocalDirectoryApiClient;
+use japanese_address_parser::source::embedded::generate;
+use japanese_address_parser::source::ApiClientSource;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (snapshot, output) = match args.as_slice() {
+        [snapshot, output] => (snapshot, output),
+        _ => {
+            eprintln!("usage: generate_embedded_master <snapshot directory> <output file>");
+            std::process::exit(2);
+        }
+    };
+
+    let source = ApiClientSource::new(LocalDirectoryApiClient::with_root(snapshot));
+    let bytes = match generate(&source).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            eprintln!("{}", error.error_message);
+            std::process::exit(1);
+        }
+    };
+    if let Err(error) = std::fs::write(output, &bytes) {
+        eprintln!("failed to write {}: {}", output, error);
+        std::process::exit(1);
+    }
+    println!("{} bytes written to {}", bytes.len(), output);
+}
@@ -2,3 +2,4 @@
 pub(crate) mod chimei_ruiju;
 pub(crate) mod common;
 pub mod geolonia;
+pub mod postal_code;
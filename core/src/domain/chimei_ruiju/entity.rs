@@ -1,4 +1,6 @@
 use crate::domain::common::latlng::LatLng;
+use crate::formatter::house_number::parse_house_number;
+use crate::formatter::postal_code::to_ascii_digit;
 use serde::Deserialize;
 
 #[derive(Deserialize, Debug)]
@@ -14,6 +16,7 @@ pub struct PrefectureMaster {
 #[derive(Deserialize, Debug)]
 pub struct CityMaster {
     /// 市区町村名
+    #[allow(dead_code)]
     pub(crate) name: String,
     /// 町名リスト
     pub(crate) towns: Vec<String>,
@@ -24,28 +27,61 @@ pub struct CityMaster {
 #[derive(Deserialize, Debug)]
 pub struct TownMaster {
     /// 町名
+    #[allow(dead_code)]
     pub(crate) name: String,
     /// 街区リスト
-    blocks: Vec<Block>,
+    pub(crate) blocks: Vec<Block>,
     /// 緯度経度
     pub(crate) coordinate: Coordinate,
 }
 
-#[allow(dead_code)]
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct Block {
     /// 小字・通称名
-    koaza: String,
+    pub(crate) koaza: String,
     /// 街区符号・地番
-    block_number: String,
+    pub(crate) block_number: String,
     /// 住居表示の有無
-    residential_address_indication: bool,
+    pub(crate) residential_address_indication: bool,
     /// 緯度経度
-    coordinate: Coordinate,
+    pub(crate) coordinate: Coordinate,
 }
 
-#[allow(dead_code)]
-#[derive(Deserialize, Debug)]
+impl TownMaster {
+    /// 町名以降の文字列の先頭にある街区符号・地番に一致する街区を返す
+    ///
+    /// 小字・通称名を持つ街区の場合は、町名以降の文字列がその小字・通称名から始まる場合のみ一致とみなす。
+    pub(crate) fn find_block(&self, rest: &str) -> Option<&Block> {
+        self.blocks.iter().find(|block| {
+            let rest = match rest.strip_prefix(block.koaza.as_str()) {
+                Some(without_koaza) if !block.koaza.is_empty() => without_koaza,
+                _ if !block.koaza.is_empty() => return false,
+                _ => rest,
+            };
+            parse_house_number(rest)
+                .block_number
+                .is_some_and(|block_number| same_number(&block_number, &block.block_number))
+        })
+    }
+
+    /// 全ての街区で住居表示の有無が共通している場合、その値を返す
+    pub(crate) fn residential_address_indication(&self) -> Option<bool> {
+        let first = self.blocks.first()?.residential_address_indication;
+        self.blocks
+            .iter()
+            .all(|block| block.residential_address_indication == first)
+            .then_some(first)
+    }
+}
+
+/// 全角数字と半角数字を区別せずに比較する
+fn same_number(a: &str, b: &str) -> bool {
+    let normalize =
+        |s: &str| -> String { s.chars().map(|c| to_ascii_digit(c).unwrap_or(c)).collect() };
+    normalize(a) == normalize(b)
+}
+
+#[derive(Deserialize, Debug, Clone)]
 pub struct Coordinate {
     /// 緯度
     pub(crate) latitude: f64,
@@ -61,3 +97,44 @@ impl Coordinate {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::chimei_ruiju::entity::TownMaster;
+
+    fn town_master() -> TownMaster {
+        serde_json::from_str(
+            r#"{
+                "name": "洋光台三丁目",
+                "blocks": [
+                    {"koaza": "", "block_number": "9", "residential_address_indication": true, "coordinate": {"latitude": 35.3801, "longitude": 139.5901}},
+                    {"koaza": "", "block_number": "10", "residential_address_indication": true, "coordinate": {"latitude": 35.3802, "longitude": 139.5902}}
+                ],
+                "coordinate": {"latitude": 35.38, "longitude": 139.59}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn find_block_先頭の街区符号に一致する街区を返す() {
+        let town_master = town_master();
+        let block = town_master.find_block("10-3").unwrap();
+        assert_eq!(block.block_number, "10");
+        assert_eq!(block.coordinate.latitude, 35.3802);
+        let block = town_master.find_block("１０番３号").unwrap();
+        assert_eq!(block.block_number, "10");
+    }
+
+    #[test]
+    fn find_block_一致する街区がない場合() {
+        let town_master = town_master();
+        assert!(town_master.find_block("11-3").is_none());
+        assert!(town_master.find_block("").is_none());
+    }
+
+    #[test]
+    fn residential_address_indication_全ての街区で共通の値を返す() {
+        assert_eq!(town_master().residential_address_indication(), Some(true));
+    }
+}
@@ -1,45 +1,35 @@
 use std::cmp::Ordering;
-use std::cmp::Ordering::{Equal, Greater, Less};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Token {
+    PostalCode(String),
     Prefecture(String),
     City(String),
     Town(String),
+    Koaza(String),
     Rest(String),
 }
 
-impl PartialOrd for Token {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+impl Token {
+    /// 住所中に現れる順番
+    fn order(&self) -> u8 {
         match self {
-            Token::Prefecture(_) => match other {
-                Token::Prefecture(_) => Some(Equal),
-                Token::City(_) => Some(Less),
-                Token::Town(_) => Some(Less),
-                Token::Rest(_) => Some(Less),
-            },
-            Token::City(_) => match other {
-                Token::Prefecture(_) => Some(Greater),
-                Token::City(_) => Some(Equal),
-                Token::Town(_) => Some(Less),
-                Token::Rest(_) => Some(Less),
-            },
-            Token::Town(_) => match other {
-                Token::Prefecture(_) => Some(Greater),
-                Token::City(_) => Some(Greater),
-                Token::Town(_) => Some(Equal),
-                Token::Rest(_) => Some(Less),
-            },
-            Token::Rest(_) => match other {
-                Token::Prefecture(_) => Some(Greater),
-                Token::City(_) => Some(Greater),
-                Token::Town(_) => Some(Greater),
-                Token::Rest(_) => Some(Equal),
-            },
+            Token::PostalCode(_) => 0,
+            Token::Prefecture(_) => 1,
+            Token::City(_) => 2,
+            Token::Town(_) => 3,
+            Token::Koaza(_) => 4,
+            Token::Rest(_) => 5,
         }
     }
 }
 
+impl PartialOrd for Token {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.order().partial_cmp(&other.order())
+    }
+}
+
 pub(crate) fn append_token(tokens: &[Token], token: Token) -> Vec<Token> {
     [tokens.to_owned(), vec![token]].concat()
 }
@@ -54,15 +44,19 @@ mod tests {
             Token::Rest("2-1".to_string()),
             Token::City("小金井市".to_string()),
             Token::Prefecture("東京都".to_string()),
+            Token::Koaza("字貫井".to_string()),
             Token::Town("貫井北町四丁目".to_string()),
+            Token::PostalCode("184-0015".to_string()),
         ];
         tokens.sort_by(|a, b| a.partial_cmp(b).unwrap());
         assert_eq!(
             tokens,
             vec![
+                Token::PostalCode("184-0015".to_string()),
                 Token::Prefecture("東京都".to_string()),
                 Token::City("小金井市".to_string()),
                 Token::Town("貫井北町四丁目".to_string()),
+                Token::Koaza("字貫井".to_string()),
                 Token::Rest("2-1".to_string()),
             ]
         );
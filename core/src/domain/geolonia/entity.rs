@@ -13,22 +13,154 @@ pub(crate) struct City {
     pub towns: Vec<Town>,
 }
 
-#[derive(PartialEq, Deserialize, Debug)]
-pub(crate) struct Town {
+/// Town in the address master data
+///
+/// 市区町村の町名マスタの一件分です。小字・通称名ごとに一件ずつ存在するため、同じ町名が複数回現れることがあります。
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+pub struct Town {
+    /// 町名
     #[serde(alias = "town")]
     pub name: String,
+    /// 小字・通称名(存在しない場合は空文字)
     pub koaza: String,
     // TODO: https://github.com/geolonia/japanese-addresses/issues/148 が解消されたらOptionを外すことができる
     pub lat: Option<f32>,
     pub lng: Option<f32>,
 }
 
-#[derive(Serialize, PartialEq, Debug)]
+/// 町名マスタから、指定した町に属する小字・通称名のリストを返す
+pub(crate) fn koaza_names(towns: &[Town], town_name: &str) -> Vec<String> {
+    let mut koaza_names: Vec<String> = towns
+        .iter()
+        .filter(|town| town.name == town_name && !town.koaza.is_empty())
+        .map(|town| town.koaza.clone())
+        .collect();
+    koaza_names.sort();
+    koaza_names.dedup();
+    koaza_names
+}
+
+#[derive(Serialize, PartialEq, Debug, Clone)]
 pub struct Address {
     pub prefecture: String,
     pub city: String,
     pub town: String,
+    /// 小字・通称名(検出できなかった場合は空文字)
+    pub koaza: String,
     pub rest: String,
+    pub rest_components: RestComponents,
+    /// 住所の先頭に記載されていた郵便番号(`NNN-NNNN`形式)
+    pub postal_code: Option<String>,
+}
+
+/// Components of the address after the town name
+///
+/// 町名以降の住所を街区符号・住居番号・建物名・部屋番号に分解したものです。
+/// 検出できなかった要素は`None`になります。
+#[derive(Serialize, PartialEq, Debug, Default, Clone)]
+pub struct RestComponents {
+    /// 街区符号または番地
+    pub block_number: Option<String>,
+    /// 住居番号または枝番
+    ///
+    /// `2-8-1`のようにハイフン区切りで数字が続く場合は、`8-1`のように続きも含みます。
+    pub house_number: Option<String>,
+    /// 建物名
+    pub building_name: Option<String>,
+    /// 部屋番号または階数
+    ///
+    /// `101号室`や`3F`のような接尾辞付きの表記か、建物名の後ろに続く数字のみを部屋番号として扱います。
+    pub room_number: Option<String>,
+}
+
+/// Representative point of a parsed address
+///
+/// 町名まで特定できた場合は町の代表点を返します。町名を特定できなかった場合は、
+/// 市区町村内の町の代表点の平均、あるいは都道府県庁の所在地で代替し、その旨を`precision`で表します。
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub struct Coordinate {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub precision: Precision,
+}
+
+/// How precisely a `Coordinate` locates the address
+///
+/// 今後バリアントが追加される可能性があるため、`match`する際はワイルドカードを使用してください。
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum Precision {
+    /// 都道府県庁の所在地
+    Prefecture,
+    /// 市区町村内の町の代表点の平均
+    City,
+    /// 町の代表点
+    Town,
+}
+
+/// Position of an address component in the original input
+///
+/// 入力された文字列中の位置をバイト単位と文字単位の両方で表します。いずれも終端を含みません。
+#[derive(Serialize, PartialEq, Debug, Default, Clone, Copy)]
+pub struct Span {
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub char_start: usize,
+    pub char_end: usize,
+}
+
+/// Positions of each address component in the original input
+///
+/// 検出できなかった要素は`None`になります。
+#[derive(Serialize, PartialEq, Debug, Default, Clone)]
+pub struct AddressSpans {
+    pub postal_code: Option<Span>,
+    pub prefecture: Option<Span>,
+    pub city: Option<Span>,
+    pub town: Option<Span>,
+    pub koaza: Option<Span>,
+    pub rest: Option<Span>,
+}
+
+/// A correction applied to the input while parsing
+///
+/// 住所を解釈する過程で入力に対して行った補正を表します。
+/// `original`は入力中の該当部分、`replaced`は補正後の文字列です。
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct Correction {
+    pub kind: CorrectionKind,
+    pub original: String,
+    pub replaced: String,
+}
+
+/// Kind of correction applied to the input
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum CorrectionKind {
+    /// 省略された都道府県名を市区町村名から推定した
+    PrefectureCompletion,
+    /// 省略された郡名を補った
+    CountyNameCompletion,
+    /// 表記ゆれを補正した
+    OrthographicalVariant,
+    /// 「〇〇町L-M-N」のような表記を「〇〇町L丁目M-N」として解釈した
+    InformalTownNameNotation,
+    /// 先頭に「大字」を補った
+    OazaInsertion,
+    /// 先頭に「字」を補った
+    AzaInsertion,
+}
+
+impl CorrectionKind {
+    /// 補正を行った解釈の確からしさを算出する際に掛ける重み
+    pub(crate) fn weight(&self) -> f64 {
+        match self {
+            Self::PrefectureCompletion | Self::CountyNameCompletion => 1.0,
+            Self::OrthographicalVariant => 0.9,
+            Self::InformalTownNameNotation => 0.95,
+            Self::OazaInsertion | Self::AzaInsertion => 0.9,
+        }
+    }
 }
 
 impl Address {
@@ -42,7 +174,10 @@ impl Address {
             prefecture: prefecture_name.to_string(),
             city: city_name.to_string(),
             town: town_name.to_string(),
+            koaza: "".to_string(),
             rest: rest_name.to_string(),
+            rest_components: RestComponents::default(),
+            postal_code: None,
         }
     }
 }
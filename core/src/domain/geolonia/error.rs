@@ -2,31 +2,125 @@ use std::fmt::{Display, Formatter};
 
 use serde::Serialize;
 
-#[derive(Serialize, Debug, PartialEq)]
+use crate::http::error::ApiClientError;
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
 pub struct Error {
     pub error_type: String,
     pub error_message: String,
+    /// 分岐処理に使用できるエラーの種類
+    ///
+    /// シリアライズ結果の互換性を保つため、シリアライズの対象には含めません。
+    #[serde(skip)]
+    pub kind: ErrorKind,
 }
 
-impl Error {
-    pub(crate) fn new_parse_error(parse_error_kind: ParseErrorKind) -> Self {
-        Error {
-            error_type: "ParseError".to_string(),
-            error_message: parse_error_kind.to_string(),
+/// Kind of failure while parsing an address
+///
+/// 住所のパースに失敗した原因を表します。
+/// 今後バリアントが追加される可能性があるため、`match`する際はワイルドカードを使用してください。
+///
+/// ```
+/// use japanese_address_parser::domain::geolonia::error::ErrorKind;
+/// use japanese_address_parser::parser::Parser;
+///
+/// async fn example() {
+///     let parser: Parser = Default::default();
+///     let result = parser.parse("東京都新宿区西新宿2-8-1").await;
+///     if let Some(error) = result.error {
+///         match error.kind {
+///             ErrorKind::Fetch { url, .. } => println!("{}の取得に失敗しました。リトライします", url),
+///             _ => println!("{}", error.error_message),
+///         }
+///     }
+/// }
+/// ```
+#[derive(thiserror::Error, Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// 一致する都道府県名が見つからなかった
+    #[error("{}", ParseErrorKind::Prefecture)]
+    PrefectureNotFound,
+    /// 一致する市区町村名が見つからなかった
+    #[error("{}", ParseErrorKind::City)]
+    CityNotFound,
+    /// 一致する町名が見つからなかった
+    #[error("{}", ParseErrorKind::Town)]
+    TownNotFound,
+    /// 省略された都道府県名を推定しようとしたが、市区町村名が一致する都道府県が複数あった
+    #[error("都道府県を一つに特定できませんでした(候補: {})", .candidates.join("、"))]
+    AmbiguousPrefecture { candidates: Vec<String> },
+    /// 郵便番号として解釈できない文字列が与えられた
+    #[error("郵便番号の形式が正しくありません")]
+    InvalidPostalCode,
+    /// 郵便番号データを参照しない`Parser`(`Parser::embedded()`や`Parser::with_source()`など)で郵便番号を検索しようとした
+    #[error("郵便番号データを参照できません")]
+    PostalCodeLookupUnavailable,
+    /// 住所マスタの取得に失敗した
+    #[error("{url}を取得できませんでした")]
+    Fetch {
+        url: String,
+        source: Box<ApiClientError>,
+    },
+    /// 住所マスタのデシリアライズに失敗した
+    #[error("{url}のデシリアライズに失敗しました")]
+    Deserialize {
+        url: String,
+        source: Box<ApiClientError>,
+    },
+}
+
+impl ErrorKind {
+    fn error_type(&self) -> &'static str {
+        match self {
+            Self::PrefectureNotFound
+            | Self::CityNotFound
+            | Self::TownNotFound
+            | Self::AmbiguousPrefecture { .. }
+            | Self::InvalidPostalCode => "ParseError",
+            Self::PostalCodeLookupUnavailable | Self::Fetch { .. } | Self::Deserialize { .. } => {
+                "ApiError"
+            }
         }
     }
-    pub(crate) fn new_api_error(api_error_kind: ApiErrorKind) -> Self {
-        let error_message = match api_error_kind {
-            ApiErrorKind::Fetch(url) => format!("{}を取得できませんでした", url),
-            ApiErrorKind::Deserialize(url) => format!("{}のデシリアライズに失敗しました", url),
-        };
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
         Error {
-            error_type: "ApiError".to_string(),
-            error_message,
+            error_type: kind.error_type().to_string(),
+            error_message: kind.to_string(),
+            kind,
         }
     }
 }
 
+impl Error {
+    pub(crate) fn new_parse_error(parse_error_kind: ParseErrorKind) -> Self {
+        let kind = match parse_error_kind {
+            ParseErrorKind::Prefecture => ErrorKind::PrefectureNotFound,
+            ParseErrorKind::City => ErrorKind::CityNotFound,
+            ParseErrorKind::Town => ErrorKind::TownNotFound,
+        };
+        Error::from(kind)
+    }
+    pub(crate) fn new_api_error(api_client_error: ApiClientError) -> Self {
+        let kind = match &api_client_error {
+            ApiClientError::Request { url, .. } | ApiClientError::NotFound { url } => {
+                ErrorKind::Fetch {
+                    url: url.clone(),
+                    source: Box::new(api_client_error.clone()),
+                }
+            }
+            ApiClientError::Deserialize { url, .. } => ErrorKind::Deserialize {
+                url: url.clone(),
+                source: Box::new(api_client_error.clone()),
+            },
+        };
+        Error::from(kind)
+    }
+}
+
 pub(crate) enum ParseErrorKind {
     Prefecture,
     City,
@@ -44,7 +138,46 @@ impl Display for ParseErrorKind {
     }
 }
 
-pub(crate) enum ApiErrorKind {
-    Fetch(String),
-    Deserialize(String),
+#[cfg(test)]
+mod tests {
+    use crate::domain::geolonia::error::{Error, ErrorKind, ParseErrorKind};
+    use crate::http::error::ApiClientError;
+
+    #[test]
+    fn new_parse_error_文字列のフィールドが維持される() {
+        let error = Error::new_parse_error(ParseErrorKind::City);
+        assert_eq!(error.error_type, "ParseError");
+        assert_eq!(error.error_message, "一致する市区町村がありませんでした");
+        assert_eq!(error.kind, ErrorKind::CityNotFound);
+    }
+
+    #[test]
+    fn new_api_error_urlと元のエラーを保持する() {
+        let api_client_error = ApiClientError::Request {
+            url: "https://example.com/東京都/master.json".to_string(),
+            message: "timeout".to_string(),
+        };
+        let error = Error::new_api_error(api_client_error.clone());
+        assert_eq!(error.error_type, "ApiError");
+        assert_eq!(
+            error.error_message,
+            "https://example.com/東京都/master.jsonを取得できませんでした"
+        );
+        assert_eq!(
+            error.kind,
+            ErrorKind::Fetch {
+                url: "https://example.com/東京都/master.json".to_string(),
+                source: Box::new(api_client_error),
+            }
+        );
+    }
+
+    #[test]
+    fn シリアライズ結果にkindを含めない() {
+        let error = Error::new_parse_error(ParseErrorKind::Town);
+        assert_eq!(
+            serde_json::to_string(&error).unwrap(),
+            r#"{"error_type":"ParseError","error_message":"一致する町名がありませんでした"}"#
+        );
+    }
 }
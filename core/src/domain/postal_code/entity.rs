@@ -0,0 +1,141 @@
+use crate::domain::geolonia::entity::Address;
+use crate::formatter::postal_code::normalize_postal_code;
+use serde::{Deserialize, Serialize};
+
+/// 町域名が記載されていないことを表す文言
+const TOWN_NOT_LISTED: &str = "以下に掲載がない場合";
+
+/// 郵便番号データの1ファイル分
+///
+/// 日本郵便の`KEN_ALL.CSV`を郵便番号ごとのJSONに変換したもの(`postal-code-api`形式)です。
+#[derive(Deserialize, Debug)]
+pub(crate) struct PostalCodeMaster {
+    /// 郵便番号(7桁)
+    pub(crate) code: String,
+    pub(crate) data: Vec<PostalCodeData>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct PostalCodeData {
+    pub(crate) ja: PostalCodeAddress,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct PostalCodeAddress {
+    /// 都道府県名
+    pub(crate) prefecture: String,
+    /// 市区町村名
+    pub(crate) address1: String,
+    /// 町域名
+    pub(crate) address2: String,
+}
+
+impl PostalCodeMaster {
+    pub(crate) fn into_entries(self) -> Vec<PostalCodeEntry> {
+        let postal_code = normalize_postal_code(&self.code).unwrap_or(self.code);
+        self.data
+            .into_iter()
+            .map(|data| PostalCodeEntry {
+                postal_code: postal_code.clone(),
+                prefecture: data.ja.prefecture,
+                city: data.ja.address1,
+                town: normalize_town_name(&data.ja.address2),
+            })
+            .collect()
+    }
+}
+
+/// 町域名から括弧書きの補足や「以下に掲載がない場合」などを取り除く
+fn normalize_town_name(town_name: &str) -> String {
+    if town_name == TOWN_NOT_LISTED {
+        return "".to_string();
+    }
+    town_name
+        .split(['（', '('])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// An area corresponding to a postal code
+///
+/// 郵便番号に対応する都道府県名・市区町村名・町域名の組です。
+/// 町域名が定められていない郵便番号の場合、`town`は空文字になります。
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct PostalCodeEntry {
+    /// 郵便番号(`NNN-NNNN`形式)
+    pub postal_code: String,
+    pub prefecture: String,
+    pub city: String,
+    pub town: String,
+}
+
+impl PostalCodeEntry {
+    /// Returns whether the given address is located in this area
+    ///
+    /// 都道府県名と市区町村名が一致し、かつ町名が町域名から始まる場合に一致とみなします。
+    /// 郵便番号の町域名には「大字」「字」や丁目が含まれないため、これらの差異は無視します。
+    pub fn matches(&self, address: &Address) -> bool {
+        if self.prefecture != address.prefecture || self.city != address.city {
+            return false;
+        }
+        if self.town.is_empty() || address.town.is_empty() {
+            return true;
+        }
+        let town_name = address
+            .town
+            .strip_prefix("大字")
+            .or_else(|| address.town.strip_prefix('字'))
+            .unwrap_or(&address.town);
+        town_name.starts_with(&self.town)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::geolonia::entity::Address;
+    use crate::domain::postal_code::entity::{PostalCodeEntry, PostalCodeMaster};
+
+    fn entry(town: &str) -> PostalCodeEntry {
+        PostalCodeEntry {
+            postal_code: "369-1503".to_string(),
+            prefecture: "埼玉県".to_string(),
+            city: "秩父郡東秩父村".to_string(),
+            town: town.to_string(),
+        }
+    }
+
+    #[test]
+    fn into_entries_町域名の補足を取り除く() {
+        let master: PostalCodeMaster = serde_json::from_str(
+            r#"{"code": "1600023", "data": [
+                {"prefcode": "13", "ja": {"prefecture": "東京都", "address1": "新宿区", "address2": "西新宿（次のビルを除く）", "address3": "", "address4": ""}}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            master.into_entries(),
+            vec![PostalCodeEntry {
+                postal_code: "160-0023".to_string(),
+                prefecture: "東京都".to_string(),
+                city: "新宿区".to_string(),
+                town: "西新宿".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn matches_大字を無視する() {
+        let address = Address::new("埼玉県", "秩父郡東秩父村", "大字御堂", "634番地");
+        assert!(entry("御堂").matches(&address));
+        assert!(!entry("坂本").matches(&address));
+    }
+
+    #[test]
+    fn matches_町域名が定められていない場合() {
+        let address = Address::new("埼玉県", "秩父郡東秩父村", "大字御堂", "634番地");
+        assert!(entry("").matches(&address));
+        let address = Address::new("埼玉県", "秩父市", "熊木町", "8番15号");
+        assert!(!entry("").matches(&address));
+    }
+}
@@ -1,71 +1,101 @@
+use crate::domain::chimei_ruiju::error::ApiError;
 use crate::domain::common::latlng::LatLng;
 use crate::domain::common::token::Token;
-use crate::experimental::parser::{Parser, ParserOptions};
+use crate::domain::geolonia::entity::Address;
+use crate::domain::geolonia::error::{Error, ErrorKind};
+use crate::experimental::parser::{AddressingSystem, Parser, ParserOptions};
 use crate::http::client::ApiClient;
+use crate::http::error::ApiClientError;
 use crate::interactor::chimei_ruiju::{ChimeiRuijuInteractor, ChimeiRuijuInteractorImpl};
-use crate::tokenizer::Tokenizer;
+use crate::parser::pure::{PureParser, PureParserAction};
+use jisx0401::Prefecture;
 use std::option::Option;
 
-impl<Client: ApiClient> Parser<Client> {
+/// 町名マスタの街区リストから得られた情報
+#[derive(Debug, Default)]
+pub(crate) struct BlockDetail {
+    /// 町名以降の先頭の街区符号・地番に一致する街区が見つかったかどうか
+    pub(crate) found: bool,
+    /// 町内の住所の表示方式
+    pub(crate) addressing_system: Option<AddressingSystem>,
+}
+
+impl<Client: ApiClient + Clone> Parser<Client> {
     pub(crate) async fn parse_with_chimeiruiju(
         &self,
         address: &str,
         options: &ParserOptions,
-    ) -> (Vec<Token>, Option<LatLng>) {
-        let interactor = ChimeiRuijuInteractorImpl::<Client>::default();
-        let tokenizer = Tokenizer::new(address);
+    ) -> (Vec<Token>, Option<LatLng>, BlockDetail, Option<Error>) {
+        let mut interactor = ChimeiRuijuInteractorImpl::new(self.api_client.clone());
+        interactor.set_endpoints(&self.endpoints);
+        let parse_options = self
+            .parse_options
+            .infer_prefecture(false)
+            .city_name_correction(options.correct_incomplete_city_names);
+        let mut pure_parser = PureParser::new(address, parse_options);
         let mut lat_lng: Option<LatLng> = None;
 
-        // 都道府県名の検出
-        let (prefecture, tokenizer) = match tokenizer.read_prefecture() {
-            Ok(found) => found,
-            Err(not_found) => {
-                if options.verbose {
-                    log::error!("都道府県名の検出に失敗しました")
+        let result = loop {
+            match pure_parser.advance() {
+                // 都道府県マスタの取得
+                PureParserAction::RequestCityNameList(prefecture_name) => {
+                    let prefecture = find_prefecture(&prefecture_name);
+                    match interactor.get_prefecture_master(prefecture).await {
+                        Ok(result) => {
+                            lat_lng.replace(result.coordinate.to_lat_lng());
+                            pure_parser.provide_input(result.cities);
+                        }
+                        Err(error) => break pure_parser.abort(to_error(error)),
+                    }
                 }
-                return (not_found.tokens, lat_lng);
-            }
-        };
-
-        // 都道府県マスタの取得
-        let prefecture_master = match interactor.get_prefecture_master(&prefecture).await {
-            Ok(result) => {
-                lat_lng.replace(result.coordinate.to_lat_lng());
-                result
-            }
-            Err(error) => {
-                if options.verbose {
-                    log::error!("{}", error)
+                // 都道府県名の推定は無効にしているため要求されない
+                PureParserAction::RequestAllCityNameLists(_) => {
+                    pure_parser.provide_city_name_lists(vec![])
                 }
-                return (tokenizer.finish().tokens, lat_lng);
-            }
-        };
-        // 市区町村名の検出
-        let (city_name, tokenizer) = match tokenizer.read_city(&prefecture_master.cities) {
-            Ok(found) => found,
-            Err(not_found) => {
-                if options.correct_incomplete_city_names {
-                    match not_found.read_city_with_county_name_completion(&prefecture_master.cities)
-                    {
-                        Ok(result) => result,
-                        Err(not_found) => {
-                            if options.verbose {
-                                log::error!("市区町村名の検出に失敗しました")
-                            }
-                            return (not_found.tokens, lat_lng);
+                // 市区町村マスタの取得
+                PureParserAction::RequestTownNameList(prefecture_name, city_name) => {
+                    let prefecture = find_prefecture(&prefecture_name);
+                    match interactor.get_city_master(prefecture, &city_name).await {
+                        Ok(result) => {
+                            lat_lng.replace(result.coordinate.to_lat_lng());
+                            pure_parser.provide_input(result.towns);
                         }
+                        Err(error) => break pure_parser.abort(to_error(error)),
                     }
-                } else {
-                    if options.verbose {
-                        log::error!("市区町村名の検出に失敗しました")
-                    }
-                    return (not_found.finish().tokens, lat_lng);
                 }
+                // ChimeiRuijuの町名マスタは小字・通称名を町名と区別しない
+                PureParserAction::RequestKoazaList(_) => pure_parser.provide_input(vec![]),
+                PureParserAction::Done(result) => break result,
             }
         };
+        if let Some(error) = result.error {
+            if options.verbose {
+                log::error!("{}", error.error_message)
+            }
+            // 住所マスタを取得できずに中断した場合は、住所が誤っている場合と区別できるようエラーを返す
+            let error = matches!(
+                error.kind,
+                ErrorKind::Fetch { .. } | ErrorKind::Deserialize { .. }
+            )
+            .then_some(error);
+            return (
+                tokens(result.address),
+                lat_lng,
+                BlockDetail::default(),
+                error,
+            );
+        }
 
-        // 市区町村マスタの取得
-        let city_master = match interactor.get_city_master(&prefecture, &city_name).await {
+        // 町村マスタの取得
+        let address = result.address;
+        let town_master = match interactor
+            .get_town_master(
+                find_prefecture(&address.prefecture),
+                &address.city,
+                &address.town,
+            )
+            .await
+        {
             Ok(result) => {
                 lat_lng.replace(result.coordinate.to_lat_lng());
                 result
@@ -74,36 +104,74 @@ impl<Client: ApiClient> Parser<Client> {
                 if options.verbose {
                     log::error!("{}", error)
                 }
-                return (tokenizer.finish().tokens, lat_lng);
+                return (tokens(address), lat_lng, BlockDetail::default(), None);
             }
         };
-        // 町名の検出
-        let (town_name, tokenizer) = match tokenizer.read_town(city_master.towns) {
-            Ok(found) => found,
-            Err(not_found) => {
-                if options.verbose {
-                    log::error!("町名の検出に失敗しました")
+        // 街区の検出
+        let block_detail = match town_master.find_block(&address.rest) {
+            Some(block) => {
+                lat_lng.replace(block.coordinate.to_lat_lng());
+                BlockDetail {
+                    found: true,
+                    addressing_system: Some(AddressingSystem::from_residential_address_indication(
+                        block.residential_address_indication,
+                    )),
                 }
-                return (not_found.tokens, lat_lng);
             }
+            None => BlockDetail {
+                found: false,
+                addressing_system: town_master
+                    .residential_address_indication()
+                    .map(AddressingSystem::from_residential_address_indication),
+            },
         };
 
-        // 町村マスタの取得
-        if let Ok(town_master) = interactor
-            .get_town_master(&prefecture, &city_name, &town_name)
-            .await
-        {
-            lat_lng.replace(town_master.coordinate.to_lat_lng());
-        };
+        (tokens(address), lat_lng, block_detail, None)
+    }
+}
 
-        (tokenizer.finish().tokens, lat_lng)
+/// `PureParser`が要求する都道府県名は入力から読み取ったものであるため、必ず見つかる
+fn find_prefecture(prefecture_name: &str) -> &'static Prefecture {
+    Prefecture::values()
+        .find(|prefecture| prefecture.name_ja() == prefecture_name)
+        .expect("prefecture name must be valid")
+}
+
+/// 住所マスタの取得に失敗した原因を`PureParser::abort`に渡せる形に変換する
+fn to_error(error: ApiError) -> Error {
+    let message = error.to_string();
+    let source = match error {
+        ApiError::Network { url } => ApiClientError::Request { url, message },
+        ApiError::NotFound { url } => ApiClientError::NotFound { url },
+        ApiError::Deserialize { url } => ApiClientError::Deserialize { url, message },
+    };
+    Error::new_api_error(source)
+}
+
+/// パース結果を検出できた部分までのトークン列に戻す
+fn tokens(address: Address) -> Vec<Token> {
+    let mut tokens = vec![];
+    if let Some(postal_code) = address.postal_code {
+        tokens.push(Token::PostalCode(postal_code));
+    }
+    for (name, token) in [
+        (address.prefecture, Token::Prefecture as fn(String) -> Token),
+        (address.city, Token::City),
+        (address.town, Token::Town),
+    ] {
+        if name.is_empty() {
+            break;
+        }
+        tokens.push(token(name));
     }
+    tokens.push(Token::Rest(address.rest));
+    tokens
 }
 
 #[cfg(test)]
 mod tests {
     use crate::domain::common::token::Token;
-    use crate::experimental::parser::{DataSource, Parser, ParserOptions};
+    use crate::experimental::parser::{AddressingSystem, DataSource, Parser, ParserOptions};
 
     #[tokio::test]
     async fn 都道府県名が誤っている場合() {
@@ -113,7 +181,7 @@ mod tests {
             correct_incomplete_city_names: false,
             verbose: false,
         };
-        let (tokens, _) = parser
+        let (tokens, _, _, _) = parser
             .parse_with_chimeiruiju("奈川県横浜市磯子区洋光台3-10-3", &parser_options)
             .await;
         assert_eq!(
@@ -130,7 +198,7 @@ mod tests {
             correct_incomplete_city_names: false,
             verbose: false,
         };
-        let (tokens, _) = parser
+        let (tokens, _, _, _) = parser
             .parse_with_chimeiruiju("神奈川県横浜県磯子市洋光台3-10-3", &parser_options)
             .await;
         assert_eq!(
@@ -150,7 +218,7 @@ mod tests {
             correct_incomplete_city_names: false,
             verbose: false,
         };
-        let (tokens, _) = parser
+        let (tokens, _, _, _) = parser
             .parse_with_chimeiruiju("神奈川県横浜市磯子区陽光台3-10-3", &parser_options)
             .await;
         assert_eq!(
@@ -163,6 +231,25 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn 街区まで検出できた場合() {
+        let parser = Parser::default();
+        let parser_options = ParserOptions {
+            data_source: DataSource::ChimeiRuiju,
+            correct_incomplete_city_names: false,
+            verbose: false,
+        };
+        let (_, lat_lng, block_detail, _) = parser
+            .parse_with_chimeiruiju("神奈川県横浜市磯子区洋光台3-10-3", &parser_options)
+            .await;
+        assert!(lat_lng.is_some());
+        assert!(block_detail.found);
+        assert_eq!(
+            block_detail.addressing_system,
+            Some(AddressingSystem::ResidentialAddressIndication)
+        );
+    }
+
     #[tokio::test]
     async fn パースに成功した場合() {
         let parser = Parser::default();
@@ -171,7 +258,7 @@ mod tests {
             correct_incomplete_city_names: false,
             verbose: false,
         };
-        let (tokens, _) = parser
+        let (tokens, _, _, _) = parser
             .parse_with_chimeiruiju("神奈川県横浜市磯子区洋光台3-10-3", &parser_options)
             .await;
         assert_eq!(
@@ -1,18 +1,20 @@
 use crate::domain::common::token::Token;
+use crate::domain::geolonia::error::Error;
 use crate::experimental::parser::{Parser, ParserOptions};
 use crate::http::client::ApiClient;
 use crate::interactor::geolonia::{GeoloniaInteractor, GeoloniaInteractorImpl};
 use crate::tokenizer::Tokenizer;
 
-impl<Client: ApiClient> Parser<Client> {
+impl<Client: ApiClient + Clone> Parser<Client> {
     #[inline]
     pub(crate) async fn parse_with_geolonia(
         &self,
         address: &str,
         options: &ParserOptions,
-    ) -> Vec<Token> {
-        let interactor = GeoloniaInteractorImpl::<Client>::default();
-        let tokenizer = Tokenizer::new(address);
+    ) -> (Vec<Token>, Option<Error>) {
+        let mut interactor = GeoloniaInteractorImpl::new(self.api_client.clone());
+        interactor.set_endpoints(&self.endpoints);
+        let tokenizer = Tokenizer::new(address, &self.parse_options);
 
         // 都道府県名の検出
         let (prefecture, tokenizer) = match tokenizer.read_prefecture() {
@@ -21,7 +23,7 @@ impl<Client: ApiClient> Parser<Client> {
                 if options.verbose {
                     log::error!("都道府県名の検出に失敗しました")
                 }
-                return not_found.tokens;
+                return (not_found.tokens, None);
             }
         };
 
@@ -32,7 +34,7 @@ impl<Client: ApiClient> Parser<Client> {
                 if options.verbose {
                     log::error!("{}", error.error_message)
                 }
-                return tokenizer.finish().tokens;
+                return (tokenizer.finish().tokens, Some(error));
             }
         };
         let (city_name, tokenizer) = match tokenizer.read_city(&prefecture_master.cities) {
@@ -46,14 +48,14 @@ impl<Client: ApiClient> Parser<Client> {
                             if options.verbose {
                                 log::error!("市区町村名の検出に失敗しました")
                             }
-                            return not_found.tokens;
+                            return (not_found.tokens, None);
                         }
                     }
                 } else {
                     if options.verbose {
                         log::error!("市区町村名の検出に失敗しました")
                     }
-                    return not_found.finish().tokens;
+                    return (not_found.finish().tokens, None);
                 }
             }
         };
@@ -68,21 +70,23 @@ impl<Client: ApiClient> Parser<Client> {
                 if options.verbose {
                     log::error!("{}", error.error_message)
                 }
-                return tokenizer.finish().tokens;
+                return (tokenizer.finish().tokens, Some(error));
             }
         };
-        let (_, tokenizer) =
-            match tokenizer.read_town(city_master.towns.iter().map(|x| x.name.clone()).collect()) {
-                Ok(found) => found,
-                Err(not_found) => {
-                    if options.verbose {
-                        log::error!("町名の検出に失敗しました")
-                    }
-                    return not_found.tokens;
+        let (_, tokenizer) = match tokenizer.read_town(
+            city_master.towns.iter().map(|x| x.name.clone()).collect(),
+            &self.parse_options,
+        ) {
+            Ok(found) => found,
+            Err(not_found) => {
+                if options.verbose {
+                    log::error!("町名の検出に失敗しました")
                 }
-            };
+                return (not_found.tokens, None);
+            }
+        };
 
-        tokenizer.finish().tokens
+        (tokenizer.finish().tokens, None)
     }
 }
 
@@ -99,7 +103,7 @@ mod tests {
             correct_incomplete_city_names: false,
             verbose: false,
         };
-        let result = parser
+        let (result, _) = parser
             .parse_with_geolonia("奈川県横浜市磯子区洋光台3-10-3", &parser_options)
             .await;
         assert_eq!(
@@ -116,7 +120,7 @@ mod tests {
             correct_incomplete_city_names: false,
             verbose: false,
         };
-        let result = parser
+        let (result, _) = parser
             .parse_with_geolonia("神奈川県横浜県磯子市洋光台3-10-3", &parser_options)
             .await;
         assert_eq!(
@@ -136,7 +140,7 @@ mod tests {
             correct_incomplete_city_names: false,
             verbose: false,
         };
-        let result = parser
+        let (result, _) = parser
             .parse_with_geolonia("神奈川県横浜市磯子区陽光台3-10-3", &parser_options)
             .await;
         assert_eq!(
@@ -157,7 +161,7 @@ mod tests {
             correct_incomplete_city_names: false,
             verbose: false,
         };
-        let result = parser
+        let (result, _) = parser
             .parse_with_geolonia("神奈川県横浜市磯子区洋光台3-10-3", &parser_options)
             .await;
         assert_eq!(
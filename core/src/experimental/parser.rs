@@ -1,9 +1,13 @@
 use crate::domain::common::latlng::LatLng;
 use crate::domain::common::token::Token;
+use crate::domain::geolonia::entity::RestComponents;
+use crate::domain::geolonia::error::Error;
+use crate::formatter::house_number::parse_house_number;
 use crate::http::client::ApiClient;
+use crate::http::endpoints::Endpoints;
 use crate::http::reqwest_client::ReqwestApiClient;
+use crate::parser::ParseOptions;
 use serde::Serialize;
-use std::marker::PhantomData;
 
 /// Data source for Parser
 ///
@@ -61,38 +65,96 @@ impl Default for ParserOptions {
 /// Yet another address parser(experimental)
 ///
 /// 新型の住所パーサーです。試験的な機能のため、予告なしに破壊的変更が入る可能性があります。
-/// 住所マスタとのデータ通信に使用する`ApiClient`を指定したい場合は`Parser#new`メソッドを使用してください。
+/// 住所マスタとのデータ通信に使用する`ApiClient`を指定したい場合は`Parser#with_api_client`メソッドを使用してください。
+/// 指定した`ApiClient`は全ての呼び出しで共有されるため、`CachedApiClient`を渡すと住所マスタの取得結果を使い回せます。
 ///
 /// # Example
 /// ```
 /// use japanese_address_parser::experimental::parser::Parser;
+/// use japanese_address_parser::http::cached_client::CachedApiClient;
+/// use japanese_address_parser::http::client::ApiClient;
 /// use japanese_address_parser::http::reqwest_client::ReqwestApiClient;
 ///
 /// // デフォルトの`ApiClient`を使用する場合
 /// let parser = Parser::default();
 ///
 /// // `ApiClient`を指定する場合
-/// let parser = Parser::<ReqwestApiClient>::new();
+/// let parser = Parser::with_api_client(CachedApiClient::<ReqwestApiClient>::new());
 /// ```
 #[derive(Debug)]
 pub struct Parser<Client: ApiClient = ReqwestApiClient> {
-    _client: PhantomData<Client>,
+    pub(crate) parse_options: ParseOptions,
+    pub(crate) endpoints: Endpoints,
+    pub(crate) api_client: Client,
 }
 
 impl Default for Parser {
     fn default() -> Self {
-        Parser {
-            _client: Default::default(),
-        }
+        Parser::new()
     }
 }
 
 impl<Client: ApiClient> Parser<Client> {
     pub fn new() -> Self {
+        Self::with_api_client(Client::new())
+    }
+
+    /// Constructs a new `Parser` with the given `ApiClient`.
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::experimental::parser::Parser;
+    /// use japanese_address_parser::http::local_directory_client::LocalDirectoryApiClient;
+    ///
+    /// // 住所マスタをローカルディレクトリから読み込む
+    /// let parser = Parser::with_api_client(LocalDirectoryApiClient::with_root("./master-data"));
+    /// ```
+    pub fn with_api_client(api_client: Client) -> Self {
         Parser {
-            _client: PhantomData::<Client>,
+            parse_options: ParseOptions::default(),
+            endpoints: Endpoints::default(),
+            api_client,
         }
     }
+
+    /// Sets the options used for normalizing the input.
+    ///
+    /// 入力の正規化と町名以降の整形に関するオプションを指定します。
+    /// 市区町村名の補正の有無は`ParserOptions::correct_incomplete_city_names`で指定するため、
+    /// `ParseOptions`の`city_name_correction`と`infer_prefecture`は使用されません。
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::experimental::parser::Parser;
+    /// use japanese_address_parser::parser::ParseOptions;
+    ///
+    /// let parser = Parser::default().options(ParseOptions::default().eliminate_whitespaces(true));
+    /// ```
+    pub fn options(mut self, options: ParseOptions) -> Self {
+        self.parse_options = options;
+        self
+    }
+
+    /// Sets the base URLs of the master data.
+    ///
+    /// `DataSource`に応じて、`Endpoints`で指定した取得先から住所マスタを取得します。
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::experimental::parser::Parser;
+    /// use japanese_address_parser::http::endpoints::Endpoints;
+    ///
+    /// let endpoints =
+    ///     Endpoints::default().chimei_ruiju("https://mirror.example.com/chimei-ruiju/{prefecture}");
+    /// let parser = Parser::default().endpoints(endpoints);
+    /// ```
+    pub fn endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+}
+
+impl<Client: ApiClient + Clone> Parser<Client> {
     /// Parse address into [ParsedAddress].
     ///
     /// 住所をパースし、[ParsedAddress]を返します。
@@ -146,10 +208,21 @@ impl<Client: ApiClient> Parser<Client> {
     ) -> ParsedAddress {
         match options.data_source {
             DataSource::ChimeiRuiju => {
-                ParsedAddress::from(self.parse_with_chimeiruiju(address, options).await)
+                let (tokens, lat_lng, block_detail, error) =
+                    self.parse_with_chimeiruiju(address, options).await;
+                let mut parsed_address = ParsedAddress::from((tokens, lat_lng));
+                if block_detail.found {
+                    parsed_address.metadata.depth = 4;
+                }
+                parsed_address.metadata.addressing_system = block_detail.addressing_system;
+                parsed_address.metadata.error = error;
+                parsed_address
             }
             DataSource::Geolonia => {
-                ParsedAddress::from(self.parse_with_geolonia(address, options).await)
+                let (tokens, error) = self.parse_with_geolonia(address, options).await;
+                let mut parsed_address = ParsedAddress::from(tokens);
+                parsed_address.metadata.error = error;
+                parsed_address
             }
         }
     }
@@ -165,6 +238,10 @@ pub struct ParsedAddress {
     pub town: String,
     /// それ以降
     pub rest: String,
+    /// それ以降を街区符号・住居番号・建物名・部屋番号に分解したもの
+    ///
+    /// 町名まで検出できた場合のみ値が入ります。
+    pub rest_components: RestComponents,
     /// メタデータ
     pub metadata: Metadata,
 }
@@ -187,7 +264,38 @@ pub struct Metadata {
     /// - `1`: 都道府県名までは検出できた場合
     /// - `2`: 市区町村名までは検出できた場合
     /// - `3`: 町名まで検出できた場合
+    /// - `4`: 街区符号・地番まで検出できた場合(`DataSource::ChimeiRuiju`を指定した場合のみ)
     pub depth: u8,
+    /// 住所の表示方式
+    ///
+    /// `DataSource::ChimeiRuiju`を指定し、町名まで検出できた場合のみ値が入ります。
+    pub addressing_system: Option<AddressingSystem>,
+    /// 住所マスタの取得に失敗した場合のエラー
+    ///
+    /// 住所マスタを取得できずにパースを中断した場合のみ値が入ります。
+    /// この場合の`depth`は、住所が誤っている場合と同様に中断した時点までの深度を表します。
+    pub error: Option<Error>,
+}
+
+/// How addresses are numbered in a town
+///
+/// 町内の住所が住居表示によるものか、地番によるものかを表します。
+#[derive(Debug, PartialEq, Serialize, Clone, Copy)]
+pub enum AddressingSystem {
+    /// 住居表示(街区符号・住居番号)
+    ResidentialAddressIndication,
+    /// 地番
+    LotNumber,
+}
+
+impl AddressingSystem {
+    pub(crate) fn from_residential_address_indication(indication: bool) -> Self {
+        if indication {
+            Self::ResidentialAddressIndication
+        } else {
+            Self::LotNumber
+        }
+    }
 }
 
 impl From<Vec<Token>> for ParsedAddress {
@@ -201,15 +309,20 @@ impl From<Vec<Token>> for ParsedAddress {
             city: "".to_string(),
             town: "".to_string(),
             rest: "".to_string(),
+            rest_components: RestComponents::default(),
             metadata: Metadata {
                 latitude: None,
                 longitude: None,
                 depth: 0,
+                addressing_system: None,
+                error: None,
             },
         };
 
         for token in value {
             match token {
+                // 郵便番号は現在のところ出力に含めない
+                Token::PostalCode(_) | Token::Koaza(_) => {}
                 Token::Prefecture(prefecture_name) => {
                     parsed_address.prefecture = prefecture_name;
                     parsed_address.metadata.depth = 1;
@@ -227,6 +340,9 @@ impl From<Vec<Token>> for ParsedAddress {
                 }
             }
         }
+        if parsed_address.metadata.depth == 3 {
+            parsed_address.rest_components = parse_house_number(&parsed_address.rest);
+        }
 
         parsed_address
     }
@@ -247,7 +363,40 @@ impl From<(Vec<Token>, Option<LatLng>)> for ParsedAddress {
 mod tests {
     use crate::domain::common::latlng::LatLng;
     use crate::domain::common::token::Token;
-    use crate::experimental::parser::{Metadata, ParsedAddress};
+    use crate::domain::geolonia::entity::RestComponents;
+    use crate::domain::geolonia::error::ErrorKind;
+    use crate::experimental::parser::{DataSource, Metadata, ParsedAddress, Parser, ParserOptions};
+    use crate::http::mock_client::MockApiClient;
+
+    #[tokio::test]
+    async fn 指定したapi_clientで住所マスタを取得すること() {
+        let client = MockApiClient::default();
+        let parser = Parser::with_api_client(client.clone());
+        let result = parser.parse("東京都新宿区西新宿2-8-1").await;
+        assert_eq!(result.prefecture, "東京都");
+        assert_eq!(result.metadata.depth, 1);
+        assert_eq!(client.count(), 1);
+    }
+
+    #[tokio::test]
+    async fn 住所マスタを取得できない場合はエラーを返すこと() {
+        let parser = Parser::with_api_client(MockApiClient::default());
+        for data_source in [DataSource::Geolonia, DataSource::ChimeiRuiju] {
+            let options = ParserOptions {
+                data_source,
+                correct_incomplete_city_names: false,
+                verbose: false,
+            };
+            let result = parser
+                .parse_with_options("東京都新宿区西新宿2-8-1", &options)
+                .await;
+            assert_eq!(result.metadata.depth, 1);
+            assert!(matches!(
+                result.metadata.error.map(|error| error.kind),
+                Some(ErrorKind::Fetch { .. })
+            ));
+        }
+    }
 
     #[test]
     fn conversion_depthが0() {
@@ -262,10 +411,13 @@ mod tests {
                 city: "".to_string(),
                 town: "".to_string(),
                 rest: "新浜県新浜市ニューポートシティ1-1-1".to_string(),
+                rest_components: RestComponents::default(),
                 metadata: Metadata {
                     latitude: None,
                     longitude: None,
                     depth: 0,
+                    addressing_system: None,
+                    error: None,
                 },
             }
         )
@@ -289,10 +441,13 @@ mod tests {
                 city: "".to_string(),
                 town: "".to_string(),
                 rest: "".to_string(),
+                rest_components: RestComponents::default(),
                 metadata: Metadata {
                     latitude: Some(139.748264),
                     longitude: Some(35.68532),
                     depth: 1,
+                    addressing_system: None,
+                    error: None,
                 },
             }
         )
@@ -317,10 +472,13 @@ mod tests {
                 city: "台東区".to_string(),
                 town: "".to_string(),
                 rest: "".to_string(),
+                rest_components: RestComponents::default(),
                 metadata: Metadata {
                     latitude: Some(139.764379),
                     longitude: Some(35.711162),
                     depth: 2,
+                    addressing_system: None,
+                    error: None,
                 },
             }
         )
@@ -346,10 +504,18 @@ mod tests {
                 city: "文京区".to_string(),
                 town: "本駒込六丁目".to_string(),
                 rest: "16-3".to_string(),
+                rest_components: RestComponents {
+                    block_number: Some("16".to_string()),
+                    house_number: Some("3".to_string()),
+                    building_name: None,
+                    room_number: None,
+                },
                 metadata: Metadata {
                     latitude: Some(139.738043),
                     longitude: Some(35.72791),
                     depth: 3,
+                    addressing_system: None,
+                    error: None,
                 },
             }
         )
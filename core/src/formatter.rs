@@ -3,6 +3,7 @@ pub(crate) mod fullwidth_character;
 pub(crate) mod halfwidth_character;
 pub(crate) mod house_number;
 pub(crate) mod informal_town_name_notation;
+pub(crate) mod postal_code;
 pub(crate) mod prepend_aza;
 pub(crate) mod prepend_oaza;
 
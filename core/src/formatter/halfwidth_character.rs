@@ -1,5 +1,32 @@
 pub(crate) fn format_halfwidth_katakana(input: &str) -> String {
+    // 濁点・半濁点を含む組み合わせを先に変換しないと、清音と濁点が別々の文字に変換されてしまう
     input
+        .replace("ｶﾞ", "ガ")
+        .replace("ｷﾞ", "ギ")
+        .replace("ｸﾞ", "グ")
+        .replace("ｹﾞ", "ゲ")
+        .replace("ｺﾞ", "ゴ")
+        .replace("ｻﾞ", "ザ")
+        .replace("ｼﾞ", "ジ")
+        .replace("ｽﾞ", "ズ")
+        .replace("ｾﾞ", "ゼ")
+        .replace("ｿﾞ", "ゾ")
+        .replace("ﾀﾞ", "ダ")
+        .replace("ﾁﾞ", "ヂ")
+        .replace("ﾂﾞ", "ヅ")
+        .replace("ﾃﾞ", "デ")
+        .replace("ﾄﾞ", "ド")
+        .replace("ﾊﾞ", "バ")
+        .replace("ﾋﾞ", "ビ")
+        .replace("ﾌﾞ", "ブ")
+        .replace("ﾍﾞ", "ベ")
+        .replace("ﾎﾞ", "ボ")
+        .replace("ﾊﾟ", "パ")
+        .replace("ﾋﾟ", "ピ")
+        .replace("ﾌﾟ", "プ")
+        .replace("ﾍﾟ", "ペ")
+        .replace("ﾎﾟ", "ポ")
+        .replace("ｳﾞ", "ヴ")
         .replace("ｱ", "ア")
         .replace("ｲ", "イ")
         .replace("ｳ", "ウ")
@@ -55,32 +82,6 @@ pub(crate) fn format_halfwidth_katakana(input: &str) -> String {
         .replace("ｬ", "ャ")
         .replace("ｭ", "ュ")
         .replace("ｮ", "ョ")
-        .replace("ｶﾞ", "ガ")
-        .replace("ｷﾞ", "ギ")
-        .replace("ｸﾞ", "グ")
-        .replace("ｹﾞ", "ゲ")
-        .replace("ｺﾞ", "ゴ")
-        .replace("ｻﾞ", "ザ")
-        .replace("ｼﾞ", "ジ")
-        .replace("ｽﾞ", "ズ")
-        .replace("ｾﾞ", "ゼ")
-        .replace("ｿﾞ", "ゾ")
-        .replace("ﾀﾞ", "ダ")
-        .replace("ﾁﾞ", "ヂ")
-        .replace("ﾂﾞ", "ヅ")
-        .replace("ﾃﾞ", "デ")
-        .replace("ﾄﾞ", "ド")
-        .replace("ﾊﾞ", "バ")
-        .replace("ﾋﾞ", "ビ")
-        .replace("ﾌﾞ", "ブ")
-        .replace("ﾍﾞ", "ベ")
-        .replace("ﾎﾞ", "ボ")
-        .replace("ﾊﾟ", "パ")
-        .replace("ﾋﾟ", "ピ")
-        .replace("ﾌﾟ", "プ")
-        .replace("ﾍﾟ", "ペ")
-        .replace("ﾎﾟ", "ポ")
-        .replace("ｳﾞ", "ヴ")
 }
 
 #[cfg(test)]
@@ -92,4 +93,11 @@ mod tests {
         assert_eq!(format_halfwidth_katakana("堀ﾉ内"), "堀ノ内");
         assert_eq!(format_halfwidth_katakana("鶴ｹ岡"), "鶴ケ岡");
     }
+
+    #[test]
+    fn format_halfwidth_katakana_濁点と半濁点() {
+        assert_eq!(format_halfwidth_katakana("ｶﾞ"), "ガ");
+        assert_eq!(format_halfwidth_katakana("ﾎﾟﾝﾌﾟ"), "ポンプ");
+        assert_eq!(format_halfwidth_katakana("ｳﾞｨ"), "ヴィ");
+    }
 }
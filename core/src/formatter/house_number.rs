@@ -1,3 +1,4 @@
+use crate::domain::geolonia::entity::RestComponents;
 use std::sync::LazyLock;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -53,9 +54,124 @@ pub(crate) fn format_house_number(input: &str) -> Result<String, &'static str> {
     Ok(format!("{}番{}号{}", block_number, house_number, rest))
 }
 
+/// 区切り文字として扱うハイフン類
+pub(crate) const HYPHENS: [char; 12] = [
+    '-', '\u{2010}', '\u{2011}', '\u{2012}', '\u{2013}', '\u{2014}', '\u{2015}', '\u{2212}',
+    '\u{30FC}', '\u{FF0D}', '\u{FF70}', 'の',
+];
+
+/// 部屋番号・階数の後ろに付く接尾辞(長いものから順に判定する)
+const ROOM_SUFFIXES: [&str; 6] = ["号室", "号", "階", "F", "Ｆ", "f"];
+
+/// 町名以降の文字列を街区符号・住居番号・建物名・部屋番号に分解する
+///
+/// `1-1`、`1番1号`、`1798番地1`、`123番地の4`のような表記に対応する。
+/// 先頭が数字で始まらない場合は、いずれの要素も検出できなかったものとして扱う。
+pub(crate) fn parse_house_number(input: &str) -> RestComponents {
+    let mut components = RestComponents::default();
+    let rest = input.trim_start();
+
+    let (block_number, rest) = take_digits(rest);
+    if block_number.is_empty() {
+        return components;
+    }
+    components.block_number = Some(block_number.to_string());
+    let rest = match strip_any_prefix(rest, &["番地", "番"]) {
+        // `123番地の4`のように、番地の後ろにハイフンや`の`を挟んで枝番が続く場合がある
+        Some(rest) => strip_hyphen(rest)
+            .filter(|after_hyphen| after_hyphen.starts_with(is_digit))
+            .unwrap_or(rest),
+        None => strip_hyphen(rest).unwrap_or(rest),
+    };
+
+    let (house_number, rest) = take_digits(rest);
+    let rest = if house_number.is_empty() {
+        rest
+    } else {
+        components.house_number = Some(house_number.to_string());
+        strip_any_prefix(rest, &["号", "番地", "番"]).unwrap_or(rest)
+    };
+
+    // `2-8-1`のように住居番号の後ろにハイフン区切りで数字が続く場合は、後ろに何が続くかによらず枝番の続きとして扱う
+    // 部屋番号は`101号室`のような接尾辞付きの表記か、建物名の後ろに続く数字からのみ検出する
+    let mut rest = rest;
+    if let (Some(house_number), Some(after_hyphen)) =
+        (components.house_number.as_mut(), strip_hyphen(rest))
+    {
+        let (digits, remaining) = take_digits(after_hyphen);
+        if !digits.is_empty() {
+            house_number.push('-');
+            house_number.push_str(digits);
+            rest = remaining;
+        }
+    }
+
+    let rest = rest.trim_matches(is_separator);
+    if rest.is_empty() {
+        return components;
+    }
+    match split_room_number(rest) {
+        Some((building_name, room_number)) => {
+            if !building_name.is_empty() {
+                components.building_name = Some(building_name.to_string());
+            }
+            components.room_number = Some(room_number.to_string());
+        }
+        None => components.building_name = Some(rest.to_string()),
+    }
+    components
+}
+
+/// 先頭の連続する数字(全角数字を含む)とそれ以降に分割する
+fn take_digits(input: &str) -> (&str, &str) {
+    let end = input
+        .char_indices()
+        .find(|(_, c)| !is_digit(*c))
+        .map_or(input.len(), |(index, _)| index);
+    input.split_at(end)
+}
+
+fn is_digit(c: char) -> bool {
+    c.is_ascii_digit() || ('０'..='９').contains(&c)
+}
+
+/// 建物名の前後から取り除く文字
+///
+/// 長音記号や`の`は建物名の一部である可能性があるため含めない。
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '-' | '\u{2010}'..='\u{2015}' | '\u{2212}' | '\u{FF0D}')
+}
+
+fn strip_hyphen(input: &str) -> Option<&str> {
+    input.strip_prefix(|c: char| HYPHENS.contains(&c))
+}
+
+fn strip_any_prefix<'a>(input: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+    prefixes
+        .iter()
+        .find_map(|prefix| input.strip_prefix(prefix))
+}
+
+/// 末尾の部屋番号(`101号室`、`3F`など)を検出し、建物名と部屋番号に分割する
+fn split_room_number(input: &str) -> Option<(&str, &str)> {
+    let without_suffix = ROOM_SUFFIXES
+        .iter()
+        .find_map(|suffix| input.strip_suffix(suffix))
+        .unwrap_or(input);
+    let start = without_suffix
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_digit(*c))
+        .last()
+        .map(|(index, _)| index)?;
+    let building_name = input[..start].trim_end_matches(is_separator);
+    Some((building_name, &input[start..]))
+}
+
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
-    use crate::formatter::house_number::format_house_number;
+    use crate::domain::geolonia::entity::RestComponents;
+    use crate::formatter::house_number::{format_house_number, parse_house_number};
 
     #[test]
     fn format_house_number_1番1号() {
@@ -70,6 +186,129 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "3番2号レジデンシャルマンション101号室");
     }
+
+    fn components(
+        block_number: Option<&str>,
+        house_number: Option<&str>,
+        building_name: Option<&str>,
+        room_number: Option<&str>,
+    ) -> RestComponents {
+        RestComponents {
+            block_number: block_number.map(String::from),
+            house_number: house_number.map(String::from),
+            building_name: building_name.map(String::from),
+            room_number: room_number.map(String::from),
+        }
+    }
+
+    #[test]
+    fn parse_house_number_ハイフン区切り() {
+        assert_eq!(
+            parse_house_number("8-20"),
+            components(Some("8"), Some("20"), None, None)
+        );
+    }
+
+    #[test]
+    fn parse_house_number_番と号() {
+        assert_eq!(
+            parse_house_number("5番15号"),
+            components(Some("5"), Some("15"), None, None)
+        );
+    }
+
+    #[test]
+    fn parse_house_number_番地と枝番() {
+        assert_eq!(
+            parse_house_number("1798番地1"),
+            components(Some("1798"), Some("1"), None, None)
+        );
+        assert_eq!(
+            parse_house_number("2780番地"),
+            components(Some("2780"), None, None, None)
+        );
+    }
+
+    #[test]
+    fn parse_house_number_番地の後ろにハイフンやのを挟んだ枝番() {
+        assert_eq!(
+            parse_house_number("123番地の4"),
+            components(Some("123"), Some("4"), None, None)
+        );
+        assert_eq!(
+            parse_house_number("123番の4"),
+            components(Some("123"), Some("4"), None, None)
+        );
+        assert_eq!(
+            parse_house_number("123番地-4 メゾン青葉201号室"),
+            components(Some("123"), Some("4"), Some("メゾン青葉"), Some("201号室"))
+        );
+        assert_eq!(
+            parse_house_number("123番地の森ビル"),
+            components(Some("123"), None, Some("の森ビル"), None)
+        );
+    }
+
+    #[test]
+    fn parse_house_number_建物名と部屋番号() {
+        assert_eq!(
+            parse_house_number("3-2レジデンシャルマンション101号室"),
+            components(
+                Some("3"),
+                Some("2"),
+                Some("レジデンシャルマンション"),
+                Some("101号室")
+            )
+        );
+        assert_eq!(
+            parse_house_number("8-1 東京都庁第一本庁舎 32F"),
+            components(
+                Some("8"),
+                Some("1"),
+                Some("東京都庁第一本庁舎"),
+                Some("32F")
+            )
+        );
+        assert_eq!(
+            parse_house_number("2-8-1 東京都庁第一本庁舎"),
+            components(Some("2"), Some("8-1"), Some("東京都庁第一本庁舎"), None)
+        );
+    }
+
+    #[test]
+    fn parse_house_number_住居番号の後ろにハイフン区切りで数字が続く場合() {
+        // 後ろに建物名が続くかどうかによらず、枝番の続きとして扱う
+        assert_eq!(
+            parse_house_number("2-8-1"),
+            components(Some("2"), Some("8-1"), None, None)
+        );
+        assert_eq!(
+            parse_house_number("3-2-101"),
+            components(Some("3"), Some("2-101"), None, None)
+        );
+        assert_eq!(
+            parse_house_number("3-2-101 メゾン青葉"),
+            components(Some("3"), Some("2-101"), Some("メゾン青葉"), None)
+        );
+    }
+
+    #[test]
+    fn parse_house_number_建物名のみ() {
+        assert_eq!(
+            parse_house_number("1-1 霞が関ビル"),
+            components(Some("1"), Some("1"), Some("霞が関ビル"), None)
+        );
+        assert_eq!(
+            parse_house_number("1-2 サンシャインセンター"),
+            components(Some("1"), Some("2"), Some("サンシャインセンター"), None)
+        );
+    }
+
+    #[test]
+    fn parse_house_number_数字で始まらない場合() {
+        assert_eq!(parse_house_number("新島8番地"), RestComponents::default());
+        assert_eq!(parse_house_number(""), RestComponents::default());
+    }
 }
 
 #[cfg(all(test, target_arch = "wasm32"))]
@@ -0,0 +1,116 @@
+use crate::formatter::house_number::HYPHENS;
+
+/// 郵便番号の前に付く記号
+const POSTAL_MARK: char = '〒';
+
+/// 文字列の先頭に記載された郵便番号を読み取り、`NNN-NNNN`形式の郵便番号と残りの文字列を返す
+///
+/// `〒123-4567`、`123-4567`、`1234567`のような表記に対応する。全角数字も受け付ける。
+/// 郵便番号の後ろに続く空白は残りの文字列に含めない。
+pub(crate) fn read_postal_code(input: &str) -> Option<(String, &str)> {
+    let body = match input.strip_prefix(POSTAL_MARK) {
+        Some(body) => body.trim_start_matches(is_blank),
+        None => input,
+    };
+    let mut digits = String::with_capacity(7);
+    let mut hyphen_found = false;
+    let mut end = body.len();
+    for (index, c) in body.char_indices() {
+        match to_ascii_digit(c) {
+            Some(digit) if digits.len() < 7 => digits.push(digit),
+            None if digits.len() == 3 && !hyphen_found && is_postal_code_hyphen(c) => {
+                hyphen_found = true
+            }
+            _ => {
+                end = index;
+                break;
+            }
+        }
+    }
+    if digits.len() != 7 || body[end..].starts_with(|c: char| to_ascii_digit(c).is_some()) {
+        return None;
+    }
+    let rest = body[end..].trim_start_matches(is_blank);
+    Some((format!("{}-{}", &digits[..3], &digits[3..]), rest))
+}
+
+/// 郵便番号を`NNN-NNNN`形式に揃える
+///
+/// 郵便番号として解釈できない場合は`None`を返す。
+pub(crate) fn normalize_postal_code(input: &str) -> Option<String> {
+    match read_postal_code(input.trim()) {
+        Some((postal_code, "")) => Some(postal_code),
+        _ => None,
+    }
+}
+
+/// 半角・全角の数字を半角数字に変換する
+pub(crate) fn to_ascii_digit(c: char) -> Option<char> {
+    match c {
+        '0'..='9' => Some(c),
+        '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32),
+        _ => None,
+    }
+}
+
+pub(crate) fn is_postal_code_hyphen(c: char) -> bool {
+    c != 'の' && HYPHENS.contains(&c)
+}
+
+pub(crate) fn is_blank(c: char) -> bool {
+    c == ' ' || c == '\u{3000}'
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::formatter::postal_code::{normalize_postal_code, read_postal_code};
+
+    #[test]
+    fn read_postal_code_郵便番号記号付き() {
+        assert_eq!(
+            read_postal_code("〒100-0001 東京都千代田区千代田1-1"),
+            Some(("100-0001".to_string(), "東京都千代田区千代田1-1"))
+        );
+    }
+
+    #[test]
+    fn read_postal_code_ハイフンなし() {
+        assert_eq!(
+            read_postal_code("1000001東京都千代田区千代田1-1"),
+            Some(("100-0001".to_string(), "東京都千代田区千代田1-1"))
+        );
+    }
+
+    #[test]
+    fn read_postal_code_全角数字() {
+        assert_eq!(
+            read_postal_code("〒　１００－０００１　東京都千代田区"),
+            Some(("100-0001".to_string(), "東京都千代田区"))
+        );
+    }
+
+    #[test]
+    fn read_postal_code_郵便番号がない場合() {
+        assert_eq!(read_postal_code("東京都千代田区千代田1-1"), None);
+        assert_eq!(read_postal_code("10-00001東京都"), None);
+        assert_eq!(read_postal_code("10000012東京都"), None);
+    }
+
+    #[test]
+    fn normalize_postal_code_成功() {
+        assert_eq!(
+            normalize_postal_code("〒1000001"),
+            Some("100-0001".to_string())
+        );
+        assert_eq!(
+            normalize_postal_code(" 100-0001 "),
+            Some("100-0001".to_string())
+        );
+    }
+
+    #[test]
+    fn normalize_postal_code_失敗() {
+        assert_eq!(normalize_postal_code("100-0001東京都"), None);
+        assert_eq!(normalize_postal_code("100-001"), None);
+    }
+}
@@ -1,4 +1,12 @@
+pub mod cache;
 pub mod cached_client;
 pub mod client;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod disk_cache;
+pub mod endpoints;
 pub mod error;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod local_directory_client;
+#[cfg(test)]
+pub(crate) mod mock_client;
 pub mod reqwest_client;
@@ -0,0 +1,227 @@
+pub use crate::util::inmemory_cache::InMemoryCache;
+
+/// Validators to revalidate a cached response
+///
+/// 前回取得したレスポンスの`ETag`と`Last-Modified`ヘッダーの値です。
+/// キャッシュの保持期間を過ぎた後、条件付きリクエストで内容が変わっていないことを確認するために使用します。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+    /// `ETag`ヘッダーの値
+    pub etag: Option<String>,
+    /// `Last-Modified`ヘッダーの値
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    /// 条件付きリクエストに使用できる値を持たない場合は`true`を返します。
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// Result of a conditional request
+///
+/// `ApiClient::fetch_revalidate`の戻り値です。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Revalidation {
+    /// 前回取得した内容から変更されていない(`304 Not Modified`)
+    NotModified,
+    /// 変更されていたため、新しい内容を取得した
+    Modified {
+        /// レスポンスボディ
+        body: Vec<u8>,
+        /// 次回の再検証に使用する値
+        validators: Validators,
+    },
+}
+
+/// Cached response body
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheEntry {
+    /// データ
+    pub body: Vec<u8>,
+    /// 再検証に使用する値
+    pub validators: Validators,
+}
+
+/// Storage for `CachedApiClient`
+///
+/// `CachedApiClient`が取得したデータを保存する先はこのトレイトを実装する必要があります。
+/// 保持期間や容量の管理は実装側で行います。
+pub trait CacheStore {
+    /// Returns the entry if it is within the retention period
+    ///
+    /// 保持期間内のエントリを返します。
+    fn get(&self, key: &str) -> Option<CacheEntry>;
+
+    /// Returns the entry even if the retention period has passed
+    ///
+    /// 保持期間を過ぎたエントリも含めて返します。再検証に使用するため、削除済みの場合は`None`を返します。
+    fn get_stale(&self, key: &str) -> Option<CacheEntry>;
+
+    /// Stores the entry and restarts its retention period
+    ///
+    /// エントリを保存し、保持期間を登録した時点から数え直します。
+    fn insert(&self, key: &str, entry: CacheEntry);
+
+    /// Removes the entry
+    ///
+    /// エントリを削除します。保持期間を過ぎたエントリも再検証に使用できなくなります。
+    fn remove(&self, key: &str);
+
+    /// Removes all entries
+    ///
+    /// 全てのエントリを削除します。
+    fn clear(&self);
+
+    /// Returns the number of entries removed to stay within the size limit
+    ///
+    /// 最大容量を超えたために削除したエントリの件数を返します。既定の実装は常に`0`を返します。
+    fn evictions(&self) -> u64 {
+        0
+    }
+
+    /// Returns the maximum number of entries, or `None` if unbounded
+    ///
+    /// 保存できるエントリの最大件数を返します。件数で容量を制限しない場合は`None`を返します。既定の実装は`None`を返します。
+    fn max_entries(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// `CacheStore` that combines two stores
+///
+/// 先に`front`を参照し、見つからない場合は`back`を参照します。`back`で見つかったエントリは`front`にも登録します。
+/// `InMemoryCache`と`DiskCache`を組み合わせることで、プロセス内ではメモリから、
+/// プロセスをまたいではディスクからキャッシュを読み込めます。
+///
+/// ```rust
+/// use japanese_address_parser::http::cache::{InMemoryCache, TieredCache};
+/// use japanese_address_parser::http::cached_client::CachedApiClient;
+/// use japanese_address_parser::http::disk_cache::DiskCache;
+/// use japanese_address_parser::http::reqwest_client::ReqwestApiClient;
+///
+/// let store = TieredCache::new(InMemoryCache::new(), DiskCache::new("./cache"));
+/// let client = CachedApiClient::with_store(ReqwestApiClient::default(), store);
+/// ```
+#[derive(Default)]
+pub struct TieredCache<Front: CacheStore, Back: CacheStore> {
+    front: Front,
+    back: Back,
+}
+
+impl<Front: CacheStore, Back: CacheStore> TieredCache<Front, Back> {
+    pub fn new(front: Front, back: Back) -> Self {
+        Self { front, back }
+    }
+}
+
+impl<Front: CacheStore, Back: CacheStore> CacheStore for TieredCache<Front, Back> {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        if let Some(entry) = self.front.get(key) {
+            return Some(entry);
+        }
+        let entry = self.back.get(key)?;
+        self.front.insert(key, entry.clone());
+        Some(entry)
+    }
+
+    fn get_stale(&self, key: &str) -> Option<CacheEntry> {
+        self.front
+            .get_stale(key)
+            .or_else(|| self.back.get_stale(key))
+    }
+
+    fn insert(&self, key: &str, entry: CacheEntry) {
+        self.front.insert(key, entry.clone());
+        self.back.insert(key, entry);
+    }
+
+    fn remove(&self, key: &str) {
+        self.front.remove(key);
+        self.back.remove(key);
+    }
+
+    fn clear(&self) {
+        self.front.clear();
+        self.back.clear();
+    }
+
+    /// 前段と後段で削除した件数の合計を返します。
+    fn evictions(&self) -> u64 {
+        self.front.evictions() + self.back.evictions()
+    }
+
+    /// 前段で見つからなくても後段から読み込めるため、大きい方の件数を返します。
+    fn max_entries(&self) -> Option<usize> {
+        Option::zip(self.front.max_entries(), self.back.max_entries())
+            .map(|(front, back)| front.max(back))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::http::cache::{CacheEntry, CacheStore, InMemoryCache, TieredCache};
+    use std::time::Duration;
+
+    fn entry(body: &[u8]) -> CacheEntry {
+        CacheEntry {
+            body: body.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tiered_cache_後段で見つかったエントリを前段にも登録すること() {
+        let cache = TieredCache::new(InMemoryCache::new(), InMemoryCache::new());
+        cache.back.insert("key1", entry(&[1, 2, 3]));
+
+        assert_eq!(cache.get("key1"), Some(entry(&[1, 2, 3])));
+        assert_eq!(cache.front.get("key1"), Some(entry(&[1, 2, 3])));
+    }
+
+    #[test]
+    fn tiered_cache_両方に登録すること() {
+        let cache = TieredCache::new(InMemoryCache::new(), InMemoryCache::new());
+        cache.insert("key1", entry(&[4, 5, 6]));
+
+        assert_eq!(cache.front.get("key1"), Some(entry(&[4, 5, 6])));
+        assert_eq!(cache.back.get("key1"), Some(entry(&[4, 5, 6])));
+    }
+
+    #[test]
+    fn tiered_cache_保持期間を過ぎたエントリを後段から返すこと() {
+        let cache = TieredCache::new(
+            InMemoryCache::with_config(Duration::ZERO, 10),
+            InMemoryCache::with_config(Duration::ZERO, 10),
+        );
+        cache.back.insert("key1", entry(&[7, 8, 9]));
+
+        assert_eq!(cache.get("key1"), None);
+        assert_eq!(cache.get_stale("key1"), Some(entry(&[7, 8, 9])));
+    }
+
+    #[test]
+    fn tiered_cache_最大件数() {
+        let cache = TieredCache::new(
+            InMemoryCache::with_config(Duration::ZERO, 10),
+            InMemoryCache::with_config(Duration::ZERO, 50),
+        );
+        assert_eq!(cache.max_entries(), Some(50));
+    }
+
+    #[test]
+    fn tiered_cache_両方から削除すること() {
+        let cache = TieredCache::new(InMemoryCache::new(), InMemoryCache::new());
+        cache.insert("key1", entry(&[1]));
+        cache.insert("key2", entry(&[2]));
+
+        cache.remove("key1");
+        assert_eq!(cache.front.get_stale("key1"), None);
+        assert_eq!(cache.back.get_stale("key1"), None);
+
+        cache.clear();
+        assert_eq!(cache.front.get_stale("key2"), None);
+        assert_eq!(cache.back.get_stale("key2"), None);
+    }
+}
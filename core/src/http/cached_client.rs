@@ -1,11 +1,124 @@
+use crate::domain::geolonia::error::Error;
+use crate::http::cache::{CacheEntry, CacheStore, InMemoryCache, Revalidation};
 use crate::http::client::ApiClient;
+use crate::http::endpoints::Endpoints;
 use crate::http::error::ApiClientError;
-use crate::util::inmemory_cache::InMemoryCache;
+use crate::source::{AddressMasterSource, ApiClientSource};
+use futures::lock::Mutex as AsyncMutex;
+use futures::stream::{self, StreamExt, TryStreamExt};
 use serde::de::DeserializeOwned;
-use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-/// Wrapper of `ApiClient` that enables in-memory cache
+/// `warm`で市区町村の住所マスタを並行して取得する件数
+const WARM_CONCURRENCY: usize = 8;
+
+/// Statistics of `CachedApiClient`
+///
+/// `CachedApiClient::stats`の戻り値です。件数は`CachedApiClient`を生成してからの累計です。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// 保持期間内のキャッシュを利用した回数
+    pub hits: u64,
+    /// キャッシュを利用できず、取得または再検証を行った回数
+    pub misses: u64,
+    /// 再検証の結果、変更がなかったため保存済みのデータを利用した回数
+    pub not_modified: u64,
+    /// `misses`のうち、同じURLを取得中だった他の呼び出しの結果を利用した回数
+    pub coalesced: u64,
+    /// 最大容量を超えたために保存先から削除したエントリの件数
+    pub evictions: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    not_modified: AtomicU64,
+    coalesced: AtomicU64,
+}
+
+/// 取得結果を共有するためのロック
+///
+/// 最初にリクエストを行った呼び出しが、失敗した場合も含めて結果を格納する。
+/// 結果を共有するのは格納した時点で待っていた呼び出しのみで、それ以降の呼び出しは新たにリクエストを行う。
+/// 結果が格納される前にその呼び出しが中断された場合は、待っていた呼び出しがリクエストを行う。
+type FlightLock = Arc<AsyncMutex<Option<Result<CacheEntry, ApiClientError>>>>;
+
+/// 取得中のURLごとのロック
+///
+/// 同じURLを同時に取得しようとした場合に、最初の呼び出しだけがリクエストを行い、
+/// 残りはその結果を待って共有するために使用する。
+/// 非同期ランタイムに依存しないロックを使用するため、tokioとwasm32のどちらでも動作する。
+#[derive(Default)]
+struct InFlight {
+    locks: Mutex<HashMap<String, FlightLock>>,
+}
+
+impl InFlight {
+    fn join(&self, url: &str) -> Flight<'_> {
+        let mut locks = self
+            .locks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let lock = locks.entry(url.to_string()).or_default().clone();
+        Flight {
+            in_flight: self,
+            url: url.to_string(),
+            lock,
+        }
+    }
+
+    /// `lock`がまだ`url`の取得中のロックとして登録されていれば取り除く
+    fn remove(&self, url: &str, lock: &FlightLock) {
+        let mut locks = self
+            .locks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if locks
+            .get(url)
+            .is_some_and(|current| Arc::ptr_eq(current, lock))
+        {
+            locks.remove(url);
+        }
+    }
+}
+
+/// 取得中のURLへの参加を表し、最後の参加者が抜けるときにロックを片付ける
+struct Flight<'a> {
+    in_flight: &'a InFlight,
+    url: String,
+    lock: FlightLock,
+}
+
+impl Flight<'_> {
+    /// 結果を格納した後に呼び出し、以降の呼び出しがこの結果を使い回さないようにする
+    fn finish(&self) {
+        self.in_flight.remove(&self.url, &self.lock);
+    }
+}
+
+impl Drop for Flight<'_> {
+    fn drop(&mut self) {
+        // 自身と`locks`以外に参照がなければ、待っている呼び出しはない
+        if Arc::strong_count(&self.lock) == 2 {
+            self.in_flight.remove(&self.url, &self.lock);
+        }
+    }
+}
+
+/// Wrapper of `ApiClient` that enables cache
+///
+/// 取得したデータを`CacheStore`に保存し、保持期間内は再取得せずに利用します。
+/// 保持期間を過ぎたデータは`ETag`/`Last-Modified`による条件付きリクエストで再検証し、変更がなければそのまま利用します。
+/// 保存先の初期値は`InMemoryCache`です。`with_store`で`DiskCache`や`TieredCache`を指定できます。
+///
+/// 複製したインスタンスは同じ保存先と統計情報を共有します。
+/// `Parser`に渡す前に複製しておくと、`stats`や`invalidate`を呼び出せます。
+///
+/// 同じURLを同時に取得しようとした場合は、リクエストを1回だけ行い、失敗した場合も含めてその結果を共有します。
 ///
 /// ```rust
 /// use japanese_address_parser::http::cached_client::CachedApiClient;
@@ -14,83 +127,267 @@ use std::time::Duration;
 ///
 /// let client = CachedApiClient::<ReqwestApiClient>::new();
 /// ```
-pub struct CachedApiClient<C: ApiClient> {
-    client: C,
-    cache: InMemoryCache,
+pub struct CachedApiClient<C: ApiClient, S: CacheStore = InMemoryCache> {
+    client: Arc<C>,
+    cache: Arc<S>,
+    counters: Arc<Counters>,
+    in_flight: Arc<InFlight>,
+}
+
+impl<C: ApiClient, S: CacheStore> Clone for CachedApiClient<C, S> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            cache: self.cache.clone(),
+            counters: self.counters.clone(),
+            in_flight: self.in_flight.clone(),
+        }
+    }
 }
 
 impl<C: ApiClient> CachedApiClient<C> {
     pub fn with_config(ttl: Duration, max_entries: usize) -> Self {
+        Self::with_store(C::new(), InMemoryCache::with_config(ttl, max_entries))
+    }
+}
+
+impl<C: ApiClient, S: CacheStore> CachedApiClient<C, S> {
+    /// Constructs a new `CachedApiClient` with the given client and cache store.
+    ///
+    /// ```rust
+    /// use japanese_address_parser::http::cached_client::CachedApiClient;
+    /// use japanese_address_parser::http::disk_cache::DiskCache;
+    /// use japanese_address_parser::http::reqwest_client::ReqwestApiClient;
+    /// use japanese_address_parser::parser::Parser;
+    ///
+    /// let client = CachedApiClient::with_store(ReqwestApiClient::default(), DiskCache::new("./cache"));
+    /// let parser = Parser::with_api_client(client);
+    /// ```
+    pub fn with_store(client: C, store: S) -> Self {
         Self {
-            client: C::new(),
-            cache: InMemoryCache::with_config(ttl, max_entries),
+            client: Arc::new(client),
+            cache: Arc::new(store),
+            counters: Default::default(),
+            in_flight: Default::default(),
+        }
+    }
+
+    /// Returns the statistics of the cache.
+    ///
+    /// ```rust
+    /// use japanese_address_parser::http::cached_client::CachedApiClient;
+    /// use japanese_address_parser::http::client::ApiClient;
+    /// use japanese_address_parser::http::reqwest_client::ReqwestApiClient;
+    /// use japanese_address_parser::parser::Parser;
+    ///
+    /// async fn example() {
+    ///     let client = CachedApiClient::<ReqwestApiClient>::new();
+    ///     let parser = Parser::with_api_client(client.clone());
+    ///     parser.parse("東京都新宿区西新宿2-8-1").await;
+    ///     println!("{:?}", client.stats());
+    /// }
+    /// ```
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+            not_modified: self.counters.not_modified.load(Ordering::Relaxed),
+            coalesced: self.counters.coalesced.load(Ordering::Relaxed),
+            evictions: self.cache.evictions(),
         }
     }
+
+    /// Removes the cached data of the URL.
+    ///
+    /// 指定したURLのキャッシュを削除し、次回は再検証せずに取得し直します。
+    pub fn invalidate(&self, url: &str) {
+        self.cache.remove(url);
+    }
+
+    /// Removes all cached data.
+    ///
+    /// 住所マスタが更新された場合など、全てのデータを取得し直す場合に使用してください。
+    pub fn clear(&self) {
+        self.cache.clear();
+    }
+
+    /// 再検証の結果をキャッシュに反映し、利用するエントリを返す
+    fn apply_revalidation(
+        &self,
+        url: &str,
+        stale: Option<CacheEntry>,
+        revalidation: Revalidation,
+    ) -> Result<CacheEntry, ApiClientError> {
+        let entry = match revalidation {
+            Revalidation::NotModified => {
+                let entry = stale.ok_or_else(|| ApiClientError::Request {
+                    url: url.to_string(),
+                    message: "304 Not Modified was returned without a cached entry".to_string(),
+                })?;
+                self.counters.not_modified.fetch_add(1, Ordering::Relaxed);
+                entry
+            }
+            Revalidation::Modified { body, validators } => CacheEntry { body, validators },
+        };
+        // 変更がなかった場合も、保持期間を数え直すため登録し直す
+        self.cache.insert(url, entry.clone());
+        Ok(entry)
+    }
+
+    /// 保存先の最大容量に収まる件数まで、`warm`で住所マスタを取得する市区町村を絞り込む
+    fn fit_to_capacity(&self, prefecture_name: &str, mut city_names: Vec<String>) -> Vec<String> {
+        // 都道府県マスタの1件を除いた件数が、市区町村マスタを保存できる件数になる
+        let Some(capacity) = self.cache.max_entries().map(|max| max.saturating_sub(1)) else {
+            return city_names;
+        };
+        if city_names.len() > capacity {
+            log::warn!(
+                "{}の市区町村({}件)の住所マスタはキャッシュの最大容量に収まらないため、{}件のみ取得します",
+                prefecture_name,
+                city_names.len(),
+                capacity
+            );
+            city_names.truncate(capacity);
+        }
+        city_names
+    }
+
+    /// 保持期間内のキャッシュを返し、統計情報を更新する
+    fn lookup(&self, url: &str) -> Option<CacheEntry> {
+        let entry = self.cache.get(url);
+        let counter = if entry.is_some() {
+            &self.counters.hits
+        } else {
+            &self.counters.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        entry
+    }
 }
 
-impl<C: ApiClient + Sync> ApiClient for CachedApiClient<C> {
-    fn new() -> Self {
-        Self {
-            client: C::new(),
-            cache: InMemoryCache::new(),
+impl<C: ApiClient + Sync + Send, S: CacheStore + Default + Sync + Send> CachedApiClient<C, S> {
+    /// Fetches the master data of the prefecture and its cities in advance.
+    ///
+    /// 指定した都道府県の住所マスタと、その都道府県に含まれる全ての市区町村の住所マスタを取得してキャッシュに保存します。
+    /// 住所マスタは`endpoints`で指定した取得先から、市区町村ごとに並行して取得します。
+    ///
+    /// 保存先の最大容量(`CacheStore::max_entries`)に都道府県と全ての市区町村の住所マスタが収まらない場合は、
+    /// 保存したエントリ同士で押し出し合わないよう、収まる件数の市区町村だけを取得します。
+    /// 例えば`InMemoryCache`の初期値(100件)では、北海道(188市区町村)の全ての住所マスタは保存できません。
+    /// 他のURLのキャッシュも押し出されるため、必要に応じて`with_config`で最大容量を増やしてください。
+    ///
+    /// ```rust
+    /// use japanese_address_parser::http::cached_client::CachedApiClient;
+    /// use japanese_address_parser::http::client::ApiClient;
+    /// use japanese_address_parser::http::endpoints::Endpoints;
+    /// use japanese_address_parser::http::reqwest_client::ReqwestApiClient;
+    ///
+    /// async fn example() {
+    ///     let client = CachedApiClient::<ReqwestApiClient>::new();
+    ///     client.warm("東京都", &Endpoints::default()).await.unwrap();
+    /// }
+    /// ```
+    pub async fn warm(&self, prefecture_name: &str, endpoints: &Endpoints) -> Result<(), Error> {
+        let source = &ApiClientSource::new(self.clone()).endpoints(endpoints);
+        let city_names =
+            self.fit_to_capacity(prefecture_name, source.prefecture(prefecture_name).await?);
+        stream::iter(city_names)
+            .map(|city_name| async move { source.city(prefecture_name, &city_name).await })
+            .buffer_unordered(WARM_CONCURRENCY)
+            .try_for_each(|_| async { Ok(()) })
+            .await
+    }
+
+    /// Fetches the master data of the prefecture and its cities in advance synchronously.
+    ///
+    /// `warm`の同期版です。
+    #[cfg(feature = "blocking")]
+    pub fn warm_blocking(&self, prefecture_name: &str, endpoints: &Endpoints) -> Result<(), Error> {
+        let source = ApiClientSource::new(self.clone()).endpoints(endpoints);
+        let city_names = source.prefecture_blocking(prefecture_name)?;
+        for city_name in self.fit_to_capacity(prefecture_name, city_names) {
+            source.city_blocking(prefecture_name, &city_name)?;
         }
+        Ok(())
+    }
+}
+
+impl<C: ApiClient + Sync + Send, S: CacheStore + Default + Sync + Send> ApiClient
+    for CachedApiClient<C, S>
+{
+    fn new() -> Self {
+        Self::with_store(C::new(), S::default())
     }
 
     async fn fetch<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
-        // キャッシュが利用できる場合は、キャッシュからバイト列を取得してデシリアライズして利用する
+        // 保持期間内のキャッシュが利用できる場合は、キャッシュからバイト列を取得してデシリアライズして利用する
+        if let Some(entry) = self.lookup(url) {
+            return deserialize(url, &entry);
+        }
+
+        // 同じURLを取得中の呼び出しがある場合は、その完了を待ってから結果を共有する
+        // 取得に失敗した場合も、待っていた呼び出しはリクエストを繰り返さずに同じエラーを返す
+        let flight = self.in_flight.join(url);
+        let mut shared = flight.lock.lock().await;
+        if let Some(result) = shared.as_ref() {
+            self.counters.coalesced.fetch_add(1, Ordering::Relaxed);
+            return deserialize(url, result.as_ref().map_err(Clone::clone)?);
+        }
         if let Some(entry) = self.cache.get(url) {
-            return serde_json::from_slice::<T>(&entry.body).map_err(|e| {
-                ApiClientError::Deserialize {
-                    url: url.to_string(),
-                    message: e.to_string(),
-                }
-            });
+            self.counters.coalesced.fetch_add(1, Ordering::Relaxed);
+            return deserialize(url, &entry);
         }
 
-        // キャッシュが利用できない場合は、APIリクエストを行ないデータを取得、取得したデータをキャッシュに保存する
-        let response = self.client.fetch::<Value>(url).await?;
-        let bytes = serde_json::to_vec(&response).map_err(|e| ApiClientError::Deserialize {
-            url: url.to_string(),
-            message: e.to_string(),
-        })?;
-        self.cache.register(url, bytes.clone());
-        serde_json::from_slice::<T>(&bytes).map_err(|e| ApiClientError::Deserialize {
-            url: url.to_string(),
-            message: e.to_string(),
-        })
+        // 保持期間を過ぎたキャッシュがある場合は再検証し、ない場合はAPIリクエストを行ないデータを取得する
+        let stale = self.cache.get_stale(url);
+        let validators = stale
+            .as_ref()
+            .map(|entry| entry.validators.clone())
+            .unwrap_or_default();
+        let result = match self.client.fetch_revalidate(url, &validators).await {
+            Ok(revalidation) => self.apply_revalidation(url, stale, revalidation),
+            Err(error) => Err(error),
+        };
+        let entry = shared.insert(result).as_ref().map_err(Clone::clone);
+        // 既に待っている呼び出しにのみ結果を共有し、これから参加する呼び出しは新たにリクエストを行う
+        flight.finish();
+        deserialize(url, entry?)
     }
 
     #[cfg(feature = "blocking")]
     fn fetch_blocking<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
-        // キャッシュが利用できる場合は、キャッシュからバイト列を取得してデシリアライズして利用する
-        if let Some(entry) = self.cache.get(url) {
-            return serde_json::from_slice::<T>(&entry.body).map_err(|e| {
-                ApiClientError::Deserialize {
-                    url: url.to_string(),
-                    message: e.to_string(),
-                }
-            });
+        // 保持期間内のキャッシュが利用できる場合は、キャッシュからバイト列を取得してデシリアライズして利用する
+        if let Some(entry) = self.lookup(url) {
+            return deserialize(url, &entry);
         }
 
-        // キャッシュが利用できない場合は、APIリクエストを行ないデータを取得、取得したデータをキャッシュに保存する
-        let response = self.client.fetch_blocking::<Value>(url)?;
-        let bytes = serde_json::to_vec(&response).map_err(|e| ApiClientError::Deserialize {
-            url: url.to_string(),
-            message: e.to_string(),
-        })?;
-        self.cache.register(url, bytes.clone());
-        serde_json::from_slice::<T>(&bytes).map_err(|e| ApiClientError::Deserialize {
-            url: url.to_string(),
-            message: e.to_string(),
-        })
+        // 保持期間を過ぎたキャッシュがある場合は再検証し、ない場合はAPIリクエストを行ないデータを取得する
+        let stale = self.cache.get_stale(url);
+        let validators = stale
+            .as_ref()
+            .map(|entry| entry.validators.clone())
+            .unwrap_or_default();
+        let revalidation = self.client.fetch_revalidate_blocking(url, &validators)?;
+        let entry = self.apply_revalidation(url, stale, revalidation)?;
+        deserialize(url, &entry)
     }
 }
 
+fn deserialize<T: DeserializeOwned>(url: &str, entry: &CacheEntry) -> Result<T, ApiClientError> {
+    serde_json::from_slice::<T>(&entry.body).map_err(|e| ApiClientError::Deserialize {
+        url: url.to_string(),
+        message: e.to_string(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::http::cached_client::CachedApiClient;
+    use crate::http::cache::{InMemoryCache, Revalidation, Validators};
+    use crate::http::cached_client::{CacheStats, CachedApiClient};
     use crate::http::client::ApiClient;
+    use crate::http::endpoints::Endpoints;
     use crate::http::error::ApiClientError;
+    use crate::http::mock_client;
     use serde::de::DeserializeOwned;
     use serde_json::Value;
     use std::sync::atomic::{AtomicUsize, Ordering};
@@ -196,4 +493,290 @@ mod tests {
         assert_ne!(response.get("called_count").unwrap().as_u64(), Some(1));
         assert_eq!(response.get("called_count").unwrap().as_u64(), Some(2));
     }
+
+    struct RevalidatingApiClient {
+        revalidated_with: std::sync::Mutex<Vec<Validators>>,
+    }
+
+    impl ApiClient for RevalidatingApiClient {
+        fn new() -> Self {
+            Self {
+                revalidated_with: Default::default(),
+            }
+        }
+
+        async fn fetch<T: DeserializeOwned>(&self, _url: &str) -> Result<T, ApiClientError> {
+            unreachable!()
+        }
+
+        #[cfg(feature = "blocking")]
+        fn fetch_blocking<T: DeserializeOwned>(&self, _url: &str) -> Result<T, ApiClientError> {
+            unreachable!()
+        }
+
+        async fn fetch_revalidate(
+            &self,
+            _url: &str,
+            validators: &Validators,
+        ) -> Result<Revalidation, ApiClientError> {
+            let mut revalidated_with = self.revalidated_with.lock().unwrap();
+            revalidated_with.push(validators.clone());
+            if validators.is_empty() {
+                Ok(Revalidation::Modified {
+                    body: br#"{"version": 1}"#.to_vec(),
+                    validators: Validators {
+                        etag: Some("\"v1\"".to_string()),
+                        last_modified: None,
+                    },
+                })
+            } else {
+                Ok(Revalidation::NotModified)
+            }
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), tokio::test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    async fn 保持期間を過ぎた場合は条件付きリクエストで再検証すること() {
+        let client = CachedApiClient::with_store(
+            RevalidatingApiClient::new(),
+            InMemoryCache::with_config(Duration::ZERO, 10),
+        );
+        let response = client.fetch::<Value>("/endpoint").await.unwrap();
+        assert_eq!(response.get("version").unwrap().as_u64(), Some(1));
+
+        // 変更がない場合は保存済みのデータを返す
+        let response = client.fetch::<Value>("/endpoint").await.unwrap();
+        assert_eq!(response.get("version").unwrap().as_u64(), Some(1));
+
+        let revalidated_with = client.client.revalidated_with.lock().unwrap();
+        assert_eq!(
+            *revalidated_with,
+            vec![
+                Validators::default(),
+                Validators {
+                    etag: Some("\"v1\"".to_string()),
+                    last_modified: None,
+                }
+            ]
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), tokio::test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    async fn 複製したクライアントはキャッシュを共有すること() {
+        let client = CachedApiClient::<MockApiClient>::new();
+        let cloned = client.clone();
+        client.fetch::<Value>("/endpoint").await.unwrap();
+
+        let response = cloned.fetch::<Value>("/endpoint").await.unwrap();
+        assert_eq!(response.get("called_count").unwrap().as_u64(), Some(1));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), tokio::test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    async fn 統計情報を集計すること() {
+        let client = CachedApiClient::with_store(
+            RevalidatingApiClient::new(),
+            InMemoryCache::with_config(Duration::ZERO, 1),
+        );
+        client.fetch::<Value>("/endpoint1").await.unwrap();
+        client.fetch::<Value>("/endpoint1").await.unwrap();
+        client.fetch::<Value>("/endpoint2").await.unwrap();
+        assert_eq!(
+            client.stats(),
+            CacheStats {
+                hits: 0,
+                misses: 3,
+                not_modified: 1,
+                coalesced: 0,
+                evictions: 1,
+            }
+        );
+
+        let client = CachedApiClient::<MockApiClient>::new();
+        client.fetch::<Value>("/endpoint").await.unwrap();
+        client.fetch::<Value>("/endpoint").await.unwrap();
+        assert_eq!(client.stats().hits, 1);
+        assert_eq!(client.stats().misses, 1);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), tokio::test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    async fn 削除したurlは取得し直すこと() {
+        let client = CachedApiClient::<MockApiClient>::new();
+        client.fetch::<Value>("/endpoint1").await.unwrap();
+        client.fetch::<Value>("/endpoint2").await.unwrap();
+
+        client.invalidate("/endpoint1");
+        let response = client.fetch::<Value>("/endpoint1").await.unwrap();
+        assert_eq!(response.get("called_count").unwrap().as_u64(), Some(3));
+        let response = client.fetch::<Value>("/endpoint2").await.unwrap();
+        assert_eq!(response.get("called_count").unwrap().as_u64(), Some(2));
+
+        client.clear();
+        let response = client.fetch::<Value>("/endpoint2").await.unwrap();
+        assert_eq!(response.get("called_count").unwrap().as_u64(), Some(4));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), tokio::test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    async fn warm_都道府県と市区町村の住所マスタを保存すること() {
+        let client =
+            CachedApiClient::with_store(mock_client::MockApiClient::tokyo(), InMemoryCache::new());
+        client.warm("東京都", &Endpoints::default()).await.unwrap();
+        // 都道府県マスタ1件と市区町村マスタ2件
+        assert_eq!(client.client.count(), 3);
+
+        client.warm("東京都", &Endpoints::default()).await.unwrap();
+        assert_eq!(client.client.count(), 3);
+        assert_eq!(client.stats().hits, 3);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), tokio::test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    async fn warm_最大容量に収まる件数の市区町村のみ取得すること() {
+        let client = CachedApiClient::with_store(
+            mock_client::MockApiClient::tokyo(),
+            InMemoryCache::with_config(Duration::from_secs(3600), 2),
+        );
+        client.warm("東京都", &Endpoints::default()).await.unwrap();
+        // 都道府県マスタ1件と市区町村マスタ1件
+        assert_eq!(client.client.count(), 2);
+        assert_eq!(client.stats().evictions, 0);
+
+        client.warm("東京都", &Endpoints::default()).await.unwrap();
+        assert_eq!(client.client.count(), 2);
+        assert_eq!(client.stats().hits, 2);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), tokio::test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    async fn warm_住所マスタを取得できない場合はエラーを返すこと() {
+        let client =
+            CachedApiClient::with_store(mock_client::MockApiClient::tokyo(), InMemoryCache::new());
+        let result = client.warm("大阪府", &Endpoints::default()).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn warm_blocking_都道府県と市区町村の住所マスタを保存すること() {
+        let client =
+            CachedApiClient::with_store(mock_client::MockApiClient::tokyo(), InMemoryCache::new());
+        client
+            .warm_blocking("東京都", &Endpoints::default())
+            .unwrap();
+        client
+            .warm_blocking("東京都", &Endpoints::default())
+            .unwrap();
+        assert_eq!(client.client.count(), 3);
+    }
+
+    /// 応答に時間がかかるモック
+    struct SlowApiClient {
+        called_count: AtomicUsize,
+    }
+
+    impl ApiClient for SlowApiClient {
+        fn new() -> Self {
+            Self {
+                called_count: 0.into(),
+            }
+        }
+
+        async fn fetch<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
+            let called_count = self.called_count.fetch_add(1, Ordering::SeqCst) + 1;
+            #[cfg(not(target_arch = "wasm32"))]
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            #[cfg(target_arch = "wasm32")]
+            gloo_timers::future::TimeoutFuture::new(100).await;
+            if url.starts_with("/missing") {
+                return Err(ApiClientError::NotFound {
+                    url: url.to_string(),
+                });
+            }
+            Ok(serde_json::from_str(&format!(
+                "{{\"url\": \"{}\", \"called_count\": {}}}",
+                url, called_count
+            ))
+            .unwrap())
+        }
+
+        #[cfg(feature = "blocking")]
+        fn fetch_blocking<T: DeserializeOwned>(&self, _url: &str) -> Result<T, ApiClientError> {
+            unreachable!()
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), tokio::test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    async fn 同じurlを同時に取得した場合はリクエストを1回だけ行うこと() {
+        let client = CachedApiClient::<SlowApiClient>::new();
+        let responses =
+            futures::future::join_all((0..8).map(|_| client.fetch::<Value>("/endpoint"))).await;
+
+        assert_eq!(client.client.called_count.load(Ordering::SeqCst), 1);
+        for response in responses {
+            assert_eq!(
+                response.unwrap().get("called_count").unwrap().as_u64(),
+                Some(1)
+            );
+        }
+        assert_eq!(client.stats().misses, 8);
+        assert_eq!(client.stats().coalesced, 7);
+        assert!(client.in_flight.locks.lock().unwrap().is_empty());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), tokio::test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    async fn 同じurlを同時に取得して失敗した場合はエラーを共有すること() {
+        let client = CachedApiClient::<SlowApiClient>::new();
+        let responses =
+            futures::future::join_all((0..8).map(|_| client.fetch::<Value>("/missing"))).await;
+
+        assert_eq!(client.client.called_count.load(Ordering::SeqCst), 1);
+        for response in responses {
+            assert!(response.unwrap_err().is_not_found());
+        }
+        assert_eq!(client.stats().coalesced, 7);
+        assert!(client.in_flight.locks.lock().unwrap().is_empty());
+
+        // 取得が終わった後の呼び出しは、失敗した結果を使い回さずにリクエストを行う
+        assert!(client.fetch::<Value>("/missing").await.is_err());
+        assert_eq!(client.client.called_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), tokio::test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    async fn 失敗した結果を共有した後に参加した呼び出しはリクエストを行うこと() {
+        let client = CachedApiClient::<SlowApiClient>::new();
+        // 結果を受け取る前の呼び出しが残っている状態を再現する
+        let waiting = client.in_flight.join("/missing");
+
+        assert!(client.fetch::<Value>("/missing").await.is_err());
+        assert!(client.fetch::<Value>("/missing").await.is_err());
+        assert_eq!(client.client.called_count.load(Ordering::SeqCst), 2);
+        assert_eq!(client.stats().coalesced, 0);
+
+        drop(waiting);
+        assert!(client.in_flight.locks.lock().unwrap().is_empty());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), tokio::test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    async fn 異なるurlを同時に取得した場合はそれぞれリクエストを行うこと() {
+        let client = CachedApiClient::<SlowApiClient>::new();
+        let cloned = client.clone();
+        let (first, second) = futures::future::join(
+            client.fetch::<Value>("/endpoint1"),
+            cloned.fetch::<Value>("/endpoint2"),
+        )
+        .await;
+
+        assert_eq!(first.unwrap().get("url").unwrap(), "/endpoint1");
+        assert_eq!(second.unwrap().get("url").unwrap(), "/endpoint2");
+        assert_eq!(client.client.called_count.load(Ordering::SeqCst), 2);
+        assert_eq!(client.stats().coalesced, 0);
+    }
 }
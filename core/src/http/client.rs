@@ -1,5 +1,8 @@
+use crate::http::cache::{Revalidation, Validators};
 use crate::http::error::ApiClientError;
 use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::future::Future;
 
 /// HTTP client to fetch remote resources
 ///
@@ -21,4 +24,53 @@ pub trait ApiClient {
     /// 引数で指定したURLから同期的にデータを取得し、デシリアライズする処理を実装します。
     #[cfg(feature = "blocking")]
     fn fetch_blocking<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError>;
+
+    /// Fetches data from a remote URL asynchronously unless it has not been modified
+    ///
+    /// `validators`を使って条件付きリクエストを行い、前回取得した内容から変更がない場合は
+    /// `Revalidation::NotModified`を返す処理を実装します。`CachedApiClient`がキャッシュの再検証に使用します。
+    /// 既定の実装は条件付きリクエストを行わず、常に`fetch`で取得した内容を返します。
+    /// このとき内容は`serde_json::Value`を経由して再シリアライズされるため、レスポンスボディとは
+    /// バイト列が一致しません(オブジェクトのキーは辞書順に並び替えられ、空白は取り除かれます)。
+    /// 取得元のレスポンスボディをそのまま扱う必要がある場合は、このメソッドを実装してください。
+    fn fetch_revalidate(
+        &self,
+        url: &str,
+        validators: &Validators,
+    ) -> impl Future<Output = Result<Revalidation, ApiClientError>>
+    where
+        Self: Sync,
+    {
+        let _ = validators;
+        async move {
+            let value = self.fetch::<Value>(url).await?;
+            to_revalidation(url, &value)
+        }
+    }
+
+    /// Fetches data from a remote URL synchronously unless it has not been modified
+    ///
+    /// `fetch_revalidate`の同期版です。既定の実装は常に`fetch_blocking`で取得した内容を返し、
+    /// `fetch_revalidate`と同様にレスポンスボディとはバイト列が一致しません。
+    #[cfg(feature = "blocking")]
+    fn fetch_revalidate_blocking(
+        &self,
+        url: &str,
+        validators: &Validators,
+    ) -> Result<Revalidation, ApiClientError> {
+        let _ = validators;
+        let value = self.fetch_blocking::<Value>(url)?;
+        to_revalidation(url, &value)
+    }
+}
+
+fn to_revalidation(url: &str, value: &Value) -> Result<Revalidation, ApiClientError> {
+    let body = serde_json::to_vec(value).map_err(|e| ApiClientError::Deserialize {
+        url: url.to_string(),
+        message: e.to_string(),
+    })?;
+    Ok(Revalidation::Modified {
+        body,
+        validators: Validators::default(),
+    })
 }
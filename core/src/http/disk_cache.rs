@@ -0,0 +1,344 @@
+use crate::http::cache::{CacheEntry, CacheStore, Validators};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// キャッシュを保存するディレクトリを指定する環境変数
+const CACHE_DIR_ENV_KEY: &str = "JAPANESE_ADDRESS_PARSER_CACHE_DIR";
+/// キャッシュファイルの拡張子
+const EXTENSION: &str = "cache";
+
+/// 一時ファイル名の重複を避けるための連番
+static TEMPORARY_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// `CacheStore` that persists entries in a local directory
+///
+/// 取得したデータをディレクトリに保存し、プロセスをまたいで再利用します。
+/// 保持期間の初期値は24時間、最大容量の初期値は100MiBです。最大容量を超えた場合は更新日時が最も古いファイルから削除します。
+///
+/// 書き込みは一時ファイルに書き出してから置き換えるため、同じディレクトリを複数のプロセスで共有しても
+/// 書き込み途中のファイルを読み込むことはありません。
+///
+/// `Default::default()`で初期化した場合は、環境変数`JAPANESE_ADDRESS_PARSER_CACHE_DIR`で指定したディレクトリ
+/// (未指定の場合は一時ディレクトリ内の`japanese-address-parser-cache`)を使用します。
+///
+/// ```rust
+/// use japanese_address_parser::http::cached_client::CachedApiClient;
+/// use japanese_address_parser::http::disk_cache::DiskCache;
+/// use japanese_address_parser::http::reqwest_client::ReqwestApiClient;
+/// use japanese_address_parser::parser::Parser;
+///
+/// let client = CachedApiClient::with_store(ReqwestApiClient::default(), DiskCache::new("./cache"));
+/// ```
+#[derive(Debug)]
+pub struct DiskCache {
+    /// キャッシュを保存するディレクトリ
+    directory: PathBuf,
+    /// キャッシュの保持期間
+    ttl: Duration,
+    /// キャッシュの最大容量(バイト)
+    max_bytes: u64,
+    /// このインスタンスが最大容量を超えたために削除したファイルの件数
+    evictions: AtomicU64,
+}
+
+/// キャッシュファイルの1行目に保存するメタデータ
+#[derive(Serialize, Deserialize)]
+struct Metadata {
+    url: String,
+    /// 登録した時刻(UNIX時間、ミリ秒)
+    registered_at: u64,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl Default for DiskCache {
+    fn default() -> Self {
+        let directory = std::env::var(CACHE_DIR_ENV_KEY).map_or_else(
+            |_| std::env::temp_dir().join("japanese-address-parser-cache"),
+            PathBuf::from,
+        );
+        Self::new(directory)
+    }
+}
+
+impl DiskCache {
+    /// キャッシュの初期化
+    pub fn new<P: AsRef<Path>>(directory: P) -> Self {
+        Self::with_config(directory, Duration::from_secs(24 * 3600), 100 * 1024 * 1024)
+    }
+
+    /// キャッシュの初期化(カスタム)
+    pub fn with_config<P: AsRef<Path>>(directory: P, ttl: Duration, max_bytes: u64) -> Self {
+        assert!(max_bytes > 0, "max_bytes must be greater than 0");
+        Self {
+            directory: directory.as_ref().to_path_buf(),
+            ttl,
+            max_bytes,
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.directory
+            .join(format!("{:016x}.{}", fnv1a(key.as_bytes()), EXTENSION))
+    }
+
+    /// キャッシュファイルを読み込み、エントリと登録した時刻を返す
+    fn read(&self, key: &str) -> Option<(CacheEntry, SystemTime)> {
+        let bytes = std::fs::read(self.path(key)).ok()?;
+        let newline = bytes.iter().position(|&byte| byte == b'\n')?;
+        let metadata: Metadata = serde_json::from_slice(&bytes[..newline]).ok()?;
+        // ハッシュ値が衝突した場合は別のURLのデータが保存されている
+        if metadata.url != key {
+            return None;
+        }
+        let entry = CacheEntry {
+            body: bytes[newline + 1..].to_vec(),
+            validators: Validators {
+                etag: metadata.etag,
+                last_modified: metadata.last_modified,
+            },
+        };
+        let registered_at = UNIX_EPOCH + Duration::from_millis(metadata.registered_at);
+        Some((entry, registered_at))
+    }
+
+    fn write(&self, key: &str, entry: &CacheEntry) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.directory)?;
+        let metadata = Metadata {
+            url: key.to_string(),
+            registered_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            etag: entry.validators.etag.clone(),
+            last_modified: entry.validators.last_modified.clone(),
+        };
+        let mut bytes = serde_json::to_vec(&metadata)?;
+        bytes.push(b'\n');
+        bytes.extend_from_slice(&entry.body);
+
+        // 他のプロセスが書き込み途中のファイルを読み込まないよう、一時ファイルに書き出してから置き換える
+        let temporary = self.directory.join(format!(
+            "{}.{}.{}.tmp",
+            fnv1a(key.as_bytes()),
+            std::process::id(),
+            TEMPORARY_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&temporary, bytes)?;
+        std::fs::rename(&temporary, self.path(key)).inspect_err(|_| {
+            let _ = std::fs::remove_file(&temporary);
+        })
+    }
+
+    /// キャッシュファイルの一覧
+    fn files(&self) -> std::io::Result<Vec<PathBuf>> {
+        let mut files = vec![];
+        for entry in std::fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == EXTENSION)
+            {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    }
+
+    /// 最大容量を超えている場合に、更新日時が古いファイルから削除する
+    fn evict(&self) -> std::io::Result<()> {
+        let mut files = vec![];
+        for path in self.files()? {
+            // 他のプロセスが同時に削除した場合は読み飛ばす
+            if let Ok(metadata) = std::fs::metadata(&path) {
+                let modified = metadata.modified().unwrap_or(UNIX_EPOCH);
+                files.push((modified, metadata.len(), path));
+            }
+        }
+        let mut total: u64 = files.iter().map(|(_, length, _)| length).sum();
+        files.sort();
+        for (_, length, path) in files {
+            if total <= self.max_bytes {
+                break;
+            }
+            if std::fs::remove_file(path).is_ok() {
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+            total = total.saturating_sub(length);
+        }
+        Ok(())
+    }
+}
+
+impl CacheStore for DiskCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let (entry, registered_at) = self.read(key)?;
+        // 時刻が巻き戻った場合は保持期間内とみなす
+        let elapsed = registered_at.elapsed().unwrap_or_default();
+        if elapsed < self.ttl {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+
+    fn get_stale(&self, key: &str) -> Option<CacheEntry> {
+        self.read(key).map(|(entry, _)| entry)
+    }
+
+    fn insert(&self, key: &str, entry: CacheEntry) {
+        if let Err(error) = self.write(key, &entry).and_then(|_| self.evict()) {
+            log::warn!("{}のキャッシュを保存できませんでした: {}", key, error);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        match std::fs::remove_file(self.path(key)) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                log::warn!("{}のキャッシュを削除できませんでした: {}", key, error);
+            }
+            _ => {}
+        }
+    }
+
+    fn clear(&self) {
+        // ディレクトリが存在しない場合は削除するものがない
+        if let Ok(files) = self.files() {
+            for path in files {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
+    fn evictions(&self) -> u64 {
+        self.evictions.load(Ordering::Relaxed)
+    }
+}
+
+/// FNV-1aによる64bitのハッシュ値
+///
+/// ファイル名はプロセスやコンパイラのバージョンをまたいで一致する必要があるため、`DefaultHasher`は使用しない。
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::http::cache::{CacheEntry, CacheStore, Validators};
+    use crate::http::disk_cache::DiskCache;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn temporary_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!(
+            "japanese-address-parser-disk-cache-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&directory);
+        directory
+    }
+
+    fn entry(body: &str) -> CacheEntry {
+        CacheEntry {
+            body: body.as_bytes().to_vec(),
+            validators: Validators {
+                etag: Some("\"abc\"".to_string()),
+                last_modified: None,
+            },
+        }
+    }
+
+    #[test]
+    fn 別のインスタンスから保存したデータを読み込めること() {
+        let directory = temporary_directory("shared");
+        DiskCache::new(&directory).insert("https://example.com/東京都.json", entry("[1]"));
+
+        let cache = DiskCache::new(&directory);
+        assert_eq!(
+            cache.get("https://example.com/東京都.json"),
+            Some(entry("[1]"))
+        );
+        assert_eq!(cache.get("https://example.com/大阪府.json"), None);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn 保持期間を過ぎたデータは再検証用にのみ返すこと() {
+        let directory = temporary_directory("expired");
+        let cache = DiskCache::with_config(&directory, Duration::ZERO, 1024);
+        cache.insert("key1", entry("{}"));
+
+        assert_eq!(cache.get("key1"), None);
+        assert_eq!(cache.get_stale("key1"), Some(entry("{}")));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn 最大容量を超えた場合は古いファイルから削除すること() {
+        let directory = temporary_directory("evict");
+        let cache = DiskCache::with_config(&directory, Duration::from_secs(3600), 200);
+        cache.insert("key1", entry(&"1".repeat(100)));
+        std::thread::sleep(Duration::from_millis(10));
+        cache.insert("key2", entry(&"2".repeat(100)));
+
+        assert_eq!(cache.get("key1"), None);
+        assert!(cache.get("key2").is_some());
+        assert_eq!(cache.evictions(), 1);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn 指定したエントリと全てのエントリを削除できること() {
+        let directory = temporary_directory("remove");
+        let cache = DiskCache::new(&directory);
+        cache.insert("key1", entry("1"));
+        cache.insert("key2", entry("2"));
+
+        cache.remove("key1");
+        assert_eq!(cache.get_stale("key1"), None);
+        assert!(cache.get_stale("key2").is_some());
+
+        cache.clear();
+        assert_eq!(cache.get_stale("key2"), None);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn 複数のスレッドから同時に書き込んでも壊れたデータを返さないこと() {
+        let directory = temporary_directory("concurrent");
+        std::thread::scope(|scope| {
+            for index in 0..8 {
+                let directory = &directory;
+                scope.spawn(move || {
+                    let cache = DiskCache::new(directory);
+                    for _ in 0..20 {
+                        cache.insert("key1", entry(&index.to_string().repeat(1000)));
+                        if let Some(read) = cache.get("key1") {
+                            let body = String::from_utf8(read.body).unwrap();
+                            assert_eq!(body.len(), 1000);
+                            assert!(body.chars().all(|c| c == body.chars().next().unwrap()));
+                        }
+                    }
+                });
+            }
+        });
+        assert!(std::fs::read_dir(&directory).unwrap().all(|file| file
+            .unwrap()
+            .path()
+            .extension()
+            .unwrap()
+            == "cache"));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,150 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+
+const GEOLONIA: &str = "https://geolonia.github.io/japanese-addresses/api/ja";
+const GEOLONIA_PREFECTURE: &str =
+    "https://yuukitoriyama.github.io/geolonia-japanese-addresses-accompanist";
+const CHIMEI_RUIJU: &str = "https://{prefecture}.chimei-ruiju.org";
+const POSTAL_CODE: &str = "https://madefor.github.io/postal-code-api/api/v1";
+
+/// パスの1セグメントとしてエンコードする文字
+///
+/// WHATWG URL Standardのpath percent-encode setに`/`と`%`を加えたもの
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}')
+    .add(b'/')
+    .add(b'%');
+
+/// Base URLs of the address master data
+///
+/// 住所マスタの取得先をデータソースごとに指定します。
+/// 社内のミラーサーバーなど、公開されている配信元以外から住所マスタを取得する場合に使用してください。
+/// 指定しなかった項目は公開されている配信元を参照します。
+///
+/// # Example
+/// ```
+/// use japanese_address_parser::http::endpoints::Endpoints;
+/// use japanese_address_parser::parser::Parser;
+///
+/// let endpoints = Endpoints::default()
+///     .geolonia("https://mirror.example.com/japanese-addresses/api/ja")
+///     .geolonia_prefecture("https://mirror.example.com/accompanist");
+/// let parser = Parser::default().endpoints(endpoints);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoints {
+    pub(crate) geolonia: String,
+    pub(crate) geolonia_prefecture: String,
+    pub(crate) chimei_ruiju: String,
+    pub(crate) postal_code: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            geolonia: GEOLONIA.to_string(),
+            geolonia_prefecture: GEOLONIA_PREFECTURE.to_string(),
+            chimei_ruiju: CHIMEI_RUIJU.to_string(),
+            postal_code: POSTAL_CODE.to_string(),
+        }
+    }
+}
+
+impl Endpoints {
+    /// Geoloniaの町名マスタ(`{base_url}/{都道府県名}/{市区町村名}.json`)の取得先を指定します。
+    ///
+    /// 初期値は`https://geolonia.github.io/japanese-addresses/api/ja`です。
+    pub fn geolonia(mut self, base_url: &str) -> Self {
+        self.geolonia = trim_base_url(base_url);
+        self
+    }
+
+    /// Geoloniaの都道府県マスタ(`{base_url}/{都道府県名}/master.json`)の取得先を指定します。
+    ///
+    /// 初期値は`https://yuukitoriyama.github.io/geolonia-japanese-addresses-accompanist`です。
+    pub fn geolonia_prefecture(mut self, base_url: &str) -> Self {
+        self.geolonia_prefecture = trim_base_url(base_url);
+        self
+    }
+
+    /// ChimeiRuijuの住所マスタの取得先を指定します。
+    ///
+    /// `{prefecture}`は都道府県名のローマ字表記(`tokyo`など)に置き換えられます。
+    /// `{prefecture}`を含まない場合は、`{base_url}/{prefecture}`を取得先とします。
+    /// 初期値は`https://{prefecture}.chimei-ruiju.org`です。
+    pub fn chimei_ruiju(mut self, base_url: &str) -> Self {
+        self.chimei_ruiju = trim_base_url(base_url);
+        self
+    }
+
+    /// 郵便番号データ(`{base_url}/{上3桁}/{下4桁}.json`)の取得先を指定します。
+    ///
+    /// 初期値は`https://madefor.github.io/postal-code-api/api/v1`です。
+    pub fn postal_code(mut self, base_url: &str) -> Self {
+        self.postal_code = trim_base_url(base_url);
+        self
+    }
+}
+
+fn trim_base_url(base_url: &str) -> String {
+    base_url.trim_end_matches('/').to_string()
+}
+
+/// ChimeiRuijuの取得先の指定から、指定した都道府県の住所マスタの取得先を返す
+#[cfg_attr(not(feature = "experimental"), allow(dead_code))]
+pub(crate) fn chimei_ruiju_url(base_url: &str, prefecture_name_en: &str) -> String {
+    if base_url.contains("{prefecture}") {
+        base_url.replace("{prefecture}", prefecture_name_en)
+    } else {
+        format!("{}/{}", base_url, prefecture_name_en)
+    }
+}
+
+/// 取得先のURLに含める都道府県名・市区町村名などをパーセントエンコードする
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::http::endpoints::{chimei_ruiju_url, encode_path_segment, Endpoints};
+
+    #[test]
+    fn chimei_ruiju_url_初期値() {
+        assert_eq!(
+            chimei_ruiju_url(&Endpoints::default().chimei_ruiju, "tokyo"),
+            "https://tokyo.chimei-ruiju.org"
+        );
+    }
+
+    #[test]
+    fn chimei_ruiju_url_都道府県名を含まない場合はパスに追加する() {
+        let endpoints =
+            Endpoints::default().chimei_ruiju("https://mirror.example.com/chimei-ruiju/");
+        assert_eq!(
+            chimei_ruiju_url(&endpoints.chimei_ruiju, "tokyo"),
+            "https://mirror.example.com/chimei-ruiju/tokyo"
+        );
+    }
+
+    #[test]
+    fn encode_path_segment_日本語をエンコードする() {
+        assert_eq!(
+            encode_path_segment("千代田区"),
+            "%E5%8D%83%E4%BB%A3%E7%94%B0%E5%8C%BA"
+        );
+        assert_eq!(encode_path_segment("tokyo"), "tokyo");
+    }
+
+    #[test]
+    fn encode_path_segment_区切り文字をエンコードする() {
+        assert_eq!(encode_path_segment("a/b?c#d e"), "a%2Fb%3Fc%23d%20e");
+    }
+}
@@ -1,10 +1,17 @@
 use strum::EnumIs;
 use thiserror::Error;
 
-#[derive(Error, Debug, EnumIs)]
+/// Error while fetching data with `ApiClient`
+///
+/// 今後バリアントが追加される可能性があるため、`match`する際はワイルドカードを使用してください。
+#[derive(Error, Debug, EnumIs, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum ApiClientError {
     #[error("Network error: {url} {message}")]
     Request { url: String, message: String },
+    /// 取得先にデータが存在しない(HTTPの`404 Not Found`やファイルが存在しない場合)
+    #[error("Not found: {url}")]
+    NotFound { url: String },
     #[error("Deserialization error: {message}")]
     Deserialize { url: String, message: String },
 }
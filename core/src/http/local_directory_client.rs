@@ -0,0 +1,257 @@
+use crate::http::cache::{Revalidation, Validators};
+use crate::http::client::ApiClient;
+use crate::http::error::ApiClientError;
+use percent_encoding::percent_decode_str;
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+
+/// 住所マスタを格納するディレクトリを指定する環境変数
+const DATA_DIR_ENV_KEY: &str = "JAPANESE_ADDRESS_PARSER_DATA_DIR";
+
+/// An implementation of `ApiClient` that reads master data from a local directory
+///
+/// 住所マスタのJSONをHTTP経由ではなくローカルディレクトリから読み込みます。
+/// URLのホスト名とパスがそのままディレクトリ構造に対応するため、例えば
+/// `https://geolonia.github.io/japanese-addresses/api/ja/東京都/千代田区.json`は
+/// `{root}/geolonia.github.io/japanese-addresses/api/ja/東京都/千代田区.json`から読み込まれます。
+/// URL中のパーセントエンコードされた都道府県名などは、デコードしてからパスに対応付けます。
+///
+/// `ApiClient::new()`で初期化した場合は、環境変数`JAPANESE_ADDRESS_PARSER_DATA_DIR`で指定したディレクトリ
+/// (未指定の場合はカレントディレクトリ)を参照します。
+///
+/// ```rust
+/// use japanese_address_parser::http::local_directory_client::LocalDirectoryApiClient;
+/// use japanese_address_parser::parser::Parser;
+///
+/// let client = LocalDirectoryApiClient::with_root("./master-data");
+/// let parser = Parser::with_api_client(client);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LocalDirectoryApiClient {
+    root: PathBuf,
+}
+
+impl LocalDirectoryApiClient {
+    /// Initialize `LocalDirectoryApiClient` with the given root directory
+    pub fn with_root<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    fn read_bytes(&self, url: &str) -> Result<Vec<u8>, ApiClientError> {
+        let path = url_to_path(&self.root, url);
+        std::fs::read(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ApiClientError::NotFound {
+                url: url.to_string(),
+            },
+            _ => ApiClientError::Request {
+                url: url.to_string(),
+                message: format!("{}: {}", path.display(), e),
+            },
+        })
+    }
+
+    fn read<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
+        let bytes = self.read_bytes(url)?;
+        serde_json::from_slice::<T>(&bytes).map_err(|e| ApiClientError::Deserialize {
+            url: url.to_string(),
+            message: e.to_string(),
+        })
+    }
+}
+
+impl ApiClient for LocalDirectoryApiClient {
+    fn new() -> Self {
+        let root = std::env::var(DATA_DIR_ENV_KEY).unwrap_or_else(|_| ".".to_string());
+        Self::with_root(root)
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
+        self.read(url)
+    }
+
+    #[cfg(feature = "blocking")]
+    fn fetch_blocking<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
+        self.read(url)
+    }
+
+    // ファイルの内容を加工せずに返すため、スナップショットから作り直してもチェックサムが変わらない
+    async fn fetch_revalidate(
+        &self,
+        url: &str,
+        _validators: &Validators,
+    ) -> Result<Revalidation, ApiClientError> {
+        Ok(Revalidation::Modified {
+            body: self.read_bytes(url)?,
+            validators: Validators::default(),
+        })
+    }
+
+    #[cfg(feature = "blocking")]
+    fn fetch_revalidate_blocking(
+        &self,
+        url: &str,
+        _validators: &Validators,
+    ) -> Result<Revalidation, ApiClientError> {
+        Ok(Revalidation::Modified {
+            body: self.read_bytes(url)?,
+            validators: Validators::default(),
+        })
+    }
+}
+
+/// URLをローカルディレクトリ上のパスに変換する
+///
+/// スキームを取り除き、ホスト名以降をディレクトリ構造として扱う。
+/// パーセントエンコードされた都道府県名などはデコードし、
+/// `..`などのルートディレクトリの外を指すセグメントは無視する。
+pub(crate) fn url_to_path(root: &Path, url: &str) -> PathBuf {
+    url_segments(url)
+        .iter()
+        .fold(root.to_path_buf(), |path, segment| path.join(segment))
+}
+
+/// URLをルートディレクトリからの相対パス(`/`区切り)に変換する
+#[cfg_attr(not(feature = "snapshot"), allow(dead_code))]
+pub(crate) fn url_to_relative_path(url: &str) -> String {
+    url_segments(url).join("/")
+}
+
+fn url_segments(url: &str) -> Vec<String> {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    // デコード後の`/`もセグメントの区切りとして扱い、`%2E%2E`のような指定でも外に出られないようにする
+    let decoded = percent_decode_str(without_scheme).decode_utf8_lossy();
+    decoded
+        .split('/')
+        .filter(|segment| !segment.is_empty() && *segment != "." && *segment != "..")
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::http::local_directory_client::{url_to_path, url_to_relative_path};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn url_to_path_geolonia() {
+        assert_eq!(
+            url_to_path(
+                Path::new("/data"),
+                "https://geolonia.github.io/japanese-addresses/api/ja/東京都/千代田区.json"
+            ),
+            PathBuf::from(
+                "/data/geolonia.github.io/japanese-addresses/api/ja/東京都/千代田区.json"
+            )
+        );
+    }
+
+    #[test]
+    fn url_to_path_chimei_ruiju() {
+        assert_eq!(
+            url_to_path(
+                Path::new("/data"),
+                "https://tokyo.chimei-ruiju.org/千代田区/master.json"
+            ),
+            PathBuf::from("/data/tokyo.chimei-ruiju.org/千代田区/master.json")
+        );
+    }
+
+    #[test]
+    fn url_to_relative_path_成功() {
+        assert_eq!(
+            url_to_relative_path("https://tokyo.chimei-ruiju.org/千代田区/master.json"),
+            "tokyo.chimei-ruiju.org/千代田区/master.json"
+        );
+    }
+
+    #[test]
+    fn url_to_path_パーセントエンコードされたパスをデコードする() {
+        assert_eq!(
+            url_to_path(
+                Path::new("/data"),
+                "https://tokyo.chimei-ruiju.org/%E5%8D%83%E4%BB%A3%E7%94%B0%E5%8C%BA/master.json"
+            ),
+            PathBuf::from("/data/tokyo.chimei-ruiju.org/千代田区/master.json")
+        );
+        assert_eq!(
+            url_to_path(
+                Path::new("/data"),
+                "https://example.com/%2E%2E%2F%2E%2E/etc/passwd"
+            ),
+            PathBuf::from("/data/example.com/etc/passwd")
+        );
+    }
+
+    #[test]
+    fn url_to_path_ルートディレクトリの外を参照しない() {
+        assert_eq!(
+            url_to_path(Path::new("/data"), "https://example.com/../../etc/passwd"),
+            PathBuf::from("/data/example.com/etc/passwd")
+        );
+    }
+}
+
+#[cfg(test)]
+mod async_tests {
+    use crate::domain::geolonia::entity::Prefecture;
+    use crate::http::client::ApiClient;
+    use crate::http::local_directory_client::LocalDirectoryApiClient;
+    use std::path::PathBuf;
+
+    fn prepare_directory(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "japanese-address-parser-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let directory = root.join("example.com").join("富山県");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(
+            directory.join("master.json"),
+            r#"{"name": "富山県", "cities": ["富山市", "高岡市"]}"#,
+        )
+        .unwrap();
+        std::fs::write(directory.join("broken.json"), r#"{"hoge": true}"#).unwrap();
+        root
+    }
+
+    #[tokio::test]
+    async fn ファイルが存在する場合_データを返す() {
+        let root = prepare_directory("found");
+        let client = LocalDirectoryApiClient::with_root(&root);
+        let result = client
+            .fetch::<Prefecture>("https://example.com/富山県/master.json")
+            .await;
+        assert!(result.is_ok());
+        let prefecture = result.unwrap();
+        assert_eq!(prefecture.name, "富山県");
+        assert_eq!(prefecture.cities, vec!["富山市", "高岡市"]);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn ファイルが存在しない場合_not_foundエラーになる() {
+        let root = prepare_directory("not-found");
+        let client = LocalDirectoryApiClient::with_root(&root);
+        let result = client
+            .fetch::<Prefecture>("https://example.com/石川県/master.json")
+            .await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().is_not_found());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn デシリアライズに失敗した場合_deserializeエラーになる() {
+        let root = prepare_directory("broken");
+        let client = LocalDirectoryApiClient::with_root(&root);
+        let result = client
+            .fetch::<Prefecture>("https://example.com/富山県/broken.json")
+            .await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().is_deserialize());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,122 @@
+//! テストで使用する`ApiClient`のモック
+
+use crate::http::cache::{Revalidation, Validators};
+use crate::http::client::ApiClient;
+use crate::http::error::ApiClientError;
+use percent_encoding::percent_decode_str;
+use serde::de::DeserializeOwned;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// URLの末尾に応じて、登録したレスポンスボディを返すモック
+///
+/// URLはパーセントデコードしてから、登録した順に末尾が一致するものを探す。
+/// 一致するものがない場合は`ApiClientError::NotFound`を返す。
+/// 複製したモックとは、登録したレスポンスと取得回数を共有する。
+#[derive(Clone, Default)]
+pub(crate) struct MockApiClient {
+    responses: Arc<Vec<(String, String)>>,
+    /// 一致するものがない場合に、404ではなく通信エラーを返す
+    network_error: bool,
+    count: Arc<AtomicUsize>,
+}
+
+impl MockApiClient {
+    /// URLの末尾とレスポンスボディの組を指定してモックを生成する
+    pub(crate) fn with_responses<'a>(
+        responses: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Self {
+        Self {
+            responses: Arc::new(
+                responses
+                    .into_iter()
+                    .map(|(suffix, body)| (suffix.to_string(), body.to_string()))
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    /// 東京都千代田区・中央区の住所マスタのみを返すモック
+    pub(crate) fn tokyo() -> Self {
+        Self::with_responses([
+            (
+                "東京都/master.json",
+                r#"{"name": "東京都", "cities": ["千代田区", "中央区"]}"#,
+            ),
+            (
+                "東京都/千代田区.json",
+                r#"[{"town": "千代田", "koaza": "", "lat": 35.68391, "lng": 139.754282},
+                    {"town": "丸の内一丁目", "koaza": "", "lat": 35.68156, "lng": 139.767201}]"#,
+            ),
+            (
+                "東京都/中央区.json",
+                r#"[{"town": "銀座一丁目", "koaza": "", "lat": 35.674845, "lng": 139.770348}]"#,
+            ),
+        ])
+    }
+
+    /// 一致するものがない場合に、通信エラーを返すようにする
+    #[cfg(feature = "experimental")]
+    pub(crate) fn network_error(self) -> Self {
+        Self {
+            network_error: true,
+            ..self
+        }
+    }
+
+    /// これまでに取得した回数
+    pub(crate) fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+
+    fn body(&self, url: &str) -> Result<&str, ApiClientError> {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        let path = percent_decode_str(url).decode_utf8_lossy();
+        match self
+            .responses
+            .iter()
+            .find(|(suffix, _)| path.ends_with(suffix.as_str()))
+        {
+            Some((_, body)) => Ok(body),
+            None if self.network_error => Err(ApiClientError::Request {
+                url: url.to_string(),
+                message: "timeout".to_string(),
+            }),
+            None => Err(ApiClientError::NotFound {
+                url: url.to_string(),
+            }),
+        }
+    }
+
+    fn generate_dummy_response<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
+        Ok(serde_json::from_str(self.body(url)?).unwrap())
+    }
+}
+
+impl ApiClient for MockApiClient {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
+        self.generate_dummy_response(url)
+    }
+
+    #[cfg(feature = "blocking")]
+    fn fetch_blocking<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
+        self.generate_dummy_response(url)
+    }
+
+    /// 登録したレスポンスボディをそのまま返す
+    async fn fetch_revalidate(
+        &self,
+        url: &str,
+        _validators: &Validators,
+    ) -> Result<Revalidation, ApiClientError> {
+        Ok(Revalidation::Modified {
+            body: self.body(url)?.as_bytes().to_vec(),
+            validators: Validators::default(),
+        })
+    }
+}
@@ -1,41 +1,267 @@
+use crate::http::cache::{Revalidation, Validators};
 use crate::http::client::ApiClient;
 use crate::http::error::ApiClientError;
+use reqwest::header::{
+    HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER,
+};
+use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
+use std::hash::{BuildHasher, Hasher};
+#[cfg(feature = "blocking")]
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Settings of `ReqwestApiClient`
+///
+/// タイムアウト、再試行、プロキシを指定します。指定しなかった項目は初期値を使用します。
+///
+/// 接続に失敗した場合、タイムアウトした場合、およびステータスコードが408、429、500、502、503、504の場合は、
+/// 指数関数的に待ち時間を延ばしながら`max_retries`回まで再試行します。
+/// 待ち時間は`initial_backoff * 2^(再試行した回数)`の半分から全体までの間でランダムに決まり、`max_backoff`を超えません。
+///
+/// # Example
+/// ```
+/// use japanese_address_parser::http::reqwest_client::{ReqwestApiClient, ReqwestConfig};
+/// use japanese_address_parser::parser::Parser;
+/// use std::time::Duration;
+///
+/// let config = ReqwestConfig::default()
+///     .timeout(Duration::from_secs(10))
+///     .max_retries(5)
+///     .proxy("http://proxy.example.com:8080");
+/// let client = ReqwestApiClient::with_config(config).unwrap();
+/// let parser = Parser::with_api_client(client);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReqwestConfig {
+    timeout: Duration,
+    connect_timeout: Duration,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    proxy: Option<String>,
+}
+
+impl Default for ReqwestConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            proxy: None,
+        }
+    }
+}
+
+impl ReqwestConfig {
+    /// 1回のリクエストのタイムアウトを指定します。再試行の待ち時間は含みません。
+    ///
+    /// 初期値は30秒です。
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 接続のタイムアウトを指定します。wasm32では使用しません。
+    ///
+    /// 初期値は10秒です。
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// 再試行する回数の上限を指定します。`0`を指定すると再試行しません。
+    ///
+    /// 初期値は3回です。
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// 1回目の再試行までの待ち時間の基準を指定します。
+    ///
+    /// 初期値は200ミリ秒です。
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// 再試行までの待ち時間の上限を指定します。`Retry-After`ヘッダーで指定された待ち時間もこの値を超えません。
+    ///
+    /// 初期値は5秒です。
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// 全てのリクエストに使用するプロキシを指定します。wasm32では使用しません。
+    ///
+    /// 指定しない場合は、環境変数`HTTP_PROXY`/`HTTPS_PROXY`の設定に従います。
+    pub fn proxy(mut self, proxy_url: &str) -> Self {
+        self.proxy = Some(proxy_url.to_string());
+        self
+    }
+
+    /// `attempt`回目の再試行までの待ち時間
+    fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let exponential = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff);
+        // 同時に失敗したリクエストが一斉に再試行しないよう、待ち時間を半分から全体までの間でばらつかせる
+        let half = exponential / 2;
+        let jittered = half + half.mul_f64(random_ratio());
+        retry_after.map_or(jittered, |retry_after| {
+            retry_after.max(jittered).min(self.max_backoff)
+        })
+    }
+}
 
 /// An implementation of `ApiClient` with `reqwest`
-pub struct ReqwestApiClient {}
+///
+/// 1つのコネクションプールを共有し、複製したインスタンスも同じプールを使用します。
+/// タイムアウトや再試行の設定を変更する場合は`with_config`を使用してください。
+#[derive(Clone)]
+pub struct ReqwestApiClient {
+    client: reqwest::Client,
+    /// 非同期ランタイム内で生成・破棄できないため、`fetch_blocking`を初めて呼び出したときに生成する
+    #[cfg(feature = "blocking")]
+    blocking_client: Arc<OnceLock<reqwest::blocking::Client>>,
+    config: ReqwestConfig,
+}
 
-impl ApiClient for ReqwestApiClient {
-    fn new() -> Self {
-        ReqwestApiClient {}
+impl Default for ReqwestApiClient {
+    fn default() -> Self {
+        Self::with_config(ReqwestConfig::default())
+            .expect("failed to build the default http client")
     }
+}
 
-    async fn fetch<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
-        let response = if cfg!(target_arch = "wasm32") {
-            reqwest::get(url).await
-        } else {
-            reqwest::Client::builder()
-                .user_agent(format!(
-                    "{}/{}",
-                    env!("CARGO_PKG_NAME"),
-                    env!("CARGO_PKG_VERSION")
-                ))
-                .build()
-                .unwrap()
+impl ReqwestApiClient {
+    /// Constructs a new `ReqwestApiClient` with the given settings.
+    ///
+    /// プロキシのURLが不正な場合などは`ApiClientError::Request`を返します。
+    pub fn with_config(config: ReqwestConfig) -> Result<Self, ApiClientError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let builder = {
+            let mut builder = reqwest::Client::builder()
+                .user_agent(user_agent())
+                .connect_timeout(config.connect_timeout);
+            if let Some(proxy_url) = &config.proxy {
+                builder = builder.proxy(proxy(proxy_url)?);
+            }
+            builder
+        };
+        #[cfg(target_arch = "wasm32")]
+        let builder = reqwest::Client::builder();
+        let client = builder.build().map_err(|e| ApiClientError::Request {
+            url: String::new(),
+            message: e.to_string(),
+        })?;
+        Ok(Self {
+            client,
+            #[cfg(feature = "blocking")]
+            blocking_client: Default::default(),
+            config,
+        })
+    }
+
+    /// リクエストを送信し、再試行できる失敗の場合は待ち時間を置いて再試行する
+    async fn send(
+        &self,
+        url: &str,
+        headers: HeaderMap,
+    ) -> Result<reqwest::Response, ApiClientError> {
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .client
                 .get(url)
+                .headers(headers.clone())
+                .timeout(self.config.timeout)
                 .send()
-                .await
+                .await;
+            let retry_after = match &result {
+                Ok(response) if is_retriable_status(response.status()) => {
+                    retry_after(response.headers())
+                }
+                Err(error) if is_retriable_error(error) => None,
+                _ => return result.map_err(|e| request_error(url, e)),
+            };
+            if attempt >= self.config.max_retries {
+                return result.map_err(|e| request_error(url, e));
+            }
+            let backoff = self.config.backoff(attempt, retry_after);
+            log::debug!("{}の取得に失敗したため、{:?}後に再試行します", url, backoff);
+            sleep(backoff).await;
+            attempt += 1;
         }
-        .map_err(|e| ApiClientError::Request {
-            url: url.to_string(),
+    }
+
+    #[cfg(feature = "blocking")]
+    fn blocking_client(&self) -> Result<&reqwest::blocking::Client, ApiClientError> {
+        if let Some(client) = self.blocking_client.get() {
+            return Ok(client);
+        }
+        let mut builder = reqwest::blocking::Client::builder()
+            .user_agent(user_agent())
+            .connect_timeout(self.config.connect_timeout);
+        if let Some(proxy_url) = &self.config.proxy {
+            builder = builder.proxy(proxy(proxy_url)?);
+        }
+        let client = builder.build().map_err(|e| ApiClientError::Request {
+            url: String::new(),
             message: e.to_string(),
         })?;
+        // 他のスレッドが先に生成した場合はそちらを使用する
+        Ok(self.blocking_client.get_or_init(|| client))
+    }
+
+    /// `send`の同期版
+    #[cfg(feature = "blocking")]
+    fn send_blocking(
+        &self,
+        url: &str,
+        headers: HeaderMap,
+    ) -> Result<reqwest::blocking::Response, ApiClientError> {
+        let client = self.blocking_client()?;
+        let mut attempt = 0;
+        loop {
+            let result = client
+                .get(url)
+                .headers(headers.clone())
+                .timeout(self.config.timeout)
+                .send();
+            let retry_after = match &result {
+                Ok(response) if is_retriable_status(response.status()) => {
+                    retry_after(response.headers())
+                }
+                Err(error) if is_retriable_error(error) => None,
+                _ => return result.map_err(|e| request_error(url, e)),
+            };
+            if attempt >= self.config.max_retries {
+                return result.map_err(|e| request_error(url, e));
+            }
+            let backoff = self.config.backoff(attempt, retry_after);
+            log::debug!("{}の取得に失敗したため、{:?}後に再試行します", url, backoff);
+            std::thread::sleep(backoff);
+            attempt += 1;
+        }
+    }
+}
+
+impl ApiClient for ReqwestApiClient {
+    fn new() -> Self {
+        Default::default()
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
+        let response = self.send(url, HeaderMap::new()).await?;
         let status = response.status();
         if !status.is_success() {
-            return Err(ApiClientError::Request {
-                url: url.to_string(),
-                message: status.to_string(),
-            });
+            return Err(status_error(url, status));
         }
 
         response
@@ -49,28 +275,11 @@ impl ApiClient for ReqwestApiClient {
 
     #[cfg(feature = "blocking")]
     fn fetch_blocking<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
-        let client = reqwest::blocking::Client::builder()
-            .user_agent(format!(
-                "{}/{}",
-                env!("CARGO_PKG_NAME"),
-                env!("CARGO_PKG_VERSION")
-            ))
-            .build()
-            .unwrap();
-        let response = client
-            .get(url)
-            .send()
-            .map_err(|e| ApiClientError::Request {
-                url: url.to_string(),
-                message: e.to_string(),
-            })?;
+        let response = self.send_blocking(url, HeaderMap::new())?;
 
         let status = response.status();
         if !status.is_success() {
-            return Err(ApiClientError::Request {
-                url: url.to_string(),
-                message: status.to_string(),
-            });
+            return Err(status_error(url, status));
         }
 
         response
@@ -80,18 +289,239 @@ impl ApiClient for ReqwestApiClient {
                 message: e.to_string(),
             })
     }
+
+    async fn fetch_revalidate(
+        &self,
+        url: &str,
+        validators: &Validators,
+    ) -> Result<Revalidation, ApiClientError> {
+        let response = self.send(url, conditional_headers(validators)).await?;
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            return Ok(Revalidation::NotModified);
+        }
+        if !status.is_success() {
+            return Err(status_error(url, status));
+        }
+
+        let validators = response_validators(response.headers());
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| ApiClientError::Request {
+                url: url.to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(Revalidation::Modified {
+            body: body.to_vec(),
+            validators,
+        })
+    }
+
+    #[cfg(feature = "blocking")]
+    fn fetch_revalidate_blocking(
+        &self,
+        url: &str,
+        validators: &Validators,
+    ) -> Result<Revalidation, ApiClientError> {
+        let response = self.send_blocking(url, conditional_headers(validators))?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            return Ok(Revalidation::NotModified);
+        }
+        if !status.is_success() {
+            return Err(status_error(url, status));
+        }
+
+        let validators = response_validators(response.headers());
+        let body = response.bytes().map_err(|e| ApiClientError::Request {
+            url: url.to_string(),
+            message: e.to_string(),
+        })?;
+        Ok(Revalidation::Modified {
+            body: body.to_vec(),
+            validators,
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn user_agent() -> String {
+    format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn proxy(proxy_url: &str) -> Result<reqwest::Proxy, ApiClientError> {
+    reqwest::Proxy::all(proxy_url).map_err(|e| ApiClientError::Request {
+        url: proxy_url.to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// 成功以外のステータスコードをエラーに変換する
+fn status_error(url: &str, status: StatusCode) -> ApiClientError {
+    if status == StatusCode::NOT_FOUND {
+        return ApiClientError::NotFound {
+            url: url.to_string(),
+        };
+    }
+    ApiClientError::Request {
+        url: url.to_string(),
+        message: status.to_string(),
+    }
+}
+
+fn request_error(url: &str, error: reqwest::Error) -> ApiClientError {
+    ApiClientError::Request {
+        url: url.to_string(),
+        message: error.to_string(),
+    }
+}
+
+/// 時間を置けば成功する可能性があるステータスコード
+fn is_retriable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// 接続の失敗やタイムアウトなど、時間を置けば成功する可能性があるエラー
+fn is_retriable_error(error: &reqwest::Error) -> bool {
+    error.is_timeout() || error.is_connect() || error.is_request()
+}
+
+/// `Retry-After`ヘッダーで指定された待ち時間(秒数での指定のみ対応)
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let seconds = headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+/// 0以上1未満の乱数
+fn random_ratio() -> f64 {
+    let value = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    (value >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// 条件付きリクエストのヘッダーを組み立てる
+fn conditional_headers(validators: &Validators) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(value) = validators.etag.as_ref().and_then(|v| v.parse().ok()) {
+        headers.insert(IF_NONE_MATCH, value);
+    }
+    if let Some(value) = validators
+        .last_modified
+        .as_ref()
+        .and_then(|v| v.parse().ok())
+    {
+        headers.insert(IF_MODIFIED_SINCE, value);
+    }
+    headers
+}
+
+/// レスポンスヘッダーから次回の再検証に使用する値を取り出す
+fn response_validators(headers: &HeaderMap) -> Validators {
+    let value = |name| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+    };
+    Validators {
+        etag: value(ETAG),
+        last_modified: value(LAST_MODIFIED),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::http::reqwest_client::{retry_after, ReqwestConfig};
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+    use std::time::Duration;
+
+    #[test]
+    fn backoff_再試行するたびに待ち時間が延びる() {
+        let config = ReqwestConfig::default()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(1));
+        for (attempt, expected) in [(0, 100), (1, 200), (2, 400), (3, 800), (4, 1000)] {
+            let backoff = config.backoff(attempt, None);
+            let expected = Duration::from_millis(expected);
+            assert!(
+                backoff >= expected / 2,
+                "{:?} < {:?}",
+                backoff,
+                expected / 2
+            );
+            assert!(backoff <= expected, "{:?} > {:?}", backoff, expected);
+        }
+    }
+
+    #[test]
+    fn backoff_retry_afterを上限の範囲で優先する() {
+        let config = ReqwestConfig::default()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(5));
+        assert_eq!(
+            config.backoff(0, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            config.backoff(0, Some(Duration::from_secs(60))),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn retry_after_秒数で指定された場合のみ読み取る() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(3)));
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
 }
 
 #[cfg(all(test, feature = "experimental", not(target_arch = "wasm32")))]
 mod async_tests {
     use crate::domain::chimei_ruiju::entity::PrefectureMaster;
+    use crate::http::cache::{Revalidation, Validators};
     use crate::http::client::ApiClient;
-    use crate::http::reqwest_client::ReqwestApiClient;
+    use crate::http::reqwest_client::{ReqwestApiClient, ReqwestConfig};
+    use std::time::Duration;
 
     #[tokio::test]
     async fn 不正なurlを渡した場合_requestエラーになる() {
         let invalid_url = "htttp://chimei-ruiju.org";
-        let api_client = ReqwestApiClient {};
+        let api_client = ReqwestApiClient::default();
 
         let result = api_client.fetch::<PrefectureMaster>(invalid_url).await;
         assert!(result.is_err());
@@ -99,7 +529,7 @@ mod async_tests {
     }
 
     #[tokio::test]
-    async fn レスポンスが404の場合_requestエラーになる() {
+    async fn レスポンスが404の場合_not_foundエラーになる() {
         let mut server = mockito::Server::new_async().await;
         let url = format!("{}/master.json", &server.url());
         let mock = server
@@ -108,10 +538,10 @@ mod async_tests {
             .create_async()
             .await;
 
-        let api_client = ReqwestApiClient {};
+        let api_client = ReqwestApiClient::default();
         let result = api_client.fetch::<PrefectureMaster>(&url).await;
         assert!(result.is_err());
-        assert!(result.unwrap_err().is_request());
+        assert!(result.unwrap_err().is_not_found());
 
         mock.assert_async().await;
     }
@@ -128,7 +558,7 @@ mod async_tests {
             .create_async()
             .await;
 
-        let api_client = ReqwestApiClient {};
+        let api_client = ReqwestApiClient::default();
         let result = api_client.fetch::<PrefectureMaster>(&url).await;
         assert!(result.is_err());
         assert!(result.unwrap_err().is_deserialize());
@@ -148,7 +578,7 @@ mod async_tests {
             .create_async()
             .await;
 
-        let api_client = ReqwestApiClient {};
+        let api_client = ReqwestApiClient::default();
         let result = api_client.fetch::<PrefectureMaster>(&url).await;
         assert!(result.is_ok());
         let data = result.unwrap();
@@ -159,6 +589,152 @@ mod async_tests {
 
         mock.assert_async().await;
     }
+
+    #[tokio::test]
+    async fn 再検証_変更がない場合_not_modifiedを返す() {
+        let mut server = mockito::Server::new_async().await;
+        let url = format!("{}/master.json", &server.url());
+        let mock = server
+            .mock("GET", "/master.json")
+            .match_header("if-none-match", "\"v1\"")
+            .with_status(304)
+            .create_async()
+            .await;
+
+        let api_client = ReqwestApiClient::default();
+        let validators = Validators {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+        };
+        let result = api_client.fetch_revalidate(&url, &validators).await;
+        assert_eq!(result.unwrap(), Revalidation::NotModified);
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn 再検証_変更がある場合_新しい内容と検証用の値を返す() {
+        let mut server = mockito::Server::new_async().await;
+        let url = format!("{}/master.json", &server.url());
+        let mock = server
+            .mock("GET", "/master.json")
+            .with_status(200)
+            .with_header("etag", "\"v2\"")
+            .with_header("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT")
+            .with_body("[]")
+            .create_async()
+            .await;
+
+        let api_client = ReqwestApiClient::default();
+        let result = api_client
+            .fetch_revalidate(&url, &Validators::default())
+            .await;
+        assert_eq!(
+            result.unwrap(),
+            Revalidation::Modified {
+                body: b"[]".to_vec(),
+                validators: Validators {
+                    etag: Some("\"v2\"".to_string()),
+                    last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+                },
+            }
+        );
+
+        mock.assert_async().await;
+    }
+
+    fn retrying_client() -> ReqwestApiClient {
+        let config = ReqwestConfig::default()
+            .max_retries(2)
+            .initial_backoff(Duration::from_millis(1));
+        ReqwestApiClient::with_config(config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn 一時的なエラーの場合_再試行して成功したデータを返す() {
+        let mut server = mockito::Server::new_async().await;
+        let url = format!("{}/master.json", &server.url());
+        let unavailable = server
+            .mock("GET", "/master.json")
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("GET", "/master.json")
+            .with_status(200)
+            .with_body(r#"{"name": "新浜県", "cities": ["新浜市"], "coordinate": {"latitude": 34.6570413, "longitude": 135.2741341}}"#)
+            .create_async()
+            .await;
+
+        let result = retrying_client().fetch::<PrefectureMaster>(&url).await;
+        assert_eq!(result.unwrap().name, "新浜県");
+
+        unavailable.assert_async().await;
+        ok.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn 再試行の上限に達した場合_requestエラーになる() {
+        let mut server = mockito::Server::new_async().await;
+        let url = format!("{}/master.json", &server.url());
+        let mock = server
+            .mock("GET", "/master.json")
+            .with_status(500)
+            .expect(3)
+            .create_async()
+            .await;
+
+        let result = retrying_client().fetch::<PrefectureMaster>(&url).await;
+        assert!(result.unwrap_err().is_request());
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn 再試行しても成功しないエラーの場合_再試行しない() {
+        let mut server = mockito::Server::new_async().await;
+        let url = format!("{}/master.json", &server.url());
+        let mock = server
+            .mock("GET", "/master.json")
+            .with_status(404)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let result = retrying_client().fetch::<PrefectureMaster>(&url).await;
+        assert!(result.unwrap_err().is_not_found());
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn プロキシを指定した場合_プロキシを経由する() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", mockito::Matcher::Any)
+            .match_header("host", "master.example.com")
+            .with_status(200)
+            .with_body(r#"{"name": "新浜県", "cities": ["新浜市"], "coordinate": {"latitude": 34.6570413, "longitude": 135.2741341}}"#)
+            .create_async()
+            .await;
+
+        let config = ReqwestConfig::default().proxy(&server.url());
+        let api_client = ReqwestApiClient::with_config(config).unwrap();
+        let result = api_client
+            .fetch::<PrefectureMaster>("http://master.example.com/master.json")
+            .await;
+        assert_eq!(result.unwrap().name, "新浜県");
+
+        mock.assert_async().await;
+    }
+
+    #[test]
+    fn プロキシのurlが不正な場合_requestエラーになる() {
+        let config = ReqwestConfig::default().proxy("not a url");
+        let result = ReqwestApiClient::with_config(config);
+        assert!(result.err().unwrap().is_request());
+    }
 }
 
 #[cfg(all(
@@ -170,27 +746,28 @@ mod async_tests {
 mod blocking_tests {
     use crate::domain::chimei_ruiju::entity::PrefectureMaster;
     use crate::http::client::ApiClient;
-    use crate::http::reqwest_client::ReqwestApiClient;
+    use crate::http::reqwest_client::{ReqwestApiClient, ReqwestConfig};
+    use std::time::Duration;
 
     #[test]
     fn 不正なurlを渡した場合_requestエラーになる() {
         let invalid_url = "htttp://chimei-ruiju.org";
-        let api_client = ReqwestApiClient {};
+        let api_client = ReqwestApiClient::default();
         let result = api_client.fetch_blocking::<PrefectureMaster>(invalid_url);
         assert!(result.is_err());
         assert!(result.unwrap_err().is_request());
     }
 
     #[test]
-    fn レスポンスが404の場合_requestエラーになる() {
+    fn レスポンスが404の場合_not_foundエラーになる() {
         let mut server = mockito::Server::new();
         let url = format!("{}/master.json", &server.url());
         let mock = server.mock("GET", "/master.json").with_status(404).create();
 
-        let api_client = ReqwestApiClient {};
+        let api_client = ReqwestApiClient::default();
         let result = api_client.fetch_blocking::<PrefectureMaster>(&url);
         assert!(result.is_err());
-        assert!(result.unwrap_err().is_request());
+        assert!(result.unwrap_err().is_not_found());
 
         mock.assert();
     }
@@ -206,7 +783,7 @@ mod blocking_tests {
             .with_body(r#"{"hoge": true, "piyo": 100}"#)
             .create();
 
-        let api_client = ReqwestApiClient {};
+        let api_client = ReqwestApiClient::default();
         let result = api_client.fetch_blocking::<PrefectureMaster>(&url);
         assert!(result.is_err());
         assert!(result.unwrap_err().is_deserialize());
@@ -225,7 +802,7 @@ mod blocking_tests {
             .with_body(r#"{"name": "新浜県", "cities": ["新浜市"], "coordinate": {"latitude": 34.6570413, "longitude": 135.2741341}}"#)
             .create();
 
-        let api_client = ReqwestApiClient {};
+        let api_client = ReqwestApiClient::default();
         let result = api_client.fetch_blocking::<PrefectureMaster>(&url);
         assert!(result.is_ok());
         let data = result.unwrap();
@@ -236,4 +813,30 @@ mod blocking_tests {
 
         mock.assert();
     }
+
+    #[test]
+    fn 一時的なエラーの場合_再試行して成功したデータを返す() {
+        let mut server = mockito::Server::new();
+        let url = format!("{}/master.json", &server.url());
+        let unavailable = server
+            .mock("GET", "/master.json")
+            .with_status(503)
+            .expect(1)
+            .create();
+        let ok = server
+            .mock("GET", "/master.json")
+            .with_status(200)
+            .with_body(r#"{"name": "新浜県", "cities": ["新浜市"], "coordinate": {"latitude": 34.6570413, "longitude": 135.2741341}}"#)
+            .create();
+
+        let config = ReqwestConfig::default()
+            .max_retries(1)
+            .initial_backoff(Duration::from_millis(1));
+        let api_client = ReqwestApiClient::with_config(config).unwrap();
+        let result = api_client.fetch_blocking::<PrefectureMaster>(&url);
+        assert_eq!(result.unwrap().name, "新浜県");
+
+        unavailable.assert();
+        ok.assert();
+    }
 }
@@ -1,6 +1,7 @@
 use crate::domain::chimei_ruiju::entity::{CityMaster, PrefectureMaster, TownMaster};
 use crate::domain::chimei_ruiju::error::ApiError;
 use crate::http::client::ApiClient;
+use crate::http::endpoints::Endpoints;
 use crate::repository::chimei_ruiju::city::CityMasterRepository;
 use crate::repository::chimei_ruiju::prefecture::PrefectureMasterRepository;
 use crate::repository::chimei_ruiju::town::TownMasterRepository;
@@ -39,17 +40,49 @@ impl<Client: ApiClient> Default for ChimeiRuijuInteractorImpl<Client> {
         Self {
             prefecture_repository: PrefectureMasterRepository {
                 api_client: Client::new(),
+                base_url: Endpoints::default().chimei_ruiju,
             },
             city_repository: CityMasterRepository {
                 api_client: Client::new(),
+                base_url: Endpoints::default().chimei_ruiju,
             },
             town_repository: TownMasterRepository {
                 api_client: Client::new(),
+                base_url: Endpoints::default().chimei_ruiju,
             },
         }
     }
 }
 
+impl<Client: ApiClient + Clone> ChimeiRuijuInteractorImpl<Client> {
+    pub(crate) fn new(api_client: Client) -> Self {
+        Self {
+            prefecture_repository: PrefectureMasterRepository {
+                api_client: api_client.clone(),
+                base_url: Endpoints::default().chimei_ruiju,
+            },
+            city_repository: CityMasterRepository {
+                api_client: api_client.clone(),
+                base_url: Endpoints::default().chimei_ruiju,
+            },
+            town_repository: TownMasterRepository {
+                api_client,
+                base_url: Endpoints::default().chimei_ruiju,
+            },
+        }
+    }
+}
+
+impl<Client: ApiClient> ChimeiRuijuInteractorImpl<Client> {
+    /// 住所マスタの取得先を変更する
+    #[cfg_attr(not(feature = "experimental"), allow(dead_code))]
+    pub(crate) fn set_endpoints(&mut self, endpoints: &Endpoints) {
+        self.prefecture_repository.base_url = endpoints.chimei_ruiju.clone();
+        self.city_repository.base_url = endpoints.chimei_ruiju.clone();
+        self.town_repository.base_url = endpoints.chimei_ruiju.clone();
+    }
+}
+
 impl<Client: ApiClient> ChimeiRuijuInteractor for ChimeiRuijuInteractorImpl<Client> {
     async fn get_prefecture_master(
         &self,
@@ -1,6 +1,7 @@
 use crate::domain::geolonia::entity::{City, Prefecture};
 use crate::domain::geolonia::error::Error;
 use crate::http::client::ApiClient;
+use crate::http::endpoints::Endpoints;
 use crate::repository::geolonia::city::CityMasterRepository;
 use crate::repository::geolonia::prefecture::PrefectureMasterRepository;
 
@@ -24,6 +25,7 @@ pub(crate) trait GeoloniaInteractor {
     ) -> Result<City, Error>;
 }
 
+#[derive(Clone)]
 pub(crate) struct GeoloniaInteractorImpl<Client: ApiClient> {
     prefecture_repository: PrefectureMasterRepository<Client>,
     city_repository: CityMasterRepository<Client>,
@@ -34,14 +36,39 @@ impl<Client: ApiClient> Default for GeoloniaInteractorImpl<Client> {
         Self {
             prefecture_repository: PrefectureMasterRepository {
                 api_client: Client::new(),
+                base_url: Endpoints::default().geolonia_prefecture,
             },
             city_repository: CityMasterRepository {
                 api_client: Client::new(),
+                base_url: Endpoints::default().geolonia,
             },
         }
     }
 }
 
+impl<Client: ApiClient + Clone> GeoloniaInteractorImpl<Client> {
+    pub(crate) fn new(api_client: Client) -> Self {
+        Self {
+            prefecture_repository: PrefectureMasterRepository {
+                api_client: api_client.clone(),
+                base_url: Endpoints::default().geolonia_prefecture,
+            },
+            city_repository: CityMasterRepository {
+                api_client,
+                base_url: Endpoints::default().geolonia,
+            },
+        }
+    }
+}
+
+impl<Client: ApiClient> GeoloniaInteractorImpl<Client> {
+    /// 住所マスタの取得先を変更する
+    pub(crate) fn set_endpoints(&mut self, endpoints: &Endpoints) {
+        self.prefecture_repository.base_url = endpoints.geolonia_prefecture.clone();
+        self.city_repository.base_url = endpoints.geolonia.clone();
+    }
+}
+
 impl<Client: ApiClient> GeoloniaInteractor for GeoloniaInteractorImpl<Client> {
     async fn get_prefecture_master(&self, prefecture_name: &str) -> Result<Prefecture, Error> {
         self.prefecture_repository.get(prefecture_name).await
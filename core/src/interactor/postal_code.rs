@@ -0,0 +1,69 @@
+use crate::domain::geolonia::error::Error;
+use crate::domain::postal_code::entity::PostalCodeEntry;
+use crate::http::client::ApiClient;
+use crate::http::endpoints::Endpoints;
+use crate::repository::postal_code::PostalCodeRepository;
+
+pub(crate) trait PostalCodeInteractor {
+    /// 郵便番号に対応する地域を取得(非同期)
+    async fn get_postal_code_entries(
+        &self,
+        postal_code: &str,
+    ) -> Result<Vec<PostalCodeEntry>, Error>;
+
+    /// 郵便番号に対応する地域を取得(同期)
+    #[cfg(feature = "blocking")]
+    fn get_blocking_postal_code_entries(
+        &self,
+        postal_code: &str,
+    ) -> Result<Vec<PostalCodeEntry>, Error>;
+}
+
+#[derive(Clone)]
+pub(crate) struct PostalCodeInteractorImpl<Client: ApiClient> {
+    repository: PostalCodeRepository<Client>,
+}
+
+impl<Client: ApiClient> Default for PostalCodeInteractorImpl<Client> {
+    fn default() -> Self {
+        Self {
+            repository: PostalCodeRepository {
+                api_client: Client::new(),
+                base_url: Endpoints::default().postal_code,
+            },
+        }
+    }
+}
+
+impl<Client: ApiClient> PostalCodeInteractorImpl<Client> {
+    pub(crate) fn new(api_client: Client) -> Self {
+        Self {
+            repository: PostalCodeRepository {
+                api_client,
+                base_url: Endpoints::default().postal_code,
+            },
+        }
+    }
+
+    /// 郵便番号データの取得先を変更する
+    pub(crate) fn set_endpoints(&mut self, endpoints: &Endpoints) {
+        self.repository.base_url = endpoints.postal_code.clone();
+    }
+}
+
+impl<Client: ApiClient> PostalCodeInteractor for PostalCodeInteractorImpl<Client> {
+    async fn get_postal_code_entries(
+        &self,
+        postal_code: &str,
+    ) -> Result<Vec<PostalCodeEntry>, Error> {
+        self.repository.get(postal_code).await
+    }
+
+    #[cfg(feature = "blocking")]
+    fn get_blocking_postal_code_entries(
+        &self,
+        postal_code: &str,
+    ) -> Result<Vec<PostalCodeEntry>, Error> {
+        self.repository.get_blocking(postal_code)
+    }
+}
@@ -5,9 +5,15 @@
 //! - `city-name-correction`*(enabled by default)*: Enable autocorrection if ambiguous city name was typed
 //! - `format-house-number`: Enable normalization of addresses after town name
 //! - `eliminate-whitespaces`*(experimental)*: Enable elimination of whitespaces from given text
-//! - `enable-api-client-cache`: Enable In-Memory cache for api client
+//! - `enable-api-client-cache`: Enable In-Memory cache for api client (see [`http::cached_client::CachedApiClient`] for other cache stores)
 //! - `fix-halfwidth-katakana`*(experimental)*: Enable fixing halfwidth katakana with fullwidth ones
 //! - `experimental`: Enable experimental module
+//! - `snapshot`: Enable downloading master data as a snapshot for offline use
+//! - `embedded-master`: Embed master data into the binary at build time (see [`source::embedded`])
+//!
+//! `city-name-correction`, `format-house-number`, `eliminate-whitespaces` and `fix-halfwidth-katakana`
+//! only decide the default values of [`parser::ParseOptions`].
+//! They can be switched per `Parser` at runtime regardless of the enabled features.
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #[cfg(all(target_family = "wasm", feature = "blocking"))]
@@ -25,5 +31,10 @@ pub mod http;
 mod interactor;
 pub mod parser;
 mod repository;
+pub mod reverse_geocoder;
+#[cfg(all(feature = "snapshot", not(target_arch = "wasm32")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+pub mod snapshot;
+pub mod source;
 mod tokenizer;
 mod util;
@@ -1,35 +1,63 @@
-mod pure;
+mod batch;
+mod candidate;
+mod driver;
+mod extract;
+mod geocode;
+mod options;
+mod postal_code;
+pub mod pure;
 
 use std::sync::Arc;
 
 use crate::domain::common::token::Token;
-use crate::domain::geolonia::entity::Address;
-use crate::domain::geolonia::error::Error;
+use crate::domain::geolonia::entity::{Address, AddressSpans, Coordinate, Correction, Span, Town};
+use crate::domain::geolonia::error::{Error, ParseErrorKind};
+use crate::formatter::house_number::parse_house_number;
 #[cfg(feature = "enable-api-client-cache")]
 use crate::http::cached_client::CachedApiClient;
+use crate::http::client::ApiClient;
+use crate::http::endpoints::Endpoints;
 use crate::http::reqwest_client::ReqwestApiClient;
-use crate::interactor::geolonia::{GeoloniaInteractor, GeoloniaInteractorImpl};
-use crate::parser::pure::{PureParser, PureParserAction};
+use crate::interactor::postal_code::PostalCodeInteractorImpl;
+use crate::parser::driver::MasterCache;
+#[cfg(feature = "embedded-master")]
+use crate::source::embedded::EmbeddedSource;
+use crate::source::{AddressMasterSource, ApiClientSource};
 use crate::tokenizer::{End, Tokenizer};
 use serde::Serialize;
 
+pub use options::ParseOptions;
+
 impl From<Tokenizer<End>> for Address {
     fn from(value: Tokenizer<End>) -> Self {
         let mut address = Address::new("", "", "", "");
         for token in value.tokens {
             match token {
+                Token::PostalCode(postal_code) => address.postal_code = Some(postal_code),
                 Token::Prefecture(prefecture_name) => address.prefecture = prefecture_name,
                 Token::City(city_name) => address.city = city_name,
                 Token::Town(town_name) => address.town = town_name,
+                Token::Koaza(koaza) => address.koaza = koaza,
                 Token::Rest(rest) => address.rest = rest,
             }
         }
+        if !address.town.is_empty() {
+            address.rest_components = parse_house_number(&address.rest);
+        }
         address
     }
 }
 
+#[cfg(not(feature = "enable-api-client-cache"))]
+type DefaultApiClient = ReqwestApiClient;
+#[cfg(feature = "enable-api-client-cache")]
+type DefaultApiClient = CachedApiClient<ReqwestApiClient>;
+
 /// An asynchronous `Parser` to process addresses.
 ///
+/// 住所マスタの取得に使用する`ApiClient`を指定したい場合は`Parser::with_api_client`を、
+/// URLを介さずに独自の取得元から住所マスタを読み込みたい場合は`Parser::with_source`を使用してください。
+///
 /// # Example
 /// ```
 /// use japanese_address_parser::parser::Parser;
@@ -40,82 +68,316 @@ impl From<Tokenizer<End>> for Address {
 ///     println!("{:?}", result);
 /// }
 /// ```
-pub struct Parser {
-    #[cfg(not(feature = "enable-api-client-cache"))]
-    interactor: Arc<GeoloniaInteractorImpl<ReqwestApiClient>>,
-    #[cfg(feature = "enable-api-client-cache")]
-    interactor: Arc<GeoloniaInteractorImpl<CachedApiClient<ReqwestApiClient>>>,
+pub struct Parser<
+    Client: ApiClient = DefaultApiClient,
+    Source: AddressMasterSource = ApiClientSource<Client>,
+> {
+    source: Arc<Source>,
+    /// 郵便番号データを参照しない場合は`None`
+    postal_code_interactor: Option<Arc<PostalCodeInteractorImpl<Client>>>,
+    options: ParseOptions,
+    max_concurrency: usize,
+}
+
+impl<Client: ApiClient, Source: AddressMasterSource> Clone for Parser<Client, Source> {
+    /// 住所マスタの取得元と`ApiClient`は複製元と共有します。
+    fn clone(&self) -> Self {
+        Self {
+            source: Arc::clone(&self.source),
+            postal_code_interactor: self.postal_code_interactor.clone(),
+            options: self.options,
+            max_concurrency: self.max_concurrency,
+        }
+    }
 }
 
 impl Default for Parser {
     /// Constructs a new `Parser`.
     fn default() -> Self {
         Self {
-            interactor: Arc::new(Default::default()),
+            source: Arc::new(Default::default()),
+            postal_code_interactor: Some(Arc::new(Default::default())),
+            options: ParseOptions::default(),
+            max_concurrency: batch::DEFAULT_MAX_CONCURRENCY,
+        }
+    }
+}
+
+impl<Client: ApiClient + Clone + Sync> Parser<Client> {
+    /// Constructs a new `Parser` with the given `ApiClient`.
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::http::local_directory_client::LocalDirectoryApiClient;
+    /// use japanese_address_parser::parser::Parser;
+    ///
+    /// async fn example() {
+    ///     // 住所マスタをローカルディレクトリから読み込む
+    ///     let parser = Parser::with_api_client(LocalDirectoryApiClient::with_root("./master-data"));
+    ///     let result = parser.parse("東京都新宿区西新宿2-8-1").await;
+    ///     println!("{:?}", result);
+    /// }
+    /// ```
+    pub fn with_api_client(api_client: Client) -> Self {
+        Self {
+            source: Arc::new(ApiClientSource::new(api_client.clone())),
+            postal_code_interactor: Some(Arc::new(PostalCodeInteractorImpl::new(api_client))),
+            options: ParseOptions::default(),
+            max_concurrency: batch::DEFAULT_MAX_CONCURRENCY,
+        }
+    }
+}
+
+impl<Client: ApiClient + Clone + Sync> Parser<Client> {
+    /// Sets the base URLs of the master data.
+    ///
+    /// 住所マスタと郵便番号データを、`Endpoints`で指定した取得先から取得します。
+    /// 社内のミラーサーバーなどを参照する場合に使用してください。
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::http::endpoints::Endpoints;
+    /// use japanese_address_parser::parser::Parser;
+    ///
+    /// async fn example() {
+    ///     let endpoints = Endpoints::default()
+    ///         .geolonia("https://mirror.example.com/japanese-addresses/api/ja")
+    ///         .geolonia_prefecture("https://mirror.example.com/accompanist")
+    ///         .postal_code("https://mirror.example.com/postal-code-api/api/v1");
+    ///     let parser = Parser::default().endpoints(endpoints);
+    ///     let result = parser.parse("東京都新宿区西新宿2-8-1").await;
+    ///     println!("{:?}", result);
+    /// }
+    /// ```
+    pub fn endpoints(mut self, endpoints: Endpoints) -> Self {
+        // 複製元の`Parser`と取得元を共有している場合は、複製してから変更する
+        Arc::make_mut(&mut self.source)
+            .interactor
+            .set_endpoints(&endpoints);
+        if let Some(postal_code_interactor) = self.postal_code_interactor.as_mut() {
+            Arc::make_mut(postal_code_interactor).set_endpoints(&endpoints);
         }
+        self
     }
 }
 
-impl Parser {
+impl<Source: AddressMasterSource> Parser<DefaultApiClient, Source> {
+    /// Constructs a new `Parser` with the given `AddressMasterSource`.
+    ///
+    /// 都道府県・市区町村ごとの住所マスタを`source`から取得します。
+    /// 通信を行わない取得元を使用できるよう、郵便番号データは参照しません。
+    /// 郵便番号データを取得する場合は`postal_code_api_client`を指定してください。
+    ///
+    /// 実装例は`AddressMasterSource`を参照してください。
+    pub fn with_source(source: Source) -> Self {
+        Self {
+            source: Arc::new(source),
+            postal_code_interactor: None,
+            options: ParseOptions::default(),
+            max_concurrency: batch::DEFAULT_MAX_CONCURRENCY,
+        }
+    }
+}
+
+#[cfg(feature = "embedded-master")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded-master")))]
+impl Parser<DefaultApiClient, EmbeddedSource> {
+    /// Constructs a new `Parser` with the master data embedded at build time.
+    ///
+    /// 住所マスタを取得するための通信を行いません。郵便番号データも参照しないため、
+    /// `lookup_postal_code`と`validate_postal_code`は`ErrorKind::PostalCodeLookupUnavailable`を返します。
+    /// 郵便番号データを取得する場合は`postal_code_api_client`を指定してください。
+    /// 住所マスタの埋め込み方は`source::embedded`を参照してください。
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::parser::Parser;
+    ///
+    /// async fn example() {
+    ///     let parser = Parser::embedded();
+    ///     let result = parser.parse("東京都新宿区西新宿2-8-1").await;
+    ///     println!("{:?}", result);
+    /// }
+    /// ```
+    pub fn embedded() -> Self {
+        Self::with_source(EmbeddedSource::builtin())
+    }
+}
+
+impl<Client: ApiClient, Source: AddressMasterSource> Parser<Client, Source> {
+    /// Sets the `ApiClient` used for looking up postal codes.
+    ///
+    /// `Parser::with_source`や`Parser::embedded`で生成した`Parser`は郵便番号データを参照しません。
+    /// `lookup_postal_code`や`validate_postal_code`を使用する場合は、郵便番号データの取得に使用する`ApiClient`を指定してください。
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::http::local_directory_client::LocalDirectoryApiClient;
+    /// use japanese_address_parser::parser::Parser;
+    /// use japanese_address_parser::source::ApiClientSource;
+    ///
+    /// let api_client = LocalDirectoryApiClient::with_root("./master-data");
+    /// let parser = Parser::with_source(ApiClientSource::new(api_client.clone()))
+    ///     .postal_code_api_client(api_client);
+    /// ```
+    pub fn postal_code_api_client<PostalCodeClient: ApiClient>(
+        self,
+        api_client: PostalCodeClient,
+    ) -> Parser<PostalCodeClient, Source> {
+        Parser {
+            source: self.source,
+            postal_code_interactor: Some(Arc::new(PostalCodeInteractorImpl::new(api_client))),
+            options: self.options,
+            max_concurrency: self.max_concurrency,
+        }
+    }
+
+    /// Enables inferring the prefecture from the city name when it is omitted.
+    ///
+    /// 有効にすると、都道府県名が省略された住所について、市区町村名が一致する都道府県を
+    /// 全ての都道府県の住所マスタから探します。推定した都道府県名は`CorrectionKind::PrefectureCompletion`
+    /// として`corrections`に記録され、`spans.prefecture`は`None`になります。
+    /// 都道府県の住所マスタは`max_concurrency`件ずつ並行して取得し、取得できなかった都道府県は
+    /// 市区町村名が一致しなかったものとして扱います。
+    ///
+    /// 市区町村名が一致する都道府県が複数ある場合(府中市など)、`parse`は`ErrorKind::AmbiguousPrefecture`
+    /// を返し、`parse_candidates`はそれぞれの都道府県での解釈を候補として返します。
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::parser::Parser;
+    ///
+    /// async fn example() {
+    ///     let parser = Parser::default().infer_prefecture(true);
+    ///     let result = parser.parse("横浜市中区本町1-1").await;
+    ///     println!("{:?}", result);
+    /// }
+    /// ```
+    pub fn infer_prefecture(mut self, enabled: bool) -> Self {
+        self.options = self.options.infer_prefecture(enabled);
+        self
+    }
+
+    /// Sets the options used for parsing.
+    ///
+    /// `ParseOptions`の内容で、それまでに`infer_prefecture`などで設定した内容を置き換えます。
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::parser::{ParseOptions, Parser};
+    ///
+    /// async fn example() {
+    ///     let parser = Parser::default()
+    ///         .options(ParseOptions::default().city_name_correction(false).format_house_number(true));
+    ///     let result = parser.parse("東京都千代田区千代田1-1").await;
+    ///     println!("{:?}", result);
+    /// }
+    /// ```
+    pub fn options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     /// Parses the given `address` asynchronously.
     pub async fn parse(&self, address: &str) -> ParseResult {
-        let interactor = self.interactor.clone();
-        let mut pure_parser = PureParser::new(address);
-
-        loop {
-            match pure_parser.advance() {
-                PureParserAction::RequestCityNameList(pref_name) => {
-                    match interactor.get_prefecture_master(&pref_name).await {
-                        Ok(result) => pure_parser.provide_input(result.cities),
-                        Err(error) => return pure_parser.abort(error),
-                    }
-                }
-                PureParserAction::RequestTownNameList(pref_name, city_name) => {
-                    match interactor.get_city_master(&pref_name, &city_name).await {
-                        Ok(result) => {
-                            let town_names = result.towns.into_iter().map(|x| x.name).collect();
-                            pure_parser.provide_input(town_names);
-                        }
-                        Err(error) => return pure_parser.abort(error),
-                    }
-                }
-                PureParserAction::Done(result) => return result,
-            }
+        self.parse_with_masters(address, &mut MasterCache::default())
+            .await
+    }
+
+    /// Returns up to `limit` interpretations of the given `address` asynchronously, ranked by score.
+    ///
+    /// 郡名の補完や表記ゆれの補正などにより複数の解釈が考えられる場合に、町名まで特定できた解釈を
+    /// スコアの高い順に最大`limit`件返します。解釈が一つも得られない場合はエラーを返します。
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::parser::Parser;
+    ///
+    /// async fn example() {
+    ///     let parser: Parser = Default::default();
+    ///     let candidates = parser.parse_candidates("北海道森町字森1", 3).await.unwrap();
+    ///     for candidate in candidates {
+    ///         println!("{} {:?}", candidate.score, candidate.address);
+    ///     }
+    /// }
+    /// ```
+    pub async fn parse_candidates(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<ParseCandidate>, Error> {
+        let prefectures = candidate::prefecture_candidates(
+            &Tokenizer::new(address, &self.options),
+            &self.options,
+        )?;
+        let mut cities = vec![];
+        for (prefecture_name, tokenizer) in &prefectures {
+            let city_names = self.source.prefecture(prefecture_name).await?;
+            cities.extend(
+                candidate::city_candidates(tokenizer, &city_names, limit, &self.options)
+                    .into_iter()
+                    .map(|city| (prefecture_name, city)),
+            );
+        }
+        if cities.is_empty() {
+            return Err(Error::new_parse_error(ParseErrorKind::City));
         }
+        let mut candidates = vec![];
+        for (prefecture_name, city) in &cities {
+            let towns = self.source.city(prefecture_name, &city.name).await?;
+            candidates.extend(candidate::town_candidates(city, &towns, &self.options));
+        }
+        if candidates.is_empty() {
+            return Err(Error::new_parse_error(ParseErrorKind::Town));
+        }
+        Ok(candidate::rank(candidates, limit))
     }
 
     /// Parses the given `address` synchronously.
     #[cfg(feature = "blocking")]
     pub fn parse_blocking(&self, address: &str) -> ParseResult {
-        let interactor = self.interactor.clone();
-        let mut pure_parser = PureParser::new(address);
-
-        loop {
-            match pure_parser.advance() {
-                PureParserAction::RequestCityNameList(pref_name) => {
-                    match interactor.get_blocking_prefecture_master(&pref_name) {
-                        Ok(result) => pure_parser.provide_input(result.cities),
-                        Err(error) => return pure_parser.abort(error),
-                    }
-                }
-                PureParserAction::RequestTownNameList(pref_name, city_name) => {
-                    match interactor.get_blocking_city_master(&pref_name, &city_name) {
-                        Ok(result) => {
-                            let town_names = result.towns.into_iter().map(|x| x.name).collect();
-                            pure_parser.provide_input(town_names);
-                        }
-                        Err(error) => return pure_parser.abort(error),
-                    }
-                }
-                PureParserAction::Done(result) => return result,
-            }
+        self.parse_with_masters_blocking(address, &mut MasterCache::default())
+    }
+
+    /// Returns up to `limit` interpretations of the given `address` synchronously, ranked by score.
+    #[cfg(feature = "blocking")]
+    pub fn parse_candidates_blocking(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<ParseCandidate>, Error> {
+        let prefectures = candidate::prefecture_candidates(
+            &Tokenizer::new(address, &self.options),
+            &self.options,
+        )?;
+        let mut cities = vec![];
+        for (prefecture_name, tokenizer) in &prefectures {
+            let city_names = self.source.prefecture_blocking(prefecture_name)?;
+            cities.extend(
+                candidate::city_candidates(tokenizer, &city_names, limit, &self.options)
+                    .into_iter()
+                    .map(|city| (prefecture_name, city)),
+            );
         }
+        if cities.is_empty() {
+            return Err(Error::new_parse_error(ParseErrorKind::City));
+        }
+        let mut candidates = vec![];
+        for (prefecture_name, city) in &cities {
+            let towns = self.source.city_blocking(prefecture_name, &city.name)?;
+            candidates.extend(candidate::town_candidates(city, &towns, &self.options));
+        }
+        if candidates.is_empty() {
+            return Err(Error::new_parse_error(ParseErrorKind::Town));
+        }
+        Ok(candidate::rank(candidates, limit))
     }
 }
 
 #[cfg(all(test, not(feature = "blocking")))]
 mod tests {
-    use crate::domain::geolonia::error::ParseErrorKind;
+    use crate::domain::geolonia::entity::{Correction, CorrectionKind};
+    use crate::domain::geolonia::error::{ErrorKind, ParseErrorKind};
     use crate::parser::Parser;
     use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
 
@@ -164,6 +426,271 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn 郡名を補完した場合_補正の内容を返す() {
+        let parser = Parser::default();
+        let result = parser.parse("埼玉県東秩父村大字御堂634番地").await;
+        assert_eq!(result.address.city, "秩父郡東秩父村");
+        assert_eq!(result.error, None);
+        assert_eq!(
+            result.corrections,
+            vec![Correction {
+                kind: CorrectionKind::CountyNameCompletion,
+                original: "東秩父村".to_string(),
+                replaced: "秩父郡東秩父村".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn 都道府県名が省略されている場合_推定する() {
+        let parser = Parser::default().infer_prefecture(true);
+        let result = parser.parse("横浜市中区本町1-1").await;
+        assert_eq!(result.address.prefecture, "神奈川県");
+        assert_eq!(result.address.city, "横浜市中区");
+        assert_eq!(result.address.town, "本町一丁目");
+        assert_eq!(result.error, None);
+        assert_eq!(
+            result.corrections[0].kind,
+            CorrectionKind::PrefectureCompletion
+        );
+        assert_eq!(result.spans.prefecture, None);
+    }
+
+    #[tokio::test]
+    async fn parse_candidates_都道府県名を一つに特定できない場合_それぞれの候補を返す() {
+        let parser = Parser::default().infer_prefecture(true);
+        let candidates = parser
+            .parse_candidates("府中市宮西町2丁目24番地", 5)
+            .await
+            .unwrap();
+        let prefectures: Vec<&str> = candidates
+            .iter()
+            .map(|candidate| candidate.address.prefecture.as_str())
+            .collect();
+        assert!(prefectures.contains(&"東京都"));
+    }
+
+    #[tokio::test]
+    async fn parse_candidates_郡名を補完した候補を返す() {
+        let parser = Parser::default();
+        let result = parser
+            .parse_candidates("埼玉県東秩父村大字御堂634番地", 3)
+            .await;
+        assert!(result.is_ok());
+        let candidates = result.unwrap();
+        assert!(!candidates.is_empty() && candidates.len() <= 3);
+        assert_eq!(candidates[0].address.city, "秩父郡東秩父村");
+        assert_eq!(candidates[0].address.town, "大字御堂");
+        assert_eq!(
+            candidates[0].corrections[0].kind,
+            CorrectionKind::CountyNameCompletion
+        );
+    }
+
+    #[tokio::test]
+    async fn parse_candidates_都道府県名が誤っている場合() {
+        let parser = Parser::default();
+        let result = parser
+            .parse_candidates("青盛県青森市長島１丁目１−１", 3)
+            .await;
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind, ErrorKind::PrefectureNotFound);
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn ローカルディレクトリの住所マスタを使用する場合() {
+        use crate::domain::geolonia::entity::Precision;
+        use crate::http::local_directory_client::LocalDirectoryApiClient;
+
+        let root = std::env::temp_dir().join(format!(
+            "japanese-address-parser-offline-{}",
+            std::process::id()
+        ));
+        let prefecture_directory = root
+            .join("yuukitoriyama.github.io")
+            .join("geolonia-japanese-addresses-accompanist")
+            .join("東京都");
+        let city_directory = root
+            .join("geolonia.github.io")
+            .join("japanese-addresses")
+            .join("api")
+            .join("ja")
+            .join("東京都");
+        std::fs::create_dir_all(&prefecture_directory).unwrap();
+        std::fs::create_dir_all(&city_directory).unwrap();
+        std::fs::write(
+            prefecture_directory.join("master.json"),
+            r#"{"name": "東京都", "cities": ["千代田区", "中央区"]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            city_directory.join("千代田区.json"),
+            r#"[{"town": "千代田", "koaza": "", "lat": 35.68339, "lng": 139.753634}]"#,
+        )
+        .unwrap();
+
+        let parser = Parser::with_api_client(LocalDirectoryApiClient::with_root(&root));
+        let result = parser.parse("東京都千代田区千代田1-1").await;
+        assert_eq!(result.address.prefecture, "東京都");
+        assert_eq!(result.address.city, "千代田区");
+        assert_eq!(result.address.town, "千代田");
+        assert_eq!(result.address.rest, "1-1");
+        assert_eq!(result.error, None);
+        let town = result.spans.town.unwrap();
+        assert_eq!(
+            &"東京都千代田区千代田1-1"[town.byte_start..town.byte_end],
+            "千代田"
+        );
+        assert_eq!((town.char_start, town.char_end), (7, 10));
+        let coordinate = result.coordinate.unwrap();
+        assert_eq!(coordinate.precision, Precision::Town);
+        assert!((coordinate.latitude - 35.68339).abs() < 1e-5);
+
+        let result = parser.parse("〒100-0001 東京都千代田区千代田1-1").await;
+        assert_eq!(result.address.postal_code, Some("100-0001".to_string()));
+        assert_eq!(result.address.prefecture, "東京都");
+        assert_eq!(result.address.rest, "1-1");
+        let prefecture = result.spans.prefecture.unwrap();
+        assert_eq!((prefecture.char_start, prefecture.char_end), (10, 13));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn 住所マスタの取得先を変更した場合() {
+        use crate::http::endpoints::Endpoints;
+        use crate::http::local_directory_client::LocalDirectoryApiClient;
+
+        let root = std::env::temp_dir().join(format!(
+            "japanese-address-parser-endpoints-{}",
+            std::process::id()
+        ));
+        let mirror = root.join("mirror.example.com");
+        std::fs::create_dir_all(mirror.join("accompanist").join("東京都")).unwrap();
+        std::fs::create_dir_all(mirror.join("geolonia").join("東京都")).unwrap();
+        std::fs::write(
+            mirror
+                .join("accompanist")
+                .join("東京都")
+                .join("master.json"),
+            r#"{"name": "東京都", "cities": ["千代田区"]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            mirror.join("geolonia").join("東京都").join("千代田区.json"),
+            r#"[{"town": "千代田", "koaza": "", "lat": 35.68339, "lng": 139.753634}]"#,
+        )
+        .unwrap();
+
+        let endpoints = Endpoints::default()
+            .geolonia("https://mirror.example.com/geolonia/")
+            .geolonia_prefecture("https://mirror.example.com/accompanist");
+        let original = Parser::with_api_client(LocalDirectoryApiClient::with_root(&root));
+        // 複製した`Parser`の取得先を変更しても、複製元には影響しない
+        let parser = original.clone().endpoints(endpoints);
+        let result = parser.parse("東京都千代田区千代田1-1").await;
+        assert_eq!(result.address.city, "千代田区");
+        assert_eq!(result.address.town, "千代田");
+        assert_eq!(result.error, None);
+
+        let result = parser.parse("大阪府大阪市北区梅田1-1").await;
+        assert_eq!(
+            result.error.unwrap().error_message,
+            "https://mirror.example.com/accompanist/%E5%A4%A7%E9%98%AA%E5%BA%9C/master.jsonを取得できませんでした"
+        );
+        let result = original.parse("東京都千代田区千代田1-1").await;
+        assert!(result
+            .error
+            .unwrap()
+            .error_message
+            .starts_with("https://yuukitoriyama.github.io/"));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn 独自の取得元から住所マスタを読み込む場合() {
+        use crate::domain::geolonia::entity::Town;
+        use crate::domain::geolonia::error::Error;
+        use crate::http::error::ApiClientError;
+        use crate::source::AddressMasterSource;
+
+        struct InMemorySource;
+
+        impl AddressMasterSource for InMemorySource {
+            async fn prefecture(&self, prefecture_name: &str) -> Result<Vec<String>, Error> {
+                match prefecture_name {
+                    "東京都" => Ok(vec!["千代田区".to_string(), "中央区".to_string()]),
+                    _ => Err(ApiClientError::Request {
+                        url: format!("memory://{}", prefecture_name),
+                        message: "not found".to_string(),
+                    }
+                    .into()),
+                }
+            }
+
+            async fn city(&self, _: &str, city_name: &str) -> Result<Vec<Town>, Error> {
+                match city_name {
+                    "千代田区" => Ok(vec![Town {
+                        name: "千代田".to_string(),
+                        koaza: "".to_string(),
+                        lat: Some(35.68339),
+                        lng: Some(139.753634),
+                    }]),
+                    _ => Ok(vec![]),
+                }
+            }
+        }
+
+        let parser = Parser::with_source(InMemorySource);
+        let result = parser.parse("東京都千代田区千代田1-1").await;
+        assert_eq!(result.address.city, "千代田区");
+        assert_eq!(result.address.town, "千代田");
+        assert_eq!(result.address.rest, "1-1");
+        assert_eq!(result.error, None);
+
+        // 独自の取得元を使用する場合は、郵便番号データを取得するための通信も行わない
+        assert_eq!(
+            parser
+                .lookup_postal_code("100-0001")
+                .await
+                .unwrap_err()
+                .kind,
+            ErrorKind::PostalCodeLookupUnavailable
+        );
+
+        let result = parser.parse("大阪府大阪市北区梅田1-1").await;
+        assert_eq!(result.address.prefecture, "大阪府");
+        assert_eq!(
+            result.error.unwrap().kind,
+            ErrorKind::Fetch {
+                url: "memory://大阪府".to_string(),
+                source: Box::new(ApiClientError::Request {
+                    url: "memory://大阪府".to_string(),
+                    message: "not found".to_string(),
+                }),
+            }
+        );
+
+        // 都道府県名を推定する場合、取得できなかった都道府県は一致しなかったものとして扱う
+        let parser = parser.infer_prefecture(true);
+        let result = parser.parse("千代田区千代田1-1").await;
+        assert_eq!(result.address.prefecture, "東京都");
+        assert_eq!(result.address.town, "千代田");
+        assert_eq!(result.error, None);
+
+        // 一致する都道府県がなかった場合は、都道府県が見つからなかったことにせず取得時のエラーを返す
+        let result = parser.parse("大阪市北区梅田1-1").await;
+        assert_eq!(result.address.prefecture, "");
+        assert!(matches!(
+            result.error.unwrap().kind,
+            ErrorKind::Fetch { .. }
+        ));
+    }
+
     wasm_bindgen_test_configure!(run_in_browser);
 
     #[wasm_bindgen_test]
@@ -191,6 +718,14 @@ mod blocking_tests {
         assert_eq!(result.address.city, "秩父市");
         assert_eq!(result.address.town, "熊木町");
         assert_eq!(result.address.rest, "8番15号");
+        assert_eq!(
+            result.address.rest_components.block_number,
+            Some("8".to_string())
+        );
+        assert_eq!(
+            result.address.rest_components.house_number,
+            Some("15".to_string())
+        );
         assert_eq!(result.error, None);
     }
 
@@ -209,8 +744,59 @@ mod blocking_tests {
     }
 }
 
+/// One of the interpretations returned by `Parser::parse_candidates`
+#[derive(Serialize, PartialEq, Debug)]
+pub struct ParseCandidate {
+    pub address: Address,
+    /// 解釈の確からしさ(0.0〜1.0)
+    pub score: f64,
+    /// この解釈を得るために行った補正
+    pub corrections: Vec<Correction>,
+    /// 都道府県名、市区町村名、町名、それ以降のそれぞれの入力中の位置
+    pub spans: AddressSpans,
+}
+
+/// An address found by `Parser::extract_addresses`
+#[derive(Serialize, PartialEq, Debug)]
+pub struct ExtractedAddress {
+    /// テキスト中の住所の位置(郵便番号が前置されている場合はそれを含む)
+    pub span: Span,
+    /// 住所の直前に記載されていた郵便番号(`NNN-NNNN`形式)
+    pub postal_code: Option<String>,
+    /// 住所部分のパース結果(`spans`はテキスト全体における位置を表す)
+    pub result: ParseResult,
+}
+
 #[derive(Serialize, PartialEq, Debug)]
 pub struct ParseResult {
     pub address: Address,
     pub error: Option<Error>,
+    /// 住所を解釈する過程で入力に対して行った補正
+    pub corrections: Vec<Correction>,
+    /// 都道府県名、市区町村名、町名、それ以降のそれぞれの入力中の位置
+    pub spans: AddressSpans,
+    /// 住所の代表点(緯度経度)
+    pub coordinate: Option<Coordinate>,
+}
+
+impl ParseResult {
+    pub(crate) fn new(tokenizer: Tokenizer<End>, error: Option<Error>) -> Self {
+        Self {
+            corrections: tokenizer.corrections.clone(),
+            spans: tokenizer.spans(),
+            address: Address::from(tokenizer),
+            error,
+            coordinate: None,
+        }
+    }
+
+    /// 市区町村マスタの町の代表点から、住所の代表点を求めて設定する
+    ///
+    /// `towns`はパース結果の市区町村のものであること。
+    pub(crate) fn with_coordinate(self, towns: &[Town]) -> Self {
+        Self {
+            coordinate: geocode::coordinate(&self.address, towns),
+            ..self
+        }
+    }
 }
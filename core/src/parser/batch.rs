@@ -0,0 +1,155 @@
+use std::collections::BTreeSet;
+
+use crate::http::client::ApiClient;
+use crate::parser::driver::{MasterCache, MasterKey, ParseJob, Progress};
+use crate::parser::{ParseResult, Parser};
+use crate::source::AddressMasterSource;
+
+/// `parse_many`で住所マスタを同時に取得する数の初期値
+pub(super) const DEFAULT_MAX_CONCURRENCY: usize = 8;
+
+impl<Client: ApiClient, Source: AddressMasterSource> Parser<Client, Source> {
+    /// Sets the maximum number of master data fetched concurrently by `parse_many`.
+    ///
+    /// 初期値は8です。0を指定した場合は1として扱います。
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Parses the given `addresses` asynchronously and returns the results in the same order.
+    ///
+    /// 全ての住所の解析を一段階ずつ同時に進め、各段階で必要になった住所マスタを都道府県・市区町村ごとに
+    /// 重複なくまとめて取得します。そのため同じ市区町村の住所が多く含まれている場合でも、
+    /// 住所マスタの取得は一度だけで済みます。同時に取得する数は`max_concurrency`で制限できます。
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::parser::Parser;
+    ///
+    /// async fn example() {
+    ///     let parser = Parser::default().max_concurrency(4);
+    ///     let results = parser
+    ///         .parse_many(["東京都新宿区西新宿2-8-1", "東京都千代田区丸の内1-1"])
+    ///         .await;
+    ///     for result in results {
+    ///         println!("{:?}", result.address);
+    ///     }
+    /// }
+    /// ```
+    pub async fn parse_many<'a>(
+        &self,
+        addresses: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<ParseResult> {
+        let mut jobs: Vec<Option<ParseJob>> = addresses
+            .into_iter()
+            .map(|address| Some(ParseJob::new(address, self.options)))
+            .collect();
+        let mut results: Vec<Option<ParseResult>> = jobs.iter().map(|_| None).collect();
+        let mut masters = MasterCache::default();
+
+        while jobs.iter().any(Option::is_some) {
+            // 取得済みの住所マスタで各住所の解析を進められるところまで進める
+            for index in 0..jobs.len() {
+                let Some(job) = jobs[index].take() else {
+                    continue;
+                };
+                match job.resume(&masters) {
+                    Progress::Pending(job) => jobs[index] = Some(job),
+                    Progress::Done(result) => results[index] = Some(result),
+                }
+            }
+
+            // 各住所が必要としている住所マスタのうち、未取得のものを重複なくまとめて取得する
+            let keys: BTreeSet<MasterKey> = jobs
+                .iter()
+                .flatten()
+                .flat_map(|job| job.missing_masters(&masters))
+                .collect();
+            self.fetch_masters(keys, &mut masters).await;
+        }
+
+        results
+            .into_iter()
+            .map(|result| result.expect("every address should be parsed"))
+            .collect()
+    }
+
+    /// Parses the given `addresses` synchronously and returns the results in the same order.
+    ///
+    /// 一度取得した住所マスタは、以降の住所の解析に再利用します。
+    #[cfg(feature = "blocking")]
+    pub fn parse_many_blocking<'a>(
+        &self,
+        addresses: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<ParseResult> {
+        let mut masters = MasterCache::default();
+        addresses
+            .into_iter()
+            .map(|address| self.parse_with_masters_blocking(address, &mut masters))
+            .collect()
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod async_tests {
+    use crate::domain::geolonia::error::ErrorKind;
+    use crate::http::mock_client::MockApiClient;
+    use crate::parser::Parser;
+
+    #[tokio::test]
+    async fn parse_many_入力の順に結果を返す() {
+        let client = MockApiClient::tokyo();
+        let parser = Parser::with_api_client(client.clone()).max_concurrency(2);
+        let results = parser
+            .parse_many([
+                "東京都中央区銀座1-1",
+                "東京都千代田区千代田1-1",
+                "東京都千代田区丸の内1-1",
+                "東京都中央区銀座1-2",
+            ])
+            .await;
+        let towns: Vec<&str> = results
+            .iter()
+            .map(|result| result.address.town.as_str())
+            .collect();
+        assert_eq!(
+            towns,
+            vec!["銀座一丁目", "千代田", "丸の内一丁目", "銀座一丁目"]
+        );
+        assert!(results.iter().all(|result| result.error.is_none()));
+        assert!(results.iter().all(|result| result.coordinate.is_some()));
+        // 都道府県マスタ1件と市区町村マスタ2件
+        assert_eq!(client.count(), 3);
+    }
+
+    #[tokio::test]
+    async fn parse_many_住所マスタの取得に失敗した場合() {
+        let client = MockApiClient::tokyo();
+        let parser = Parser::with_api_client(client.clone());
+        let results = parser
+            .parse_many([
+                "大阪府大阪市北区梅田1-1",
+                "東京都中央区銀座1-1",
+                "大阪府堺市堺区1",
+            ])
+            .await;
+        assert_eq!(results.len(), 3);
+        assert!(matches!(
+            results[0].error.as_ref().map(|error| &error.kind),
+            Some(ErrorKind::Fetch { .. })
+        ));
+        assert_eq!(results[0].address.prefecture, "大阪府");
+        assert_eq!(results[0].address.rest, "大阪市北区梅田1-1");
+        assert_eq!(results[1].address.town, "銀座一丁目");
+        assert!(results[2].error.is_some());
+        // 失敗した大阪府の都道府県マスタは一度しか取得しない
+        assert_eq!(client.count(), 3);
+    }
+
+    #[tokio::test]
+    async fn parse_many_空の場合() {
+        let parser = Parser::with_api_client(MockApiClient::tokyo());
+        assert!(parser.parse_many(Vec::<&str>::new()).await.is_empty());
+    }
+}
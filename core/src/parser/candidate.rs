@@ -0,0 +1,128 @@
+use crate::domain::geolonia::entity::{koaza_names, Address, CorrectionKind, Town};
+use crate::domain::geolonia::error::{Error, ParseErrorKind};
+use crate::parser::{ParseCandidate, ParseOptions};
+use crate::tokenizer::{Candidate, CityNameFound, Init, PrefectureNameFound, Tokenizer};
+
+/// 都道府県名の解釈候補を列挙する
+///
+/// 都道府県名が省略されている場合、`infer_prefecture`が有効であれば全ての都道府県を候補とする。
+pub(super) fn prefecture_candidates(
+    tokenizer: &Tokenizer<Init>,
+    options: &ParseOptions,
+) -> Result<Vec<(String, Tokenizer<PrefectureNameFound>)>, Error> {
+    match tokenizer.read_prefecture() {
+        Ok((prefecture, tokenizer)) => Ok(vec![(prefecture.name_ja().to_string(), tokenizer)]),
+        Err(_) if options.infer_prefecture => Ok(jisx0401::Prefecture::values()
+            .map(|prefecture| {
+                let prefecture_name = prefecture.name_ja().to_string();
+                let tokenizer = tokenizer.assume_prefecture(&prefecture_name);
+                (prefecture_name, tokenizer)
+            })
+            .collect()),
+        Err(_) => Err(Error::new_parse_error(ParseErrorKind::Prefecture)),
+    }
+}
+
+/// 市区町村名の解釈候補を列挙する
+///
+/// 郡名の補完による候補は類似度の高いものから最大`limit`件に絞る。
+/// 都道府県名を推定した場合は、誤った都道府県で類似した市区町村名を拾わないよう郡名の補完を行わない。
+pub(super) fn city_candidates(
+    tokenizer: &Tokenizer<PrefectureNameFound>,
+    city_names: &[String],
+    limit: usize,
+    options: &ParseOptions,
+) -> Vec<Candidate<CityNameFound>> {
+    let prefecture_inferred = tokenizer
+        .corrections
+        .iter()
+        .any(|correction| correction.kind == CorrectionKind::PrefectureCompletion);
+    match tokenizer.read_city_candidates(city_names) {
+        Ok(candidates) => candidates,
+        Err(tokenizer) if options.city_name_correction && !prefecture_inferred => tokenizer
+            .read_city_with_county_name_completion_candidates(city_names)
+            .into_iter()
+            .take(limit)
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// 町名の解釈候補を列挙し、市区町村名の候補と組み合わせる
+///
+/// 町名の後に小字・通称名が続く場合は、それも読み取る。
+pub(super) fn town_candidates(
+    city: &Candidate<CityNameFound>,
+    towns: &[Town],
+    options: &ParseOptions,
+) -> Vec<ParseCandidate> {
+    let town_names: Vec<String> = towns.iter().map(|x| x.name.clone()).collect();
+    city.tokenizer
+        .read_town_candidates(&town_names, options)
+        .into_iter()
+        .map(|town| {
+            let tokenizer = town
+                .tokenizer
+                .read_koaza(&koaza_names(towns, &town.name))
+                .finish();
+            ParseCandidate {
+                corrections: tokenizer.corrections.clone(),
+                spans: tokenizer.spans(),
+                address: Address::from(tokenizer),
+                score: city.score * town.score,
+            }
+        })
+        .collect()
+}
+
+/// スコアの高い順に並べ替え、上位`limit`件を返す
+pub(super) fn rank(mut candidates: Vec<ParseCandidate>, limit: usize) -> Vec<ParseCandidate> {
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates.truncate(limit);
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::geolonia::entity::Address;
+    use crate::parser::candidate::rank;
+    use crate::parser::ParseCandidate;
+
+    fn candidate(town: &str, score: f64) -> ParseCandidate {
+        ParseCandidate {
+            address: Address::new("北海道", "茅部郡森町", town, ""),
+            score,
+            corrections: vec![],
+            spans: Default::default(),
+        }
+    }
+
+    #[test]
+    fn rank_スコアの高い順に上位の候補を返す() {
+        let candidates = vec![
+            candidate("字森", 0.5),
+            candidate("字赤井川", 0.9),
+            candidate("字姫川", 0.7),
+        ];
+        let ranked = rank(candidates, 2);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].address.town, "字赤井川");
+        assert_eq!(ranked[1].address.town, "字姫川");
+    }
+
+    #[test]
+    fn rank_スコアがnanの場合もパニックしない() {
+        let candidates = vec![
+            candidate("字森", 0.5),
+            candidate("字赤井川", f64::NAN),
+            candidate("字姫川", 0.7),
+        ];
+        let ranked = rank(candidates, 3);
+        let towns = ranked
+            .iter()
+            .filter(|candidate| !candidate.score.is_nan())
+            .map(|candidate| candidate.address.town.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(towns, ["字姫川", "字森"]);
+    }
+}
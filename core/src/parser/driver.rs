@@ -0,0 +1,268 @@
+use std::collections::{BTreeSet, HashMap};
+
+use futures::stream::{self, StreamExt};
+
+use crate::domain::geolonia::entity::{koaza_names, Town};
+use crate::domain::geolonia::error::{Error, ErrorKind};
+use crate::http::client::ApiClient;
+use crate::parser::pure::{PureParser, PureParserAction};
+use crate::parser::{ParseOptions, ParseResult, Parser};
+use crate::source::AddressMasterSource;
+
+/// 住所マスタの種類と、それを特定する名称
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub(super) enum MasterKey {
+    /// 都道府県マスタ(市区町村名のリスト)
+    Prefecture(String),
+    /// 市区町村マスタ(町名マスタ)
+    City(String, String),
+}
+
+/// 取得済みの住所マスタ
+///
+/// 取得に失敗した場合はそのエラーを保持し、同じ住所マスタを必要とする住所に同じエラーを返して再取得しないようにする。
+#[derive(Default)]
+pub(super) struct MasterCache {
+    city_names: HashMap<String, Result<Vec<String>, Error>>,
+    towns: HashMap<(String, String), Result<Vec<Town>, Error>>,
+}
+
+impl MasterCache {
+    fn contains(&self, key: &MasterKey) -> bool {
+        match key {
+            MasterKey::Prefecture(pref_name) => self.city_names.contains_key(pref_name),
+            MasterKey::City(pref_name, city_name) => self
+                .towns
+                .contains_key(&(pref_name.clone(), city_name.clone())),
+        }
+    }
+
+    /// 取得済みの町名マスタ(未取得または取得に失敗した場合は空)
+    fn towns(&self, key: Option<&(String, String)>) -> &[Town] {
+        match key.and_then(|key| self.towns.get(key)) {
+            Some(Ok(towns)) => towns,
+            _ => &[],
+        }
+    }
+}
+
+/// 一件の住所の解析状況
+///
+/// `PureParser`が要求した住所マスタを`MasterCache`から渡し、解析を進められるところまで進める。
+/// 住所マスタの取得方法(非同期・同期、一件ずつ・まとめて)は呼び出し側に任せる。
+pub(super) struct ParseJob {
+    pure_parser: PureParser,
+    action: PureParserAction,
+    /// 直前に町名マスタを渡した市区町村
+    city_key: Option<(String, String)>,
+}
+
+/// `ParseJob::resume`の結果
+#[allow(clippy::large_enum_variant)]
+pub(super) enum Progress {
+    /// 未取得の住所マスタがあるため、解析を進められない
+    Pending(ParseJob),
+    /// 解析が完了した
+    Done(ParseResult),
+}
+
+impl ParseJob {
+    pub(super) fn new(address: &str, options: ParseOptions) -> Self {
+        let mut pure_parser = PureParser::new(address, options);
+        let action = pure_parser.advance();
+        Self {
+            pure_parser,
+            action,
+            city_key: None,
+        }
+    }
+
+    /// 解析を進めるために取得が必要な住所マスタ
+    pub(super) fn missing_masters(&self, masters: &MasterCache) -> Vec<MasterKey> {
+        let keys = match &self.action {
+            PureParserAction::RequestCityNameList(pref_name) => {
+                vec![MasterKey::Prefecture(pref_name.clone())]
+            }
+            PureParserAction::RequestAllCityNameLists(pref_names) => pref_names
+                .iter()
+                .map(|pref_name| MasterKey::Prefecture(pref_name.clone()))
+                .collect(),
+            PureParserAction::RequestTownNameList(pref_name, city_name) => {
+                vec![MasterKey::City(pref_name.clone(), city_name.clone())]
+            }
+            PureParserAction::RequestKoazaList(_) | PureParserAction::Done(_) => vec![],
+        };
+        keys.into_iter()
+            .filter(|key| !masters.contains(key))
+            .collect()
+    }
+
+    /// 取得済みの住所マスタを使って、解析を進められるところまで進める
+    ///
+    /// 住所マスタの取得に失敗していた場合は、その時点で解析を中断してエラーを返す。
+    pub(super) fn resume(mut self, masters: &MasterCache) -> Progress {
+        loop {
+            let input = match self.action {
+                PureParserAction::RequestCityNameList(ref pref_name) => {
+                    match masters.city_names.get(pref_name) {
+                        Some(Ok(city_names)) => city_names.clone(),
+                        Some(Err(error)) => {
+                            return Progress::Done(self.pure_parser.abort(error.clone()))
+                        }
+                        None => return Progress::Pending(self),
+                    }
+                }
+                PureParserAction::RequestAllCityNameLists(ref pref_names) => {
+                    // 取得できなかった都道府県は、市区町村名が一致しなかったものとして扱う
+                    let mut lists = vec![];
+                    let mut failure = None;
+                    for pref_name in pref_names {
+                        match masters.city_names.get(pref_name) {
+                            Some(Ok(city_names)) => {
+                                lists.push((pref_name.clone(), city_names.clone()))
+                            }
+                            Some(Err(error)) => {
+                                failure.get_or_insert(error);
+                            }
+                            None => return Progress::Pending(self),
+                        }
+                    }
+                    self.pure_parser.provide_city_name_lists(lists);
+                    self.action = self.pure_parser.advance();
+                    // 一致する都道府県がなかった場合は、取得できなかった都道府県に一致した可能性があるため、
+                    // 都道府県が見つからなかったことにせず取得時のエラーを返す
+                    if let (Some(error), PureParserAction::Done(result)) =
+                        (failure, &mut self.action)
+                    {
+                        if result.error.as_ref().map(|error| &error.kind)
+                            == Some(&ErrorKind::PrefectureNotFound)
+                        {
+                            result.error = Some(error.clone());
+                        }
+                    }
+                    continue;
+                }
+                PureParserAction::RequestTownNameList(ref pref_name, ref city_name) => {
+                    let key = (pref_name.clone(), city_name.clone());
+                    let town_names = match masters.towns.get(&key) {
+                        Some(Ok(towns)) => towns.iter().map(|x| x.name.clone()).collect(),
+                        Some(Err(error)) => {
+                            return Progress::Done(self.pure_parser.abort(error.clone()))
+                        }
+                        None => return Progress::Pending(self),
+                    };
+                    self.city_key = Some(key);
+                    town_names
+                }
+                PureParserAction::RequestKoazaList(ref town_name) => {
+                    koaza_names(masters.towns(self.city_key.as_ref()), town_name)
+                }
+                PureParserAction::Done(result) => {
+                    let towns = masters.towns(self.city_key.as_ref());
+                    return Progress::Done(result.with_coordinate(towns));
+                }
+            };
+            self.pure_parser.provide_input(input);
+            self.action = self.pure_parser.advance();
+        }
+    }
+}
+
+impl<Client: ApiClient, Source: AddressMasterSource> Parser<Client, Source> {
+    /// 取得済みの住所マスタを再利用しながら住所をパースする(非同期)
+    pub(super) async fn parse_with_masters(
+        &self,
+        address: &str,
+        masters: &mut MasterCache,
+    ) -> ParseResult {
+        let mut job = ParseJob::new(address, self.options);
+        loop {
+            match job.resume(masters) {
+                Progress::Done(result) => return result,
+                Progress::Pending(pending) => {
+                    let keys = pending.missing_masters(masters).into_iter().collect();
+                    self.fetch_masters(keys, masters).await;
+                    job = pending;
+                }
+            }
+        }
+    }
+
+    /// 取得済みの住所マスタを再利用しながら住所をパースする(同期)
+    #[cfg(feature = "blocking")]
+    pub(super) fn parse_with_masters_blocking(
+        &self,
+        address: &str,
+        masters: &mut MasterCache,
+    ) -> ParseResult {
+        let mut job = ParseJob::new(address, self.options);
+        loop {
+            match job.resume(masters) {
+                Progress::Done(result) => return result,
+                Progress::Pending(pending) => {
+                    for key in pending.missing_masters(masters) {
+                        self.fetch_master_blocking(key, masters);
+                    }
+                    job = pending;
+                }
+            }
+        }
+    }
+
+    /// 住所マスタを`max_concurrency`件ずつ並行して取得する
+    pub(super) async fn fetch_masters(&self, keys: BTreeSet<MasterKey>, masters: &mut MasterCache) {
+        let source = &self.source;
+        let mut results = stream::iter(keys)
+            .map(|key| async move {
+                match key {
+                    MasterKey::Prefecture(pref_name) => {
+                        let result = source.prefecture(&pref_name).await;
+                        Fetched::CityNames(pref_name, result)
+                    }
+                    MasterKey::City(pref_name, city_name) => {
+                        let result = source.city(&pref_name, &city_name).await;
+                        Fetched::Towns((pref_name, city_name), result)
+                    }
+                }
+            })
+            .buffer_unordered(self.max_concurrency);
+        while let Some(fetched) = results.next().await {
+            fetched.store(masters);
+        }
+    }
+
+    /// 住所マスタを同期的に取得する
+    #[cfg(feature = "blocking")]
+    fn fetch_master_blocking(&self, key: MasterKey, masters: &mut MasterCache) {
+        let fetched = match key {
+            MasterKey::Prefecture(pref_name) => {
+                let result = self.source.prefecture_blocking(&pref_name);
+                Fetched::CityNames(pref_name, result)
+            }
+            MasterKey::City(pref_name, city_name) => {
+                let result = self.source.city_blocking(&pref_name, &city_name);
+                Fetched::Towns((pref_name, city_name), result)
+            }
+        };
+        fetched.store(masters);
+    }
+}
+
+/// 取得した住所マスタ
+enum Fetched {
+    CityNames(String, Result<Vec<String>, Error>),
+    Towns((String, String), Result<Vec<Town>, Error>),
+}
+
+impl Fetched {
+    fn store(self, masters: &mut MasterCache) {
+        match self {
+            Self::CityNames(pref_name, result) => {
+                masters.city_names.insert(pref_name, result);
+            }
+            Self::Towns(key, result) => {
+                masters.towns.insert(key, result);
+            }
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use crate::domain::geolonia::entity::{Address, AddressSpans, Span};
+use crate::formatter::house_number::HYPHENS;
+use crate::formatter::postal_code::{is_blank, is_postal_code_hyphen, to_ascii_digit};
+use crate::http::client::ApiClient;
+use crate::parser::driver::MasterCache;
+use crate::parser::{ExtractedAddress, ParseResult, Parser};
+use crate::source::AddressMasterSource;
+use crate::tokenizer::read_prefecture::find_prefecture;
+
+/// 住所の終わりとみなす文字
+const DELIMITERS: [char; 24] = [
+    '\n', '\r', '\t', '。', '、', '，', ',', '．', '(', ')', '（', '）', '「', '」', '『', '』',
+    '【', '】', '[', ']', '［', '］', ';', '；',
+];
+
+/// 町名以降のうち、番地・号として扱う文字(ハイフン類を除く)
+const HOUSE_NUMBER_CHARS: [char; 18] = [
+    '〇', '一', '二', '三', '四', '五', '六', '七', '八', '九', '十', '百', '千', '丁', '目', '番',
+    '地', '号',
+];
+
+impl<Client: ApiClient, Source: AddressMasterSource> Parser<Client, Source> {
+    /// Extracts every address embedded in the given free-form `text` asynchronously.
+    ///
+    /// 都道府県名から始まり、市区町村名まで特定できた箇所を住所として抽出します。
+    /// 住所の終わりは町名以降の番地・号の表記が途切れた位置とみなすため、建物名・部屋番号は含まれません。
+    /// 住所の直前に`〒123-4567`のような郵便番号が記載されている場合は、郵便番号を含む範囲を返します。
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::parser::Parser;
+    ///
+    /// async fn example() {
+    ///     let parser: Parser = Default::default();
+    ///     let text = "本社は〒163-8001 東京都新宿区西新宿2-8-1にあります。";
+    ///     for extracted in parser.extract_addresses(text).await {
+    ///         let span = extracted.span;
+    ///         println!("{} {:?}", &text[span.byte_start..span.byte_end], extracted.result.address);
+    ///     }
+    /// }
+    /// ```
+    pub async fn extract_addresses(&self, text: &str) -> Vec<ExtractedAddress> {
+        let mut masters = MasterCache::default();
+        let mut extracted = vec![];
+        let mut position = 0;
+        while let Some(start) = find_next_prefecture(text, position) {
+            let segment = &text[start..segment_end(text, start)];
+            let result = self.parse_with_masters(segment, &mut masters).await;
+            let Some(end) = address_end(segment, &result) else {
+                position = next_char_boundary(text, start);
+                continue;
+            };
+            let result = if end < segment.len() {
+                self.parse_with_masters(&segment[..end], &mut masters).await
+            } else {
+                result
+            };
+            extracted.push(ExtractedAddress::new(text, position, start, end, result));
+            position = start + end;
+        }
+        extracted
+    }
+
+    /// Extracts every address embedded in the given free-form `text` synchronously.
+    #[cfg(feature = "blocking")]
+    pub fn extract_addresses_blocking(&self, text: &str) -> Vec<ExtractedAddress> {
+        let mut masters = MasterCache::default();
+        let mut extracted = vec![];
+        let mut position = 0;
+        while let Some(start) = find_next_prefecture(text, position) {
+            let segment = &text[start..segment_end(text, start)];
+            let result = self.parse_with_masters_blocking(segment, &mut masters);
+            let Some(end) = address_end(segment, &result) else {
+                position = next_char_boundary(text, start);
+                continue;
+            };
+            let result = if end < segment.len() {
+                self.parse_with_masters_blocking(&segment[..end], &mut masters)
+            } else {
+                result
+            };
+            extracted.push(ExtractedAddress::new(text, position, start, end, result));
+            position = start + end;
+        }
+        extracted
+    }
+}
+
+impl ExtractedAddress {
+    /// `text`の`start`から`start + end`バイト目までを住所として抽出結果を組み立てる
+    ///
+    /// 郵便番号は直前に抽出した住所の終わり(`position`)以降からのみ探す。
+    fn new(text: &str, position: usize, start: usize, end: usize, result: ParseResult) -> Self {
+        let (byte_start, postal_code) = match find_postal_code(&text[position..start]) {
+            Some((postal_code_start, postal_code)) => {
+                (position + postal_code_start, Some(postal_code))
+            }
+            None => (start, None),
+        };
+        let byte_offset = start;
+        let char_offset = text[..start].chars().count();
+        let shift = |span: Option<Span>| {
+            span.map(|span| Span {
+                byte_start: span.byte_start + byte_offset,
+                byte_end: span.byte_end + byte_offset,
+                char_start: span.char_start + char_offset,
+                char_end: span.char_end + char_offset,
+            })
+        };
+        let spans = AddressSpans {
+            postal_code: postal_code.as_ref().map(|_| Span {
+                byte_start,
+                byte_end: start,
+                char_start: text[..byte_start].chars().count(),
+                char_end: char_offset,
+            }),
+            prefecture: shift(result.spans.prefecture),
+            city: shift(result.spans.city),
+            town: shift(result.spans.town),
+            koaza: shift(result.spans.koaza),
+            rest: shift(result.spans.rest),
+        };
+        let byte_end = start + end;
+        Self {
+            span: Span {
+                byte_start,
+                byte_end,
+                char_start: text[..byte_start].chars().count(),
+                char_end: char_offset + text[start..byte_end].chars().count(),
+            },
+            result: ParseResult {
+                address: Address {
+                    postal_code: postal_code.clone(),
+                    ..result.address
+                },
+                spans,
+                ..result
+            },
+            postal_code,
+        }
+    }
+}
+
+/// `from`バイト目以降で最初に都道府県名が現れる位置を返す
+fn find_next_prefecture(text: &str, from: usize) -> Option<usize> {
+    text[from..]
+        .char_indices()
+        .map(|(index, _)| from + index)
+        .find(|&index| find_prefecture(&text[index..]).is_some())
+}
+
+fn next_char_boundary(text: &str, index: usize) -> usize {
+    index + text[index..].chars().next().map_or(1, char::len_utf8)
+}
+
+/// `start`バイト目から始まる住所を含みうる範囲の終わりを返す
+fn segment_end(text: &str, start: usize) -> usize {
+    text[start..]
+        .find(|c: char| DELIMITERS.contains(&c))
+        .map_or(text.len(), |end| start + end)
+}
+
+/// パース結果から、`segment`のうち住所とみなす範囲の終わりを返す
+///
+/// 市区町村名まで特定できなかった場合は住所ではないものとして`None`を返す。
+fn address_end(segment: &str, result: &ParseResult) -> Option<usize> {
+    if result.address.city.is_empty() {
+        return None;
+    }
+    let Some(rest) = result.spans.rest else {
+        return Some(segment.len());
+    };
+    let mut end = rest.byte_start;
+    for (index, c) in segment[rest.byte_start..rest.byte_end].char_indices() {
+        if HYPHENS.contains(&c) {
+            continue;
+        }
+        if !is_house_number_char(c) {
+            break;
+        }
+        end = rest.byte_start + index + c.len_utf8();
+    }
+    Some(end)
+}
+
+fn is_house_number_char(c: char) -> bool {
+    to_ascii_digit(c).is_some() || HOUSE_NUMBER_CHARS.contains(&c)
+}
+
+/// `before`の末尾に記載された郵便番号を探し、その開始位置と`NNN-NNNN`形式の郵便番号を返す
+///
+/// `〒123-4567`、`123-4567`、`1234567`のような表記に対応する。
+fn find_postal_code(before: &str) -> Option<(usize, String)> {
+    let before = before.trim_end_matches(is_blank);
+    let mut digits = vec![];
+    let mut hyphen_found = false;
+    let mut start = before.len();
+    for (index, c) in before.char_indices().rev() {
+        match to_ascii_digit(c) {
+            Some(digit) if digits.len() < 7 => digits.push(digit),
+            None if digits.len() == 4 && !hyphen_found && is_postal_code_hyphen(c) => {
+                hyphen_found = true
+            }
+            _ => break,
+        }
+        start = index;
+    }
+    if digits.len() != 7 || before[..start].ends_with(|c: char| to_ascii_digit(c).is_some()) {
+        return None;
+    }
+    let prefix = before[..start].trim_end_matches(is_blank);
+    if let Some(without_mark) = prefix.strip_suffix('〒') {
+        start = without_mark.len();
+    }
+    let digits: String = digits.into_iter().rev().collect();
+    Some((start, format!("{}-{}", &digits[..3], &digits[3..])))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::extract::{find_next_prefecture, find_postal_code, segment_end};
+
+    #[test]
+    fn find_next_prefecture_成功() {
+        let text = "住所は東京都千代田区です";
+        assert_eq!(find_next_prefecture(text, 0), Some("住所は".len()));
+        assert_eq!(find_next_prefecture(text, "住所は東".len()), None);
+    }
+
+    #[test]
+    fn segment_end_句読点で区切る() {
+        let text = "東京都千代田区千代田1-1、大阪府大阪市";
+        assert_eq!(segment_end(text, 0), "東京都千代田区千代田1-1".len());
+        assert_eq!(segment_end("東京都千代田区", 0), "東京都千代田区".len());
+    }
+
+    #[test]
+    fn find_postal_code_郵便番号記号付き() {
+        assert_eq!(
+            find_postal_code("本社は〒100-0001 "),
+            Some(("本社は".len(), "100-0001".to_string()))
+        );
+    }
+
+    #[test]
+    fn find_postal_code_全角数字_ハイフンなし() {
+        assert_eq!(
+            find_postal_code("〒１０００００１　"),
+            Some((0, "100-0001".to_string()))
+        );
+    }
+
+    #[test]
+    fn find_postal_code_郵便番号ではない場合() {
+        assert_eq!(find_postal_code("電話番号は03-1234-5678 "), None);
+        assert_eq!(find_postal_code("12345678"), None);
+        assert_eq!(find_postal_code("住所は"), None);
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod async_tests {
+    use crate::http::local_directory_client::LocalDirectoryApiClient;
+    use crate::parser::Parser;
+    use std::path::PathBuf;
+
+    fn prepare_directory(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "japanese-address-parser-extract-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let prefecture_directory = root
+            .join("yuukitoriyama.github.io")
+            .join("geolonia-japanese-addresses-accompanist")
+            .join("東京都");
+        let city_directory = root
+            .join("geolonia.github.io")
+            .join("japanese-addresses")
+            .join("api")
+            .join("ja")
+            .join("東京都");
+        std::fs::create_dir_all(&prefecture_directory).unwrap();
+        std::fs::create_dir_all(&city_directory).unwrap();
+        std::fs::write(
+            prefecture_directory.join("master.json"),
+            r#"{"name": "東京都", "cities": ["千代田区", "中央区"]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            city_directory.join("千代田区.json"),
+            r#"[{"town": "千代田", "koaza": "", "lat": 35.68339, "lng": 139.753634}]"#,
+        )
+        .unwrap();
+        root
+    }
+
+    #[tokio::test]
+    async fn extract_addresses_テキスト中の住所を抽出する() {
+        let root = prepare_directory("success");
+        let parser = Parser::with_api_client(LocalDirectoryApiClient::with_root(&root));
+        let text = "本社は〒100-0001 東京都千代田区千代田1-1にあります。東京都知事の所在地です";
+        let extracted = parser.extract_addresses(text).await;
+        assert_eq!(extracted.len(), 1);
+        let span = extracted[0].span;
+        assert_eq!(
+            &text[span.byte_start..span.byte_end],
+            "〒100-0001 東京都千代田区千代田1-1"
+        );
+        assert_eq!((span.char_start, span.char_end), (3, 26));
+        assert_eq!(extracted[0].postal_code, Some("100-0001".to_string()));
+        let result = &extracted[0].result;
+        assert_eq!(result.address.town, "千代田");
+        assert_eq!(result.address.rest, "1-1");
+        assert_eq!(result.error, None);
+        let town = result.spans.town.unwrap();
+        assert_eq!(&text[town.byte_start..town.byte_end], "千代田");
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn extract_addresses_複数の住所を抽出する() {
+        let root = prepare_directory("multiple");
+        let parser = Parser::with_api_client(LocalDirectoryApiClient::with_root(&root));
+        let text = "旧住所:東京都千代田区千代田1番地\n新住所:東京都中央区";
+        let extracted = parser.extract_addresses(text).await;
+        assert_eq!(extracted.len(), 2);
+        let first = extracted[0].span;
+        assert_eq!(
+            &text[first.byte_start..first.byte_end],
+            "東京都千代田区千代田1番地"
+        );
+        let second = extracted[1].span;
+        assert_eq!(&text[second.byte_start..second.byte_end], "東京都中央区");
+        assert_eq!(extracted[1].result.address.city, "中央区");
+        assert!(extracted[1].result.error.is_some());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,203 @@
+use crate::domain::geolonia::entity::{Address, Coordinate, Precision, Town};
+
+/// 都道府県庁の所在地(都道府県名, 緯度, 経度)
+const PREFECTURE_OFFICES: [(&str, f64, f64); 47] = [
+    ("北海道", 43.0643, 141.3469),
+    ("青森県", 40.8246, 140.7405),
+    ("岩手県", 39.7036, 141.1526),
+    ("宮城県", 38.2688, 140.8721),
+    ("秋田県", 39.7186, 140.1024),
+    ("山形県", 38.2404, 140.3636),
+    ("福島県", 37.7500, 140.4678),
+    ("茨城県", 36.3418, 140.4468),
+    ("栃木県", 36.5657, 139.8836),
+    ("群馬県", 36.3907, 139.0604),
+    ("埼玉県", 35.8570, 139.6488),
+    ("千葉県", 35.6051, 140.1233),
+    ("東京都", 35.6895, 139.6917),
+    ("神奈川県", 35.4477, 139.6425),
+    ("新潟県", 37.9025, 139.0232),
+    ("富山県", 36.6953, 137.2113),
+    ("石川県", 36.5946, 136.6257),
+    ("福井県", 36.0652, 136.2217),
+    ("山梨県", 35.6641, 138.5685),
+    ("長野県", 36.6513, 138.1810),
+    ("岐阜県", 35.3912, 136.7223),
+    ("静岡県", 34.9769, 138.3831),
+    ("愛知県", 35.1802, 136.9066),
+    ("三重県", 34.7303, 136.5086),
+    ("滋賀県", 35.0045, 135.8686),
+    ("京都府", 35.0212, 135.7556),
+    ("大阪府", 34.6865, 135.5190),
+    ("兵庫県", 34.6913, 135.1831),
+    ("奈良県", 34.6853, 135.8327),
+    ("和歌山県", 34.2261, 135.1675),
+    ("鳥取県", 35.5039, 134.2377),
+    ("島根県", 35.4723, 133.0505),
+    ("岡山県", 34.6618, 133.9344),
+    ("広島県", 34.3966, 132.4596),
+    ("山口県", 34.1860, 131.4706),
+    ("徳島県", 34.0657, 134.5593),
+    ("香川県", 34.3401, 134.0434),
+    ("愛媛県", 33.8416, 132.7657),
+    ("高知県", 33.5597, 133.5311),
+    ("福岡県", 33.6066, 130.4183),
+    ("佐賀県", 33.2494, 130.2998),
+    ("長崎県", 32.7448, 129.8738),
+    ("熊本県", 32.7898, 130.7417),
+    ("大分県", 33.2382, 131.6126),
+    ("宮崎県", 31.9111, 131.4239),
+    ("鹿児島県", 31.5601, 130.5580),
+    ("沖縄県", 26.2124, 127.6809),
+];
+
+/// パース結果の代表点を求める
+///
+/// 町名まで特定できた場合は町の代表点、できなかった場合は市区町村内の町の代表点の平均、
+/// 市区町村名も特定できなかった場合は都道府県庁の所在地を返す。
+/// 町の代表点は、小字・通称名まで一致するものがあればそれを優先する。
+pub(super) fn coordinate(address: &Address, towns: &[Town]) -> Option<Coordinate> {
+    if !address.town.is_empty() {
+        let same_town = || towns.iter().filter(|town| town.name == address.town);
+        let found = same_town()
+            .filter(|town| town.koaza == address.koaza)
+            .find_map(lat_lng)
+            .or_else(|| same_town().find_map(lat_lng));
+        if let Some((latitude, longitude)) = found {
+            return Some(Coordinate {
+                latitude,
+                longitude,
+                precision: Precision::Town,
+            });
+        }
+    }
+    let city_centroid = if address.city.is_empty() {
+        None
+    } else {
+        centroid(towns)
+    };
+    if let Some((latitude, longitude)) = city_centroid {
+        return Some(Coordinate {
+            latitude,
+            longitude,
+            precision: Precision::City,
+        });
+    }
+    PREFECTURE_OFFICES
+        .iter()
+        .find(|(prefecture_name, _, _)| *prefecture_name == address.prefecture)
+        .map(|&(_, latitude, longitude)| Coordinate {
+            latitude,
+            longitude,
+            precision: Precision::Prefecture,
+        })
+}
+
+fn lat_lng(town: &Town) -> Option<(f64, f64)> {
+    Some((town.lat? as f64, town.lng? as f64))
+}
+
+/// 代表点が登録されている町の代表点の平均を返す
+fn centroid(towns: &[Town]) -> Option<(f64, f64)> {
+    let points: Vec<(f64, f64)> = towns.iter().filter_map(lat_lng).collect();
+    if points.is_empty() {
+        return None;
+    }
+    let count = points.len() as f64;
+    let (latitude, longitude) = points
+        .iter()
+        .fold((0.0, 0.0), |(lat, lng), (x, y)| (lat + x, lng + y));
+    Some((latitude / count, longitude / count))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::geolonia::entity::{Address, Precision, Town};
+    use crate::parser::geocode::{coordinate, PREFECTURE_OFFICES};
+
+    fn towns() -> Vec<Town> {
+        vec![
+            Town {
+                name: "千代田".to_string(),
+                koaza: "".to_string(),
+                lat: Some(35.68),
+                lng: Some(139.75),
+            },
+            Town {
+                name: "丸の内一丁目".to_string(),
+                koaza: "".to_string(),
+                lat: Some(35.70),
+                lng: Some(139.77),
+            },
+            Town {
+                name: "大手町一丁目".to_string(),
+                koaza: "".to_string(),
+                lat: None,
+                lng: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn coordinate_町の代表点() {
+        let address = Address::new("東京都", "千代田区", "千代田", "1-1");
+        let coordinate = coordinate(&address, &towns()).unwrap();
+        assert_eq!(coordinate.precision, Precision::Town);
+        assert!((coordinate.latitude - 35.68).abs() < 1e-5);
+        assert!((coordinate.longitude - 139.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn coordinate_小字まで一致する代表点を優先する() {
+        let mut towns = towns();
+        towns.push(Town {
+            name: "千代田".to_string(),
+            koaza: "北".to_string(),
+            lat: Some(35.69),
+            lng: Some(139.76),
+        });
+        let mut address = Address::new("東京都", "千代田区", "千代田", "1-1");
+        address.koaza = "北".to_string();
+        let coordinate = coordinate(&address, &towns).unwrap();
+        assert_eq!(coordinate.precision, Precision::Town);
+        assert!((coordinate.latitude - 35.69).abs() < 1e-5);
+
+        // 小字まで一致する代表点がない場合は、町名が一致する代表点で代替する
+        address.koaza = "南".to_string();
+        let coordinate = super::coordinate(&address, &towns).unwrap();
+        assert_eq!(coordinate.precision, Precision::Town);
+        assert!((coordinate.latitude - 35.68).abs() < 1e-5);
+    }
+
+    #[test]
+    fn coordinate_町の代表点がない場合_市区町村の代表点で代替する() {
+        let address = Address::new("東京都", "千代田区", "大手町一丁目", "1-1");
+        let coordinate = coordinate(&address, &towns()).unwrap();
+        assert_eq!(coordinate.precision, Precision::City);
+        assert!((coordinate.latitude - 35.69).abs() < 1e-5);
+        assert!((coordinate.longitude - 139.76).abs() < 1e-5);
+    }
+
+    #[test]
+    fn coordinate_市区町村名がない場合_都道府県庁の所在地で代替する() {
+        let address = Address::new("東京都", "", "", "千代田区千代田1-1");
+        let coordinate = coordinate(&address, &[]).unwrap();
+        assert_eq!(coordinate.precision, Precision::Prefecture);
+        assert_eq!(coordinate.latitude, 35.6895);
+    }
+
+    #[test]
+    fn coordinate_都道府県名がない場合() {
+        let address = Address::new("", "", "", "東京都千代田区千代田1-1");
+        assert_eq!(coordinate(&address, &[]), None);
+    }
+
+    #[test]
+    fn prefecture_offices_全ての都道府県を含む() {
+        for prefecture in jisx0401::Prefecture::values() {
+            assert!(PREFECTURE_OFFICES
+                .iter()
+                .any(|(name, _, _)| *name == prefecture.name_ja()));
+        }
+    }
+}
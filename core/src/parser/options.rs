@@ -0,0 +1,81 @@
+/// Options to control how addresses are parsed.
+///
+/// パーサーの挙動を実行時に切り替えるためのオプションです。
+/// 各項目の初期値は対応するcargo featureが有効かどうかで決まるため、
+/// featureを指定しない場合でも必要な処理だけを`Parser`ごとに有効にできます。
+///
+/// # Example
+/// ```
+/// use japanese_address_parser::parser::{ParseOptions, Parser};
+///
+/// async fn example() {
+///     let options = ParseOptions::default()
+///         .format_house_number(true)
+///         .eliminate_whitespaces(true);
+///     let parser = Parser::default().options(options);
+///     let result = parser.parse("東京都 千代田区 千代田 1-1").await;
+///     println!("{:?}", result);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    pub(crate) infer_prefecture: bool,
+    pub(crate) city_name_correction: bool,
+    pub(crate) format_house_number: bool,
+    pub(crate) eliminate_whitespaces: bool,
+    pub(crate) fix_halfwidth_katakana: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            infer_prefecture: false,
+            city_name_correction: cfg!(feature = "city-name-correction"),
+            format_house_number: cfg!(feature = "format-house-number"),
+            eliminate_whitespaces: cfg!(feature = "eliminate-whitespaces"),
+            fix_halfwidth_katakana: cfg!(feature = "fix-halfwidth-katakana"),
+        }
+    }
+}
+
+impl ParseOptions {
+    /// 都道府県名が省略されている場合に、市区町村名から都道府県を推定します。
+    ///
+    /// 詳しくは`Parser::infer_prefecture`を参照してください。
+    pub fn infer_prefecture(mut self, enabled: bool) -> Self {
+        self.infer_prefecture = enabled;
+        self
+    }
+
+    /// 市区町村名を検出できない場合に、郡名を補完して市区町村名を推定します。
+    ///
+    /// 初期値は`city-name-correction` featureが有効かどうかで決まります。
+    pub fn city_name_correction(mut self, enabled: bool) -> Self {
+        self.city_name_correction = enabled;
+        self
+    }
+
+    /// 町名以降の「1-2」のような表記を「1番2号」の形に整形します。
+    ///
+    /// 初期値は`format-house-number` featureが有効かどうかで決まります。
+    pub fn format_house_number(mut self, enabled: bool) -> Self {
+        self.format_house_number = enabled;
+        self
+    }
+
+    /// 入力に含まれる空白文字を取り除きます。
+    ///
+    /// 初期値は`eliminate-whitespaces` featureが有効かどうかで決まります。
+    pub fn eliminate_whitespaces(mut self, enabled: bool) -> Self {
+        self.eliminate_whitespaces = enabled;
+        self
+    }
+
+    /// 入力に含まれる半角カタカナを全角カタカナに変換します。
+    ///
+    /// 初期値は`fix-halfwidth-katakana` featureが有効かどうかで決まります。
+    pub fn fix_halfwidth_katakana(mut self, enabled: bool) -> Self {
+        self.fix_halfwidth_katakana = enabled;
+        self
+    }
+}
@@ -0,0 +1,172 @@
+use crate::domain::geolonia::entity::Address;
+use crate::domain::geolonia::error::{Error, ErrorKind};
+use crate::domain::postal_code::entity::PostalCodeEntry;
+use crate::formatter::postal_code::normalize_postal_code;
+use crate::http::client::ApiClient;
+use crate::interactor::postal_code::{PostalCodeInteractor, PostalCodeInteractorImpl};
+use crate::parser::Parser;
+use crate::source::AddressMasterSource;
+
+impl<Client: ApiClient, Source: AddressMasterSource> Parser<Client, Source> {
+    /// Returns the areas corresponding to the given `postal_code` asynchronously.
+    ///
+    /// `〒123-4567`、`123-4567`、`1234567`のいずれの表記も受け付けます。
+    /// 郵便番号データは住所マスタと同じ`ApiClient`を通じて取得するため、
+    /// `LocalDirectoryApiClient`を使用すれば手元に用意したデータを参照できます。
+    /// `Parser::embedded()`や`Parser::with_source()`で生成し、`postal_code_api_client`を指定していない場合は通信を行わず、`ErrorKind::PostalCodeLookupUnavailable`を返します。
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::parser::Parser;
+    ///
+    /// async fn example() {
+    ///     let parser: Parser = Default::default();
+    ///     for entry in parser.lookup_postal_code("〒100-0001").await.unwrap() {
+    ///         println!("{}{}{}", entry.prefecture, entry.city, entry.town);
+    ///     }
+    /// }
+    /// ```
+    pub async fn lookup_postal_code(
+        &self,
+        postal_code: &str,
+    ) -> Result<Vec<PostalCodeEntry>, Error> {
+        let postal_code = normalize_postal_code(postal_code).ok_or(ErrorKind::InvalidPostalCode)?;
+        self.postal_code_interactor()?
+            .get_postal_code_entries(&postal_code)
+            .await
+    }
+
+    /// Checks asynchronously whether the given `address` is located in the area of `postal_code`.
+    ///
+    /// # Example
+    /// ```
+    /// use japanese_address_parser::parser::Parser;
+    ///
+    /// async fn example() {
+    ///     let parser: Parser = Default::default();
+    ///     let result = parser.parse("〒100-0001 東京都千代田区千代田1-1").await;
+    ///     if let Some(postal_code) = &result.address.postal_code {
+    ///         let valid = parser.validate_postal_code(postal_code, &result.address).await;
+    ///         println!("{:?}", valid);
+    ///     }
+    /// }
+    /// ```
+    pub async fn validate_postal_code(
+        &self,
+        postal_code: &str,
+        address: &Address,
+    ) -> Result<bool, Error> {
+        let entries = self.lookup_postal_code(postal_code).await?;
+        Ok(entries.iter().any(|entry| entry.matches(address)))
+    }
+
+    /// Returns the areas corresponding to the given `postal_code` synchronously.
+    #[cfg(feature = "blocking")]
+    pub fn lookup_postal_code_blocking(
+        &self,
+        postal_code: &str,
+    ) -> Result<Vec<PostalCodeEntry>, Error> {
+        let postal_code = normalize_postal_code(postal_code).ok_or(ErrorKind::InvalidPostalCode)?;
+        self.postal_code_interactor()?
+            .get_blocking_postal_code_entries(&postal_code)
+    }
+
+    /// Checks synchronously whether the given `address` is located in the area of `postal_code`.
+    #[cfg(feature = "blocking")]
+    pub fn validate_postal_code_blocking(
+        &self,
+        postal_code: &str,
+        address: &Address,
+    ) -> Result<bool, Error> {
+        let entries = self.lookup_postal_code_blocking(postal_code)?;
+        Ok(entries.iter().any(|entry| entry.matches(address)))
+    }
+
+    fn postal_code_interactor(&self) -> Result<&PostalCodeInteractorImpl<Client>, Error> {
+        self.postal_code_interactor
+            .as_deref()
+            .ok_or_else(|| ErrorKind::PostalCodeLookupUnavailable.into())
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod async_tests {
+    use crate::domain::geolonia::entity::Address;
+    use crate::domain::geolonia::error::ErrorKind;
+    use crate::http::local_directory_client::LocalDirectoryApiClient;
+    use crate::parser::Parser;
+    use std::path::PathBuf;
+
+    fn prepare_directory(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "japanese-address-parser-postal-code-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let directory = root
+            .join("madefor.github.io")
+            .join("postal-code-api")
+            .join("api")
+            .join("v1")
+            .join("100");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(
+            directory.join("0001.json"),
+            r#"{"code": "1000001", "data": [{"prefcode": "13", "ja": {"prefecture": "東京都", "address1": "千代田区", "address2": "千代田", "address3": "", "address4": ""}}]}"#,
+        )
+        .unwrap();
+        root
+    }
+
+    #[tokio::test]
+    async fn lookup_postal_code_成功() {
+        let root = prepare_directory("lookup");
+        let parser = Parser::with_api_client(LocalDirectoryApiClient::with_root(&root));
+        let entries = parser
+            .lookup_postal_code("〒１００－０００１")
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].prefecture, "東京都");
+        assert_eq!(entries[0].city, "千代田区");
+        assert_eq!(entries[0].town, "千代田");
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn lookup_postal_code_形式が正しくない場合() {
+        let parser = Parser::with_api_client(LocalDirectoryApiClient::with_root("."));
+        let result = parser.lookup_postal_code("100-001").await;
+        assert_eq!(result.unwrap_err().kind, ErrorKind::InvalidPostalCode);
+    }
+
+    #[tokio::test]
+    async fn lookup_postal_code_郵便番号データを参照しない場合() {
+        let parser = Parser {
+            postal_code_interactor: None,
+            ..Parser::with_api_client(LocalDirectoryApiClient::with_root("."))
+        };
+        let result = parser.lookup_postal_code("100-0001").await;
+        assert_eq!(
+            result.unwrap_err().kind,
+            ErrorKind::PostalCodeLookupUnavailable
+        );
+    }
+
+    #[tokio::test]
+    async fn validate_postal_code_成功() {
+        let root = prepare_directory("validate");
+        let parser = Parser::with_api_client(LocalDirectoryApiClient::with_root(&root));
+        let address = Address::new("東京都", "千代田区", "千代田", "1-1");
+        assert!(parser
+            .validate_postal_code("1000001", &address)
+            .await
+            .unwrap());
+        let address = Address::new("東京都", "中央区", "銀座一丁目", "1-1");
+        assert!(!parser
+            .validate_postal_code("1000001", &address)
+            .await
+            .unwrap());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
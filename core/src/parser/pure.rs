@@ -1,44 +1,135 @@
-use crate::domain::geolonia::entity::Address;
-use crate::domain::geolonia::error::{Error, ParseErrorKind};
-use crate::parser::ParseResult;
-use crate::tokenizer::{CityNameFound, Init, PrefectureNameFound, Tokenizer};
+use crate::domain::geolonia::error::{Error, ErrorKind, ParseErrorKind};
+use crate::parser::{ParseOptions, ParseResult};
+use crate::tokenizer::{CityNameFound, Init, PrefectureNameFound, Tokenizer, TownNameFound};
 
-type PrefectureName = String;
-type CityName = String;
+/// 都道府県名
+pub type PrefectureName = String;
+/// 市区町村名
+pub type CityName = String;
+/// 町名
+pub type TownName = String;
 
 pub(crate) enum State {
     Init(Tokenizer<Init>),
+    /// 都道府県名が省略されているため、全ての都道府県の市区町村名のリストを待っている
+    InferPrefecture(Tokenizer<Init>),
     WaitPrefectureMasterData(Tokenizer<PrefectureNameFound>, PrefectureName),
     WaitCityMasterData(Tokenizer<CityNameFound>),
-    Temporary,
+    WaitKoazaList(Tokenizer<TownNameFound>),
+    /// `PureParserAction::Done`を返した後
+    Finished,
 }
 
-pub(crate) enum PureParserAction {
+/// What `PureParser` needs next
+///
+/// `PureParser::advance`の戻り値です。`Done`以外の場合は、要求されたリストを
+/// `PureParser::provide_input`で渡してから再び`advance`を呼び出してください。
+///
+/// 今後要求の種類が追加される可能性があるため、`match`する際はワイルドカードを使用してください。
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
+pub enum PureParserAction {
+    /// 指定した都道府県に属する市区町村名のリストを要求する
     RequestCityNameList(PrefectureName),
+    /// 指定した全ての都道府県について、市区町村名のリストをまとめて要求する
+    ///
+    /// 都道府県名が省略されていて、都道府県名の推定が有効な場合に要求されます。
+    /// `PureParser::provide_city_name_lists`で都道府県名と市区町村名のリストの組を渡してください。
+    /// 取得できなかった都道府県は省略でき、市区町村名が一致しなかったものとして扱います。
+    RequestAllCityNameLists(Vec<PrefectureName>),
+    /// 指定した市区町村に属する町名のリストを要求する
     RequestTownNameList(PrefectureName, CityName),
+    /// 直前に要求した市区町村の町名マスタのうち、指定した町に属する小字・通称名のリストを要求する
+    ///
+    /// 小字・通称名を扱わない場合は空のリストを渡してください。
+    RequestKoazaList(TownName),
+    /// パースが完了した
+    ///
+    /// `coordinate`は住所マスタの代表点から求めるため、常に`None`になります。
     Done(ParseResult),
 }
 
-pub(crate) struct PureParser {
+/// A sans-IO address parser
+///
+/// 住所マスタの取得を呼び出し側に任せる状態機械です。
+/// `ApiClient`を実装することなく、Redisやデータベース、ローカルファイルなど任意の保存先から
+/// 読み込んだ住所マスタを使って住所をパースできます。
+///
+/// `advance`が返す`PureParserAction`に応じて名称のリストを`provide_input`で渡すことを、
+/// `PureParserAction::Done`が返されるまで繰り返します。
+/// ただし`PureParserAction::RequestAllCityNameLists`の場合は`provide_city_name_lists`を使用します。
+/// 住所マスタを取得できなかった場合は`abort`でパースを中断し、それまでの結果を得られます。
+///
+/// # Example
+/// ```
+/// use japanese_address_parser::parser::pure::{PureParser, PureParserAction};
+/// use japanese_address_parser::parser::ParseOptions;
+/// use std::collections::HashMap;
+///
+/// let cities = HashMap::from([("東京都", vec!["千代田区", "中央区"])]);
+/// let towns = HashMap::from([(("東京都", "千代田区"), vec!["千代田", "丸の内一丁目"])]);
+///
+/// let mut parser = PureParser::new("東京都千代田区丸の内一丁目1-1", ParseOptions::default());
+/// let result = loop {
+///     match parser.advance() {
+///         PureParserAction::RequestCityNameList(prefecture_name) => {
+///             let names = cities.get(prefecture_name.as_str()).cloned().unwrap_or_default();
+///             parser.provide_input(names.into_iter().map(String::from).collect());
+///         }
+///         PureParserAction::RequestTownNameList(prefecture_name, city_name) => {
+///             let key = (prefecture_name.as_str(), city_name.as_str());
+///             let names = towns.get(&key).cloned().unwrap_or_default();
+///             parser.provide_input(names.into_iter().map(String::from).collect());
+///         }
+///         PureParserAction::RequestKoazaList(_) => parser.provide_input(vec![]),
+///         PureParserAction::Done(result) => break result,
+///         action => unimplemented!("{:?}", action),
+///     }
+/// };
+/// assert_eq!(result.address.city, "千代田区");
+/// assert_eq!(result.address.town, "丸の内一丁目");
+/// assert_eq!(result.error, None);
+/// ```
+pub struct PureParser {
     state: State,
     input: Option<Vec<String>>,
+    city_name_lists: Option<Vec<(PrefectureName, Vec<CityName>)>>,
+    options: ParseOptions,
 }
 
 impl PureParser {
-    pub fn new(address: &str) -> Self {
+    /// Constructs a new `PureParser` for the given `address`.
+    pub fn new(address: &str, options: ParseOptions) -> Self {
         Self {
-            state: State::Init(Tokenizer::new(address)),
+            state: State::Init(Tokenizer::new(address, &options)),
             input: None,
+            city_name_lists: None,
+            options,
         }
     }
 
+    /// 直前の`advance`で要求された名称のリストを渡す
     pub fn provide_input(&mut self, data: Vec<String>) {
         self.input = Some(data);
     }
 
+    /// 直前の`advance`で要求された、都道府県ごとの市区町村名のリストを渡す
+    ///
+    /// `PureParserAction::RequestAllCityNameLists`に対して使用します。
+    pub fn provide_city_name_lists(&mut self, lists: Vec<(PrefectureName, Vec<CityName>)>) {
+        self.city_name_lists = Some(lists);
+    }
+
+    /// パースを進め、次に必要なものを返す
+    ///
+    /// # Panics
+    /// 要求されたリストを`provide_input`で渡さずに呼び出した場合、
+    /// または`PureParserAction::Done`を返した後に呼び出した場合はパニックします。
     pub fn advance(&mut self) -> PureParserAction {
-        let current_state = std::mem::replace(&mut self.state, State::Temporary);
+        let current_state = std::mem::replace(&mut self.state, State::Finished);
         let input = self.input.take();
+        let city_name_lists = self.city_name_lists.take();
 
         match current_state {
             State::Init(tokenizer) => match tokenizer.read_prefecture() {
@@ -47,12 +138,25 @@ impl PureParser {
                     self.state = State::WaitPrefectureMasterData(next_tokenizer, pref_name.clone());
                     PureParserAction::RequestCityNameList(pref_name)
                 }
-                Err(tokenizer) => PureParserAction::Done(ParseResult {
-                    address: Address::from(tokenizer),
-                    error: Some(Error::new_parse_error(ParseErrorKind::Prefecture)),
-                }),
+                Err(_) if self.options.infer_prefecture => {
+                    self.state = State::InferPrefecture(tokenizer);
+                    PureParserAction::RequestAllCityNameLists(
+                        jisx0401::Prefecture::values()
+                            .map(|prefecture| prefecture.name_ja().to_string())
+                            .collect(),
+                    )
+                }
+                Err(tokenizer) => PureParserAction::Done(ParseResult::new(
+                    tokenizer,
+                    Some(Error::new_parse_error(ParseErrorKind::Prefecture)),
+                )),
             },
 
+            State::InferPrefecture(tokenizer) => {
+                let lists = city_name_lists.expect("city name lists are required");
+                self.infer_prefecture(tokenizer, lists)
+            }
+
             State::WaitPrefectureMasterData(tokenizer, pref_name) => {
                 let city_names = input.expect("city name list is required");
                 match tokenizer.read_city(&city_names) {
@@ -63,15 +167,15 @@ impl PureParser {
                     Err(tokenizer) => {
                         match tokenizer.read_city_with_county_name_completion(&city_names) {
                             Ok((city_name, next_tokenizer))
-                                if cfg!(feature = "city-name-correction") =>
+                                if self.options.city_name_correction =>
                             {
                                 self.state = State::WaitCityMasterData(next_tokenizer);
                                 PureParserAction::RequestTownNameList(pref_name.clone(), city_name)
                             }
-                            _ => PureParserAction::Done(ParseResult {
-                                address: Address::from(tokenizer.finish()),
-                                error: Some(Error::new_parse_error(ParseErrorKind::City)),
-                            }),
+                            _ => PureParserAction::Done(ParseResult::new(
+                                tokenizer.finish(),
+                                Some(Error::new_parse_error(ParseErrorKind::City)),
+                            )),
                         }
                     }
                 }
@@ -79,53 +183,108 @@ impl PureParser {
 
             State::WaitCityMasterData(tokenizer) => {
                 let town_names = input.expect("town name list is required");
-                match tokenizer.read_town(town_names) {
-                    Ok((_, next_tokenizer)) => PureParserAction::Done(ParseResult {
-                        address: Address::from(next_tokenizer.finish()),
-                        error: None,
-                    }),
-                    Err(tokenizer) => PureParserAction::Done(ParseResult {
-                        address: Address::from(tokenizer),
-                        error: Some(Error::new_parse_error(ParseErrorKind::Town)),
-                    }),
+                match tokenizer.read_town(town_names, &self.options) {
+                    Ok((town_name, next_tokenizer)) => {
+                        self.state = State::WaitKoazaList(next_tokenizer);
+                        PureParserAction::RequestKoazaList(town_name)
+                    }
+                    Err(tokenizer) => PureParserAction::Done(ParseResult::new(
+                        tokenizer,
+                        Some(Error::new_parse_error(ParseErrorKind::Town)),
+                    )),
                 }
             }
 
-            State::Temporary => unreachable!(),
+            State::WaitKoazaList(tokenizer) => {
+                let koaza_names = input.expect("koaza name list is required");
+                let tokenizer = tokenizer.read_koaza(&koaza_names);
+                PureParserAction::Done(ParseResult::new(tokenizer.finish(), None))
+            }
+
+            State::Finished => panic!("PureParser::advance called after Done"),
+        }
+    }
+
+    /// 市区町村名が一致する都道府県を探し、推定結果に応じて次の状態に進む
+    ///
+    /// 候補は`lists`に含まれる都道府県の順に並べる。
+    fn infer_prefecture(
+        &mut self,
+        tokenizer: Tokenizer<Init>,
+        lists: Vec<(PrefectureName, Vec<CityName>)>,
+    ) -> PureParserAction {
+        let mut found: Vec<(PrefectureName, CityName, Tokenizer<CityNameFound>)> = lists
+            .into_iter()
+            .filter_map(|(pref_name, city_names)| {
+                let (city_name, next_tokenizer) = tokenizer
+                    .assume_prefecture(&pref_name)
+                    .read_city(&city_names)
+                    .ok()?;
+                Some((pref_name, city_name, next_tokenizer))
+            })
+            .collect();
+        if found.len() > 1 {
+            let candidates = found
+                .into_iter()
+                .map(|(pref_name, _, _)| pref_name)
+                .collect();
+            return PureParserAction::Done(ParseResult::new(
+                tokenizer.finish(),
+                Some(Error::from(ErrorKind::AmbiguousPrefecture { candidates })),
+            ));
+        }
+        match found.pop() {
+            Some((pref_name, city_name, next_tokenizer)) => {
+                self.state = State::WaitCityMasterData(next_tokenizer);
+                PureParserAction::RequestTownNameList(pref_name, city_name)
+            }
+            None => PureParserAction::Done(ParseResult::new(
+                tokenizer.finish(),
+                Some(Error::new_parse_error(ParseErrorKind::Prefecture)),
+            )),
         }
     }
 
     /// IOエラーなど、途中で解析を中断してエラーを返す場合に使用する
+    ///
+    /// それまでに検出できた部分と`error`を含む`ParseResult`を返します。
+    ///
+    /// # Panics
+    /// `PureParserAction::Done`を返した後に呼び出した場合はパニックします。
     pub fn abort(self, error: Error) -> ParseResult {
         let current_state = self.state;
         let tokenizer = match current_state {
             State::Init(t) => t.finish(),
+            State::InferPrefecture(t) => t.finish(),
             State::WaitPrefectureMasterData(t, _) => t.finish(),
             State::WaitCityMasterData(t) => t.finish(),
-            State::Temporary => unreachable!(),
+            State::WaitKoazaList(t) => t.finish(),
+            State::Finished => panic!("PureParser::abort called after Done"),
         };
 
-        ParseResult {
-            address: Address::from(tokenizer),
-            error: Some(error),
-        }
+        ParseResult::new(tokenizer, Some(error))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::geolonia::error::{ApiErrorKind, Error};
+    use crate::domain::geolonia::entity::CorrectionKind;
+    use crate::domain::geolonia::error::{Error, ErrorKind};
+    use crate::http::error::ApiClientError;
     use crate::parser::pure::{PureParser, PureParserAction, State};
+    use crate::parser::ParseOptions;
 
     #[test]
     fn new() {
-        let pure_parser = PureParser::new("東京都杉並区阿佐谷南1丁目15番1号");
+        let pure_parser =
+            PureParser::new("東京都杉並区阿佐谷南1丁目15番1号", ParseOptions::default());
         assert!(matches!(pure_parser.state, State::Init(_)));
     }
 
     #[test]
     fn provide_input() {
-        let mut pure_parser = PureParser::new("東京都杉並区阿佐谷南1丁目15番1号");
+        let mut pure_parser =
+            PureParser::new("東京都杉並区阿佐谷南1丁目15番1号", ParseOptions::default());
         assert!(pure_parser.input.is_none());
 
         pure_parser.provide_input(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
@@ -138,7 +297,8 @@ mod tests {
 
     #[test]
     fn advance() {
-        let mut pure_parser = PureParser::new("東京都杉並区阿佐谷南1丁目15番1号");
+        let mut pure_parser =
+            PureParser::new("東京都杉並区阿佐谷南1丁目15番1号", ParseOptions::default());
         assert!(matches!(pure_parser.state, State::Init(_)));
 
         let action = pure_parser.advance();
@@ -166,17 +326,177 @@ mod tests {
             "阿佐谷南三丁目".to_string(),
         ]);
         let action = pure_parser.advance();
+        assert!(matches!(
+            action,
+            PureParserAction::RequestKoazaList(ref town_name) if town_name == "阿佐谷南一丁目"
+        ));
+        assert!(matches!(pure_parser.state, State::WaitKoazaList(_)));
+
+        pure_parser.provide_input(vec![]);
+        let action = pure_parser.advance();
         assert!(matches!(action, PureParserAction::Done(_)));
     }
 
+    #[test]
+    fn advance_都道府県名を推定する() {
+        let mut pure_parser = PureParser::new(
+            "杉並区阿佐谷南1丁目15番1号",
+            ParseOptions::default().infer_prefecture(true),
+        );
+        let PureParserAction::RequestAllCityNameLists(requested) = pure_parser.advance() else {
+            panic!("all city name lists should be requested");
+        };
+        assert_eq!(requested.len(), 47);
+        assert_eq!(requested[0], "北海道");
+
+        let lists = requested
+            .into_iter()
+            .map(|pref_name| {
+                let city_names = match pref_name.as_str() {
+                    "東京都" => vec!["杉並区".to_string(), "中野区".to_string()],
+                    _ => vec![],
+                };
+                (pref_name, city_names)
+            })
+            .collect();
+        pure_parser.provide_city_name_lists(lists);
+        assert!(matches!(
+            pure_parser.advance(),
+            PureParserAction::RequestTownNameList(ref pref_name, ref city_name)
+                if pref_name == "東京都" && city_name == "杉並区"
+        ));
+
+        pure_parser.provide_input(vec!["阿佐谷南一丁目".to_string()]);
+        assert!(matches!(
+            pure_parser.advance(),
+            PureParserAction::RequestKoazaList(_)
+        ));
+        pure_parser.provide_input(vec![]);
+        let PureParserAction::Done(result) = pure_parser.advance() else {
+            panic!("parsing should be done");
+        };
+        assert_eq!(result.address.prefecture, "東京都");
+        assert_eq!(result.address.town, "阿佐谷南一丁目");
+        assert_eq!(
+            result.corrections[0].kind,
+            CorrectionKind::PrefectureCompletion
+        );
+    }
+
+    #[test]
+    fn advance_都道府県名を一つに特定できない場合() {
+        let mut pure_parser = PureParser::new(
+            "府中市宮西町2丁目24番地",
+            ParseOptions::default().infer_prefecture(true),
+        );
+        let PureParserAction::RequestAllCityNameLists(requested) = pure_parser.advance() else {
+            panic!("all city name lists should be requested");
+        };
+        let lists = requested
+            .into_iter()
+            .filter(|pref_name| pref_name == "東京都" || pref_name == "広島県")
+            .map(|pref_name| (pref_name, vec!["府中市".to_string()]))
+            .collect();
+        pure_parser.provide_city_name_lists(lists);
+        let PureParserAction::Done(result) = pure_parser.advance() else {
+            panic!("parsing should be done");
+        };
+        assert_eq!(result.address.prefecture, "");
+        assert_eq!(
+            result.error.unwrap().kind,
+            ErrorKind::AmbiguousPrefecture {
+                candidates: vec!["東京都".to_string(), "広島県".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn advance_市区町村名のリストが渡されなかった都道府県は一致しないものとして扱う() {
+        let mut pure_parser = PureParser::new(
+            "府中市宮西町2丁目24番地",
+            ParseOptions::default().infer_prefecture(true),
+        );
+        assert!(matches!(
+            pure_parser.advance(),
+            PureParserAction::RequestAllCityNameLists(_)
+        ));
+        // 広島県の市区町村名のリストは取得できなかった
+        pure_parser
+            .provide_city_name_lists(vec![("東京都".to_string(), vec!["府中市".to_string()])]);
+        assert!(matches!(
+            pure_parser.advance(),
+            PureParserAction::RequestTownNameList(ref pref_name, ref city_name)
+                if pref_name == "東京都" && city_name == "府中市"
+        ));
+    }
+
+    #[test]
+    fn advance_郡名の補完を無効にした場合() {
+        for (enabled, expected_city_name) in [(true, Some("秩父郡東秩父村")), (false, None)]
+        {
+            let options = ParseOptions::default().city_name_correction(enabled);
+            let mut pure_parser = PureParser::new("埼玉県東秩父村大字御堂634番地", options);
+            pure_parser.advance();
+            pure_parser.provide_input(vec!["秩父市".to_string(), "秩父郡東秩父村".to_string()]);
+            let city_name = match pure_parser.advance() {
+                PureParserAction::RequestTownNameList(_, city_name) => Some(city_name),
+                _ => None,
+            };
+            assert_eq!(city_name.as_deref(), expected_city_name);
+        }
+    }
+
+    #[test]
+    fn advance_小字を読み取る() {
+        let mut pure_parser = PureParser::new(
+            "青森県西津軽郡鰺ヶ沢町大字舞戸町鳴戸321番地",
+            ParseOptions::default(),
+        );
+        assert!(matches!(
+            pure_parser.advance(),
+            PureParserAction::RequestCityNameList(_)
+        ));
+        pure_parser.provide_input(vec!["西津軽郡鰺ヶ沢町".to_string()]);
+        assert!(matches!(
+            pure_parser.advance(),
+            PureParserAction::RequestTownNameList(_, _)
+        ));
+        pure_parser.provide_input(vec!["大字舞戸町".to_string()]);
+        assert!(matches!(
+            pure_parser.advance(),
+            PureParserAction::RequestKoazaList(_)
+        ));
+        pure_parser.provide_input(vec!["字鳴戸".to_string(), "字小夜".to_string()]);
+        let PureParserAction::Done(result) = pure_parser.advance() else {
+            panic!("parsing should be done");
+        };
+        assert_eq!(result.address.town, "大字舞戸町");
+        assert_eq!(result.address.koaza, "字鳴戸");
+        assert_eq!(result.address.rest, "321番地");
+        assert_eq!(result.corrections[0].kind, CorrectionKind::AzaInsertion);
+        let koaza = result.spans.koaza.unwrap();
+        assert_eq!((koaza.char_start, koaza.char_end), (16, 18));
+    }
+
     #[test]
     fn abort() {
-        let pure_parser = PureParser::new("東京都杉並区阿佐谷南1丁目15番1号");
-        let result = pure_parser.abort(Error::new_api_error(ApiErrorKind::Fetch(
-            "hoge".to_string(),
-        )));
+        let pure_parser =
+            PureParser::new("東京都杉並区阿佐谷南1丁目15番1号", ParseOptions::default());
+        let result = pure_parser.abort(Error::new_api_error(ApiClientError::Request {
+            url: "hoge".to_string(),
+            message: "fuga".to_string(),
+        }));
 
         assert_eq!(result.address.rest, "東京都杉並区阿佐谷南1丁目15番1号");
         assert!(result.error.is_some());
     }
+
+    #[test]
+    #[should_panic(expected = "PureParser::advance called after Done")]
+    fn advance_完了後に呼び出した場合() {
+        let options = ParseOptions::default().infer_prefecture(false);
+        let mut pure_parser = PureParser::new("杉並区阿佐谷南1丁目15番1号", options);
+        assert!(matches!(pure_parser.advance(), PureParserAction::Done(_)));
+        pure_parser.advance();
+    }
 }
@@ -1,3 +1,4 @@
 #[cfg(feature = "experimental")]
 pub mod chimei_ruiju;
 pub mod geolonia;
+pub mod postal_code;
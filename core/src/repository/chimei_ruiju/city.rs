@@ -1,10 +1,12 @@
 use crate::domain::chimei_ruiju::entity::CityMaster;
 use crate::domain::chimei_ruiju::error::ApiError;
 use crate::http::client::ApiClient;
+use crate::http::endpoints::{chimei_ruiju_url, encode_path_segment};
 use jisx0401::Prefecture;
 
 pub struct CityMasterRepository<C: ApiClient> {
     pub api_client: C,
+    pub base_url: String,
 }
 
 impl<C: ApiClient> CityMasterRepository<C> {
@@ -14,9 +16,9 @@ impl<C: ApiClient> CityMasterRepository<C> {
         city_name: &str,
     ) -> Result<CityMaster, ApiError> {
         let url = format!(
-            "https://{}.chimei-ruiju.org/{}/master.json",
-            prefecture.name_en(),
-            city_name
+            "{}/{}/master.json",
+            chimei_ruiju_url(&self.base_url, prefecture.name_en()),
+            encode_path_segment(city_name)
         );
         self.api_client
             .fetch::<CityMaster>(&url)
@@ -27,6 +29,7 @@ impl<C: ApiClient> CityMasterRepository<C> {
 
 #[cfg(test)]
 mod async_tests {
+    use crate::http::endpoints::Endpoints;
     use crate::http::reqwest_client::ReqwestApiClient;
     use crate::repository::chimei_ruiju::city::CityMasterRepository;
     use jisx0401::Prefecture;
@@ -34,7 +37,8 @@ mod async_tests {
     #[tokio::test]
     async fn 神奈川県愛甲郡清川村() {
         let repository = CityMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().chimei_ruiju,
         };
         let result = repository.get(&Prefecture::KANAGAWA, "愛甲郡清川村").await;
         assert!(result.is_ok());
@@ -46,7 +50,8 @@ mod async_tests {
     #[tokio::test]
     async fn 京都府乙訓郡大山崎町() {
         let repository = CityMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().chimei_ruiju,
         };
         let result = repository.get(&Prefecture::KYOTO, "乙訓郡大山崎町").await;
         assert!(result.is_ok());
@@ -65,9 +70,9 @@ impl<C: ApiClient> CityMasterRepository<C> {
         city_name: &str,
     ) -> Result<CityMaster, ApiError> {
         let url = format!(
-            "https://{}.chimei-ruiju.org/{}/master.json",
-            prefecture.name_en(),
-            city_name
+            "{}/{}/master.json",
+            chimei_ruiju_url(&self.base_url, prefecture.name_en()),
+            encode_path_segment(city_name)
         );
         self.api_client.fetch_blocking(&url).map_err(|e| e.into())
     }
@@ -75,6 +80,7 @@ impl<C: ApiClient> CityMasterRepository<C> {
 
 #[cfg(all(test, feature = "blocking"))]
 mod blocking_tests {
+    use crate::http::endpoints::Endpoints;
     use crate::http::reqwest_client::ReqwestApiClient;
     use crate::repository::chimei_ruiju::city::CityMasterRepository;
     use jisx0401::Prefecture;
@@ -82,7 +88,8 @@ mod blocking_tests {
     #[test]
     fn 埼玉県比企郡嵐山町() {
         let repository = CityMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().chimei_ruiju,
         };
         let result = repository.get_blocking(&Prefecture::SAITAMA, "比企郡嵐山町");
         assert!(result.is_ok());
@@ -119,7 +126,8 @@ mod blocking_tests {
     #[test]
     fn 岐阜県不破郡関ケ原町() {
         let repository = CityMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().chimei_ruiju,
         };
         let result = repository.get_blocking(&Prefecture::GIFU, "不破郡関ケ原町");
         assert!(result.is_ok());
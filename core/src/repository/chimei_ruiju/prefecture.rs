@@ -1,17 +1,19 @@
 use crate::domain::chimei_ruiju::entity::PrefectureMaster;
 use crate::domain::chimei_ruiju::error::ApiError;
 use crate::http::client::ApiClient;
+use crate::http::endpoints::chimei_ruiju_url;
 use jisx0401::Prefecture;
 
 pub struct PrefectureMasterRepository<C: ApiClient> {
     pub api_client: C,
+    pub base_url: String,
 }
 
 impl<C: ApiClient> PrefectureMasterRepository<C> {
     pub async fn get(&self, prefecture: &Prefecture) -> Result<PrefectureMaster, ApiError> {
         let url = format!(
-            "https://{}.chimei-ruiju.org/master.json",
-            prefecture.name_en()
+            "{}/master.json",
+            chimei_ruiju_url(&self.base_url, prefecture.name_en())
         );
         self.api_client
             .fetch::<PrefectureMaster>(&url)
@@ -22,6 +24,7 @@ impl<C: ApiClient> PrefectureMasterRepository<C> {
 
 #[cfg(test)]
 mod async_tests {
+    use crate::http::endpoints::Endpoints;
     use crate::http::reqwest_client::ReqwestApiClient;
     use crate::repository::chimei_ruiju::prefecture::PrefectureMasterRepository;
     use jisx0401::Prefecture;
@@ -29,7 +32,8 @@ mod async_tests {
     #[tokio::test]
     async fn 東京都() {
         let repository = PrefectureMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().chimei_ruiju,
         };
         let result = repository.get(&Prefecture::TOKYO).await;
         assert!(result.is_ok());
@@ -107,7 +111,8 @@ mod async_tests {
     #[tokio::test]
     async fn 富山県() {
         let repository = PrefectureMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().chimei_ruiju,
         };
         let result = repository.get(&Prefecture::TOYAMA).await;
         assert!(result.is_ok());
@@ -138,10 +143,11 @@ mod async_tests {
 
 #[cfg(feature = "blocking")]
 impl<C: ApiClient> PrefectureMasterRepository<C> {
+    #[allow(dead_code)]
     pub fn get_blocking(&self, prefecture: &Prefecture) -> Result<PrefectureMaster, ApiError> {
         let url = format!(
-            "https://{}.chimei-ruiju.org/master.json",
-            prefecture.name_en()
+            "{}/master.json",
+            chimei_ruiju_url(&self.base_url, prefecture.name_en())
         );
         self.api_client.fetch_blocking(&url).map_err(|e| e.into())
     }
@@ -149,6 +155,7 @@ impl<C: ApiClient> PrefectureMasterRepository<C> {
 
 #[cfg(all(test, feature = "blocking"))]
 mod blocking_tests {
+    use crate::http::endpoints::Endpoints;
     use crate::http::reqwest_client::ReqwestApiClient;
     use crate::repository::chimei_ruiju::prefecture::PrefectureMasterRepository;
     use jisx0401::Prefecture;
@@ -156,7 +163,8 @@ mod blocking_tests {
     #[test]
     fn 高知県() {
         let repository = PrefectureMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().chimei_ruiju,
         };
         let result = repository.get_blocking(&Prefecture::KOCHI);
         assert!(result.is_ok());
@@ -206,7 +214,8 @@ mod blocking_tests {
     #[test]
     fn 佐賀県() {
         let repository = PrefectureMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().chimei_ruiju,
         };
         let result = repository.get_blocking(&Prefecture::SAGA);
         assert!(result.is_ok());
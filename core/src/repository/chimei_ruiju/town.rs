@@ -1,10 +1,12 @@
 use crate::domain::chimei_ruiju::entity::TownMaster;
 use crate::domain::chimei_ruiju::error::ApiError;
 use crate::http::client::ApiClient;
+use crate::http::endpoints::{chimei_ruiju_url, encode_path_segment};
 use jisx0401::Prefecture;
 
 pub struct TownMasterRepository<C: ApiClient> {
     pub api_client: C,
+    pub base_url: String,
 }
 
 impl<C: ApiClient> TownMasterRepository<C> {
@@ -15,10 +17,10 @@ impl<C: ApiClient> TownMasterRepository<C> {
         town_name: &str,
     ) -> Result<TownMaster, ApiError> {
         let url = format!(
-            "https://{}.chimei-ruiju.org/{}/{}/master.json",
-            prefecture.name_en(),
-            city_name,
-            town_name
+            "{}/{}/{}/master.json",
+            chimei_ruiju_url(&self.base_url, prefecture.name_en()),
+            encode_path_segment(city_name),
+            encode_path_segment(town_name)
         );
         self.api_client
             .fetch::<TownMaster>(&url)
@@ -29,6 +31,7 @@ impl<C: ApiClient> TownMasterRepository<C> {
 
 #[cfg(test)]
 mod async_tests {
+    use crate::http::endpoints::Endpoints;
     use crate::http::reqwest_client::ReqwestApiClient;
     use crate::repository::chimei_ruiju::town::TownMasterRepository;
     use jisx0401::Prefecture;
@@ -36,7 +39,8 @@ mod async_tests {
     #[tokio::test]
     async fn 東京都千代田区千代田() {
         let repository = TownMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().chimei_ruiju,
         };
         let result = repository
             .get(&Prefecture::TOKYO, "千代田区", "千代田")
@@ -57,10 +61,10 @@ impl<C: ApiClient> TownMasterRepository<C> {
         town_name: &str,
     ) -> Result<TownMaster, ApiError> {
         let url = format!(
-            "https://{}.chimei-ruiju.org/{}/{}/master.json",
-            prefecture.name_en(),
-            city_name,
-            town_name
+            "{}/{}/{}/master.json",
+            chimei_ruiju_url(&self.base_url, prefecture.name_en()),
+            encode_path_segment(city_name),
+            encode_path_segment(town_name)
         );
         self.api_client.fetch_blocking(&url).map_err(|e| e.into())
     }
@@ -68,6 +72,7 @@ impl<C: ApiClient> TownMasterRepository<C> {
 
 #[cfg(all(test, feature = "blocking"))]
 mod blocking_tests {
+    use crate::http::endpoints::Endpoints;
     use crate::http::reqwest_client::ReqwestApiClient;
     use crate::repository::chimei_ruiju::town::TownMasterRepository;
     use jisx0401::Prefecture;
@@ -75,7 +80,8 @@ mod blocking_tests {
     #[test]
     fn 京都府京都市伏見区魚屋町() {
         let repository = TownMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().chimei_ruiju,
         };
         let result = repository.get_blocking(&Prefecture::KYOTO, "京都市伏見区", "魚屋町");
         assert!(result.is_ok());
@@ -5,6 +5,7 @@ impl From<ApiClientError> for ApiError {
     fn from(value: ApiClientError) -> Self {
         match value {
             ApiClientError::Request { url, .. } => ApiError::Network { url },
+            ApiClientError::NotFound { url } => ApiError::NotFound { url },
             ApiClientError::Deserialize { url, .. } => ApiError::Deserialize { url },
         }
     }
@@ -1,15 +1,22 @@
 use crate::domain::geolonia::entity::{City, Town};
 use crate::domain::geolonia::error::Error;
 use crate::http::client::ApiClient;
+use crate::http::endpoints::encode_path_segment;
 
+#[derive(Clone)]
 pub struct CityMasterRepository<C: ApiClient> {
     pub api_client: C,
+    pub base_url: String,
 }
 
 impl<C: ApiClient> CityMasterRepository<C> {
     pub async fn get(&self, prefecture_name: &str, city_name: &str) -> Result<City, Error> {
-        let server_url = "https://geolonia.github.io/japanese-addresses/api/ja";
-        let endpoint = format!("{}/{}/{}.json", server_url, prefecture_name, city_name);
+        let endpoint = format!(
+            "{}/{}/{}.json",
+            self.base_url,
+            encode_path_segment(prefecture_name),
+            encode_path_segment(city_name)
+        );
         match self.api_client.fetch::<Vec<Town>>(&endpoint).await {
             Ok(towns) => Ok(City {
                 name: city_name.to_string(),
@@ -21,8 +28,12 @@ impl<C: ApiClient> CityMasterRepository<C> {
 
     #[cfg(feature = "blocking")]
     pub fn get_blocking(&self, prefecture_name: &str, city_name: &str) -> Result<City, Error> {
-        let server_url = "https://geolonia.github.io/japanese-addresses/api/ja";
-        let endpoint = format!("{}/{}/{}.json", server_url, prefecture_name, city_name);
+        let endpoint = format!(
+            "{}/{}/{}.json",
+            self.base_url,
+            encode_path_segment(prefecture_name),
+            encode_path_segment(city_name)
+        );
         match self.api_client.fetch_blocking::<Vec<Town>>(&endpoint) {
             Ok(towns) => Ok(City {
                 name: city_name.to_string(),
@@ -36,13 +47,15 @@ impl<C: ApiClient> CityMasterRepository<C> {
 #[cfg(all(test, not(feature = "blocking")))]
 mod async_tests {
     use crate::domain::geolonia::entity::Town;
+    use crate::http::endpoints::Endpoints;
     use crate::http::reqwest_client::ReqwestApiClient;
     use crate::repository::geolonia::city::CityMasterRepository;
 
     #[tokio::test]
     async fn 非同期_石川県羽咋郡志賀町_成功() {
         let repository = CityMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().geolonia,
         };
         let result = repository.get("石川県", "羽咋郡志賀町").await;
         let city = result.unwrap();
@@ -59,13 +72,14 @@ mod async_tests {
     #[tokio::test]
     async fn 非同期_誤った市区町村名_失敗() {
         let repository = CityMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().geolonia,
         };
         let result = repository.get("石川県", "敦賀市").await;
         assert!(result.is_err());
         assert_eq!(
             result.err().unwrap().error_message,
-            "https://geolonia.github.io/japanese-addresses/api/ja/石川県/敦賀市.jsonを取得できませんでした"
+            "https://geolonia.github.io/japanese-addresses/api/ja/%E7%9F%B3%E5%B7%9D%E7%9C%8C/%E6%95%A6%E8%B3%80%E5%B8%82.jsonを取得できませんでした"
         );
     }
 }
@@ -73,13 +87,15 @@ mod async_tests {
 #[cfg(all(test, feature = "blocking"))]
 mod blocking_tests {
     use crate::domain::geolonia::entity::Town;
+    use crate::http::endpoints::Endpoints;
     use crate::http::reqwest_client::ReqwestApiClient;
     use crate::repository::geolonia::city::CityMasterRepository;
 
     #[test]
     fn 同期_石川県羽咋郡志賀町_成功() {
         let repository = CityMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().geolonia,
         };
         let result = repository.get_blocking("石川県", "羽咋郡志賀町");
         let city = result.unwrap();
@@ -96,13 +112,14 @@ mod blocking_tests {
     #[test]
     fn 同期_誤った市区町村名_失敗() {
         let repository = CityMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().geolonia,
         };
         let result = repository.get_blocking("石川県", "敦賀市");
         assert!(result.is_err());
         assert_eq!(
             result.err().unwrap().error_message,
-            "https://geolonia.github.io/japanese-addresses/api/ja/石川県/敦賀市.jsonを取得できませんでした",
+            "https://geolonia.github.io/japanese-addresses/api/ja/%E7%9F%B3%E5%B7%9D%E7%9C%8C/%E6%95%A6%E8%B3%80%E5%B8%82.jsonを取得できませんでした",
         );
     }
 }
@@ -1,15 +1,21 @@
 use crate::domain::geolonia::entity::Prefecture;
 use crate::domain::geolonia::error::Error;
 use crate::http::client::ApiClient;
+use crate::http::endpoints::encode_path_segment;
 
+#[derive(Clone)]
 pub struct PrefectureMasterRepository<C: ApiClient> {
     pub api_client: C,
+    pub base_url: String,
 }
 
 impl<C: ApiClient> PrefectureMasterRepository<C> {
     pub async fn get(&self, prefecture_name: &str) -> Result<Prefecture, Error> {
-        let server_url = "https://yuukitoriyama.github.io/geolonia-japanese-addresses-accompanist";
-        let endpoint = format!("{}/{}/master.json", server_url, prefecture_name);
+        let endpoint = format!(
+            "{}/{}/master.json",
+            self.base_url,
+            encode_path_segment(prefecture_name)
+        );
         self.api_client
             .fetch::<Prefecture>(&endpoint)
             .await
@@ -18,8 +24,11 @@ impl<C: ApiClient> PrefectureMasterRepository<C> {
 
     #[cfg(feature = "blocking")]
     pub fn get_blocking(&self, prefecture_name: &str) -> Result<Prefecture, Error> {
-        let server_url = "https://yuukitoriyama.github.io/geolonia-japanese-addresses-accompanist";
-        let endpoint = format!("{}/{}/master.json", server_url, prefecture_name);
+        let endpoint = format!(
+            "{}/{}/master.json",
+            self.base_url,
+            encode_path_segment(prefecture_name)
+        );
         self.api_client
             .fetch_blocking::<Prefecture>(&endpoint)
             .map_err(|e| e.into())
@@ -28,13 +37,15 @@ impl<C: ApiClient> PrefectureMasterRepository<C> {
 
 #[cfg(all(test, not(feature = "blocking")))]
 mod tests {
+    use crate::http::endpoints::Endpoints;
     use crate::http::reqwest_client::ReqwestApiClient;
     use crate::repository::geolonia::prefecture::PrefectureMasterRepository;
 
     #[tokio::test]
     async fn 非同期_富山県_成功() {
         let repository = PrefectureMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().geolonia_prefecture,
         };
         let result = repository.get("富山県").await;
         let prefecture = result.unwrap();
@@ -64,26 +75,29 @@ mod tests {
     #[tokio::test]
     async fn 非同期_誤った都道府県名_失敗() {
         let repository = PrefectureMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().geolonia_prefecture,
         };
         let result = repository.get("大阪都").await;
         assert!(result.is_err());
         assert_eq!(
             result.err().unwrap().error_message,
-            "https://yuukitoriyama.github.io/geolonia-japanese-addresses-accompanist/大阪都/master.jsonを取得できませんでした",
+            "https://yuukitoriyama.github.io/geolonia-japanese-addresses-accompanist/%E5%A4%A7%E9%98%AA%E9%83%BD/master.jsonを取得できませんでした",
         );
     }
 }
 
 #[cfg(all(test, feature = "blocking"))]
 mod blocking_tests {
+    use crate::http::endpoints::Endpoints;
     use crate::http::reqwest_client::ReqwestApiClient;
     use crate::repository::geolonia::prefecture::PrefectureMasterRepository;
 
     #[test]
     fn 同期_富山県_成功() {
         let repository = PrefectureMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().geolonia_prefecture,
         };
         let result = repository.get_blocking("富山県");
         let prefecture = result.unwrap();
@@ -113,13 +127,14 @@ mod blocking_tests {
     #[test]
     fn 同期_誤った都道府県名_失敗() {
         let repository = PrefectureMasterRepository {
-            api_client: ReqwestApiClient {},
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().geolonia_prefecture,
         };
         let result = repository.get_blocking("大阪都");
         assert!(result.is_err());
         assert_eq!(
             result.err().unwrap().error_message,
-            "https://yuukitoriyama.github.io/geolonia-japanese-addresses-accompanist/大阪都/master.jsonを取得できませんでした",
+            "https://yuukitoriyama.github.io/geolonia-japanese-addresses-accompanist/%E5%A4%A7%E9%98%AA%E9%83%BD/master.jsonを取得できませんでした",
         );
     }
 }
@@ -0,0 +1,88 @@
+use crate::domain::geolonia::error::Error;
+use crate::domain::postal_code::entity::{PostalCodeEntry, PostalCodeMaster};
+use crate::http::client::ApiClient;
+use crate::http::endpoints::encode_path_segment;
+
+#[derive(Clone)]
+pub struct PostalCodeRepository<C: ApiClient> {
+    pub api_client: C,
+    pub base_url: String,
+}
+
+impl<C: ApiClient> PostalCodeRepository<C> {
+    /// `postal_code`は`NNN-NNNN`形式であること
+    pub async fn get(&self, postal_code: &str) -> Result<Vec<PostalCodeEntry>, Error> {
+        self.api_client
+            .fetch::<PostalCodeMaster>(&endpoint(&self.base_url, postal_code))
+            .await
+            .map(PostalCodeMaster::into_entries)
+            .map_err(|e| e.into())
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn get_blocking(&self, postal_code: &str) -> Result<Vec<PostalCodeEntry>, Error> {
+        self.api_client
+            .fetch_blocking::<PostalCodeMaster>(&endpoint(&self.base_url, postal_code))
+            .map(PostalCodeMaster::into_entries)
+            .map_err(|e| e.into())
+    }
+}
+
+fn endpoint(base_url: &str, postal_code: &str) -> String {
+    let (upper, lower) = postal_code.split_once('-').unwrap_or(("", postal_code));
+    format!(
+        "{}/{}/{}.json",
+        base_url,
+        encode_path_segment(upper),
+        encode_path_segment(lower)
+    )
+}
+
+#[cfg(all(test, not(feature = "blocking")))]
+mod tests {
+    use crate::http::endpoints::Endpoints;
+    use crate::http::reqwest_client::ReqwestApiClient;
+    use crate::repository::postal_code::PostalCodeRepository;
+
+    #[tokio::test]
+    async fn 非同期_東京都千代田区千代田_成功() {
+        let repository = PostalCodeRepository {
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().postal_code,
+        };
+        let result = repository.get("100-0001").await;
+        let entries = result.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].postal_code, "100-0001");
+        assert_eq!(entries[0].prefecture, "東京都");
+        assert_eq!(entries[0].city, "千代田区");
+        assert_eq!(entries[0].town, "千代田");
+    }
+
+    #[tokio::test]
+    async fn 非同期_存在しない郵便番号_失敗() {
+        let repository = PostalCodeRepository {
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().postal_code,
+        };
+        let result = repository.get("000-0000").await;
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod blocking_tests {
+    use crate::http::endpoints::Endpoints;
+    use crate::http::reqwest_client::ReqwestApiClient;
+    use crate::repository::postal_code::PostalCodeRepository;
+
+    #[test]
+    fn 同期_東京都千代田区千代田_成功() {
+        let repository = PostalCodeRepository {
+            api_client: ReqwestApiClient::default(),
+            base_url: Endpoints::default().postal_code,
+        };
+        let entries = repository.get_blocking("100-0001").unwrap();
+        assert_eq!(entries[0].city, "千代田区");
+    }
+}
@@ -0,0 +1,499 @@
+//! Find the nearest towns from a coordinate
+//!
+//! 住所マスタに含まれる町の代表点から空間インデックスを構築し、緯度経度に最も近い町を検索します。
+//! [LocalDirectoryApiClient](crate::http::local_directory_client::LocalDirectoryApiClient)で
+//! 保存済みのスナップショットを読み込めば、ネットワークに接続せずに逆ジオコーディングを行えます。
+//!
+//! # Example
+//! ```no_run
+//! use japanese_address_parser::http::local_directory_client::LocalDirectoryApiClient;
+//! use japanese_address_parser::reverse_geocoder::ReverseGeocoder;
+//!
+//! async fn example() {
+//!     let client = LocalDirectoryApiClient::with_root("./snapshots/geolonia-1700000000");
+//!     let reverse_geocoder = ReverseGeocoder::from_geolonia(client).await.unwrap();
+//!     for town in reverse_geocoder.nearest(35.6812, 139.7671, 3) {
+//!         println!("{}{}{} ({:.0}m)", town.address.prefecture, town.address.city, town.address.town, town.distance);
+//!     }
+//! }
+//! ```
+
+mod kd_tree;
+
+#[cfg(feature = "experimental")]
+use crate::domain::chimei_ruiju::error::ApiError;
+use crate::domain::geolonia::entity::{Address, Coordinate, Precision, Town};
+use crate::domain::geolonia::error::Error;
+use crate::http::client::ApiClient;
+#[cfg(feature = "experimental")]
+use crate::interactor::chimei_ruiju::{ChimeiRuijuInteractor, ChimeiRuijuInteractorImpl};
+use crate::interactor::geolonia::{GeoloniaInteractor, GeoloniaInteractorImpl};
+use crate::reverse_geocoder::kd_tree::{KdTree, Point};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::Serialize;
+
+/// 地球の平均半径(メートル)
+const EARTH_RADIUS: f64 = 6_371_008.8;
+/// 住所マスタを同時に取得する数
+const MAX_CONCURRENCY: usize = 8;
+
+/// A town found near the given coordinate
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct NearbyTown {
+    /// 町の住所(`rest`は常に空文字)
+    ///
+    /// 住所マスタに小字・通称名ごとの代表点が登録されている場合は、`koaza`も設定されます。
+    pub address: Address,
+    /// 町の代表点
+    ///
+    /// 町の代表点が得られず市区町村の代表点で代替した場合、`precision`は`Precision::City`になります。
+    pub coordinate: Coordinate,
+    /// 検索した地点からの距離(メートル)
+    pub distance: f64,
+}
+
+/// Reverse geocoder backed by a spatial index of representative points
+pub struct ReverseGeocoder {
+    index: KdTree<(Address, Coordinate)>,
+}
+
+impl ReverseGeocoder {
+    fn from_points(points: Vec<(Address, Coordinate)>) -> Self {
+        let nodes = points
+            .into_iter()
+            .map(|point| (to_point(point.1.latitude, point.1.longitude), point))
+            .collect();
+        Self {
+            index: KdTree::new(nodes),
+        }
+    }
+
+    /// Builds a reverse geocoder from the Geolonia master data
+    ///
+    /// 47都道府県すべての市区町村マスタを読み込み、代表点が登録されている町から空間インデックスを構築します。
+    /// 小字・通称名ごとに代表点が登録されている町は、小字・通称名ごとに検索結果に含まれます。
+    ///
+    /// 住所マスタは最大8件ずつ並行して取得します。
+    pub async fn from_geolonia<C: ApiClient + Clone>(api_client: C) -> Result<Self, Error> {
+        let interactor = &GeoloniaInteractorImpl::new(api_client);
+        let prefecture_masters: Vec<_> = stream::iter(jisx0401::Prefecture::values())
+            .map(|prefecture| async move {
+                let prefecture_name = prefecture.name_ja();
+                let prefecture_master = interactor.get_prefecture_master(prefecture_name).await?;
+                Ok::<_, Error>((prefecture_name, prefecture_master))
+            })
+            .buffered(MAX_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        let cities = prefecture_masters
+            .iter()
+            .flat_map(|(prefecture_name, prefecture_master)| {
+                prefecture_master
+                    .cities
+                    .iter()
+                    .map(move |city_name| (*prefecture_name, city_name))
+            });
+        let points: Vec<_> = stream::iter(cities)
+            .map(|(prefecture_name, city_name)| async move {
+                let city = interactor
+                    .get_city_master(prefecture_name, city_name)
+                    .await?;
+                Ok::<_, Error>(town_points(prefecture_name, city_name, city.towns))
+            })
+            .buffered(MAX_CONCURRENCY)
+            .try_collect()
+            .await?;
+        Ok(Self::from_points(points.into_iter().flatten().collect()))
+    }
+
+    /// Builds a reverse geocoder from the Geolonia master data synchronously
+    ///
+    /// 住所マスタは最大8件ずつ、スレッドを用いて並行して取得します。
+    #[cfg(feature = "blocking")]
+    pub fn from_geolonia_blocking<C: ApiClient + Clone + Sync>(
+        api_client: C,
+    ) -> Result<Self, Error> {
+        let interactor = &GeoloniaInteractorImpl::new(api_client);
+        let prefectures: Vec<_> = jisx0401::Prefecture::values().collect();
+        let prefecture_masters = map_concurrently(&prefectures, |prefecture| {
+            let prefecture_name = prefecture.name_ja();
+            let prefecture_master = interactor.get_blocking_prefecture_master(prefecture_name)?;
+            Ok::<_, Error>((prefecture_name, prefecture_master))
+        })?;
+
+        let cities: Vec<_> = prefecture_masters
+            .iter()
+            .flat_map(|(prefecture_name, prefecture_master)| {
+                prefecture_master
+                    .cities
+                    .iter()
+                    .map(move |city_name| (*prefecture_name, city_name))
+            })
+            .collect();
+        let points = map_concurrently(&cities, |(prefecture_name, city_name)| {
+            let city = interactor.get_blocking_city_master(prefecture_name, city_name)?;
+            Ok::<_, Error>(town_points(prefecture_name, city_name, city.towns))
+        })?;
+        Ok(Self::from_points(points.into_iter().flatten().collect()))
+    }
+
+    /// Builds a reverse geocoder from the ChimeiRuiju master data
+    ///
+    /// 町名マスタが取得できた町はその代表点を、一つも取得できなかった市区町村は市区町村マスタの代表点を使用します。
+    /// スナップショットには町名マスタが含まれないため、その場合は市区町村単位の検索になります。
+    /// 町名マスタが存在しない場合以外の取得エラーは、そのまま返します。
+    ///
+    /// 住所マスタは最大8件ずつ並行して取得します。
+    #[cfg(feature = "experimental")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "experimental")))]
+    pub async fn from_chimei_ruiju<C: ApiClient + Clone>(api_client: C) -> Result<Self, ApiError> {
+        let interactor = &ChimeiRuijuInteractorImpl::new(api_client);
+        let prefecture_masters: Vec<_> = stream::iter(jisx0401::Prefecture::values())
+            .map(|prefecture| async move {
+                let prefecture_master = interactor.get_prefecture_master(prefecture).await?;
+                Ok::<_, ApiError>((prefecture, prefecture_master))
+            })
+            .buffered(MAX_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        let cities = prefecture_masters
+            .iter()
+            .flat_map(|(prefecture, prefecture_master)| {
+                prefecture_master
+                    .cities
+                    .iter()
+                    .map(move |city_name| (*prefecture, &prefecture_master.name, city_name))
+            });
+        let city_masters: Vec<_> = stream::iter(cities)
+            .map(|(prefecture, prefecture_name, city_name)| async move {
+                let city_master = interactor.get_city_master(prefecture, city_name).await?;
+                Ok::<_, ApiError>((prefecture, prefecture_name, city_name, city_master))
+            })
+            .buffered(MAX_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        let towns = city_masters.iter().enumerate().flat_map(
+            |(index, (prefecture, _, city_name, city_master))| {
+                city_master
+                    .towns
+                    .iter()
+                    .map(move |town_name| (index, *prefecture, *city_name, town_name))
+            },
+        );
+        let town_coordinates: Vec<_> = stream::iter(towns)
+            .map(|(index, prefecture, city_name, town_name)| async move {
+                match interactor
+                    .get_town_master(prefecture, city_name, town_name)
+                    .await
+                {
+                    Ok(town_master) => Ok(Some((index, town_name, town_master.coordinate))),
+                    // 町名マスタが存在しない町は、市区町村の代表点で代替する
+                    Err(ApiError::NotFound { .. }) => Ok(None),
+                    Err(error) => Err(error),
+                }
+            })
+            .buffered(MAX_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        let mut found = vec![false; city_masters.len()];
+        let mut points = vec![];
+        for (index, town_name, coordinate) in town_coordinates.into_iter().flatten() {
+            let (_, prefecture_name, city_name, _) = city_masters[index];
+            found[index] = true;
+            points.push((
+                Address::new(prefecture_name, city_name, town_name, ""),
+                Coordinate {
+                    latitude: coordinate.latitude,
+                    longitude: coordinate.longitude,
+                    precision: Precision::Town,
+                },
+            ));
+        }
+        for (index, (_, prefecture_name, city_name, city_master)) in city_masters.iter().enumerate()
+        {
+            if !found[index] {
+                points.push((
+                    Address::new(prefecture_name, city_name, "", ""),
+                    Coordinate {
+                        latitude: city_master.coordinate.latitude,
+                        longitude: city_master.coordinate.longitude,
+                        precision: Precision::City,
+                    },
+                ));
+            }
+        }
+        Ok(Self::from_points(points))
+    }
+
+    /// Returns up to `count` towns nearest to the given coordinate, closest first
+    pub fn nearest(&self, latitude: f64, longitude: f64, count: usize) -> Vec<NearbyTown> {
+        self.index
+            .nearest(&to_point(latitude, longitude), count)
+            .into_iter()
+            .map(|(squared_chord, (address, coordinate))| NearbyTown {
+                address: address.clone(),
+                coordinate: *coordinate,
+                distance: chord_to_distance(squared_chord),
+            })
+            .collect()
+    }
+
+    /// Returns the number of indexed representative points
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns `true` if no representative points are indexed
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// 町名マスタのうち、代表点が登録されている町を空間インデックスに登録する点に変換する
+fn town_points(
+    prefecture_name: &str,
+    city_name: &str,
+    towns: Vec<Town>,
+) -> Vec<(Address, Coordinate)> {
+    towns
+        .into_iter()
+        .filter_map(|town| {
+            let (Some(lat), Some(lng)) = (town.lat, town.lng) else {
+                return None;
+            };
+            let mut address = Address::new(prefecture_name, city_name, &town.name, "");
+            address.koaza = town.koaza;
+            Some((
+                address,
+                Coordinate {
+                    latitude: lat as f64,
+                    longitude: lng as f64,
+                    precision: Precision::Town,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// `items`のそれぞれについて`f`を最大`MAX_CONCURRENCY`件ずつ別のスレッドで実行し、入力の順に結果を返す
+///
+/// いずれかが失敗した場合は、その時点で最初のエラーを返す。
+#[cfg(feature = "blocking")]
+fn map_concurrently<T: Sync, R: Send, E: Send>(
+    items: &[T],
+    f: impl Fn(&T) -> Result<R, E> + Sync,
+) -> Result<Vec<R>, E> {
+    let f = &f;
+    let mut results = Vec::with_capacity(items.len());
+    for chunk in items.chunks(MAX_CONCURRENCY) {
+        let chunk_results: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = chunk
+                .iter()
+                .map(|item| scope.spawn(move || f(item)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("fetching thread panicked"))
+                .collect()
+        });
+        for result in chunk_results {
+            results.push(result?);
+        }
+    }
+    Ok(results)
+}
+
+/// 緯度経度を単位球面上の3次元直交座標に変換する
+///
+/// 直交座標上のユークリッド距離(弦の長さ)は大円距離に対して単調増加するため、
+/// k-d木で最近傍を求めた結果は大円距離での最近傍と一致する。
+fn to_point(latitude: f64, longitude: f64) -> Point {
+    let (latitude, longitude) = (latitude.to_radians(), longitude.to_radians());
+    [
+        latitude.cos() * longitude.cos(),
+        latitude.cos() * longitude.sin(),
+        latitude.sin(),
+    ]
+}
+
+/// 単位球面上の弦の長さの2乗を大円距離(メートル)に変換する
+fn chord_to_distance(squared_chord: f64) -> f64 {
+    2.0 * EARTH_RADIUS * (squared_chord.sqrt() / 2.0).min(1.0).asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::geolonia::entity::{Address, Coordinate, Precision};
+    use crate::reverse_geocoder::{chord_to_distance, to_point, ReverseGeocoder};
+
+    fn point(town: &str, latitude: f64, longitude: f64) -> (Address, Coordinate) {
+        (
+            Address::new("東京都", "千代田区", town, ""),
+            Coordinate {
+                latitude,
+                longitude,
+                precision: Precision::Town,
+            },
+        )
+    }
+
+    #[test]
+    fn nearest_近い順に返す() {
+        let reverse_geocoder = ReverseGeocoder::from_points(vec![
+            point("丸の内一丁目", 35.68156, 139.767201),
+            point("千代田", 35.68391, 139.754282),
+            point("神田神保町一丁目", 35.695932, 139.760035),
+        ]);
+        let result = reverse_geocoder.nearest(35.6812, 139.7671, 2);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].address.town, "丸の内一丁目");
+        assert_eq!(result[0].address.city, "千代田区");
+        assert_eq!(result[0].address.rest, "");
+        assert!(result[0].distance < 50.0);
+        assert_eq!(result[1].address.town, "千代田");
+        assert!(result[0].distance < result[1].distance);
+    }
+
+    #[test]
+    fn nearest_空の場合() {
+        let reverse_geocoder = ReverseGeocoder::from_points(vec![]);
+        assert!(reverse_geocoder.is_empty());
+        assert!(reverse_geocoder.nearest(35.6812, 139.7671, 1).is_empty());
+    }
+
+    #[test]
+    fn chord_to_distance_大円距離を返す() {
+        // 東京駅と大阪駅の大円距離は約403km
+        let (a, b) = (to_point(35.6812, 139.7671), to_point(34.7025, 135.4959));
+        let squared_chord: f64 = a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum();
+        let distance = chord_to_distance(squared_chord);
+        assert!((distance - 403_000.0).abs() < 2_000.0, "{}", distance);
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod async_tests {
+    #[cfg(feature = "experimental")]
+    use crate::domain::chimei_ruiju::error::ApiError;
+    use crate::domain::geolonia::entity::Precision;
+    use crate::http::mock_client::MockApiClient;
+    use crate::reverse_geocoder::ReverseGeocoder;
+
+    /// Geolonia住所データのモック
+    ///
+    /// 全ての都道府県に`中央区`のみが存在し、東京都中央区以外の町名マスタは空として扱う。
+    fn geolonia_client() -> MockApiClient {
+        MockApiClient::with_responses([
+            ("master.json", r#"{"name": "", "cities": ["中央区"]}"#),
+            (
+                "東京都/中央区.json",
+                r#"[
+                    {"town": "銀座一丁目", "koaza": "", "lat": 35.674845, "lng": 139.770348},
+                    {"town": "日本橋一丁目", "koaza": "", "lat": 35.682516, "lng": 139.775036},
+                    {"town": "晴海一丁目", "koaza": "", "lat": null, "lng": null},
+                    {"town": "佃一丁目", "koaza": "北", "lat": 35.668, "lng": 139.784},
+                    {"town": "佃一丁目", "koaza": "南", "lat": 35.666, "lng": 139.785}
+                ]"#,
+            ),
+            (".json", "[]"),
+        ])
+    }
+
+    #[tokio::test]
+    async fn from_geolonia_代表点のある町から構築する() {
+        let reverse_geocoder = ReverseGeocoder::from_geolonia(geolonia_client())
+            .await
+            .unwrap();
+        assert_eq!(reverse_geocoder.len(), 4);
+        let result = reverse_geocoder.nearest(35.6812, 139.7671, 1);
+        assert_eq!(result[0].address.prefecture, "東京都");
+        assert_eq!(result[0].address.city, "中央区");
+        assert_eq!(result[0].address.town, "日本橋一丁目");
+        assert_eq!(result[0].address.koaza, "");
+        assert_eq!(result[0].coordinate.precision, Precision::Town);
+    }
+
+    #[tokio::test]
+    async fn from_geolonia_小字ごとの代表点を区別する() {
+        let reverse_geocoder = ReverseGeocoder::from_geolonia(geolonia_client())
+            .await
+            .unwrap();
+        let result = reverse_geocoder.nearest(35.668, 139.784, 2);
+        assert_eq!(result[0].address.town, "佃一丁目");
+        assert_eq!(result[0].address.koaza, "北");
+        assert_eq!(result[1].address.town, "佃一丁目");
+        assert_eq!(result[1].address.koaza, "南");
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn from_geolonia_blocking_非同期の場合と同じ代表点から構築する() {
+        let reverse_geocoder = ReverseGeocoder::from_geolonia_blocking(geolonia_client()).unwrap();
+        assert_eq!(reverse_geocoder.len(), 4);
+        let result = reverse_geocoder.nearest(35.6812, 139.7671, 1);
+        assert_eq!(result[0].address.town, "日本橋一丁目");
+    }
+
+    /// ChimeiRuiju住所データのモック
+    ///
+    /// 東京都千代田区の`丸の内一丁目`と中央区の`銀座一丁目`の町名マスタは存在しないものとして扱う。
+    #[cfg(feature = "experimental")]
+    fn chimei_ruiju_client() -> MockApiClient {
+        MockApiClient::with_responses([
+            (
+                "://tokyo.chimei-ruiju.org/master.json",
+                r#"{"name": "東京都", "cities": ["千代田区", "中央区"],
+                    "coordinate": {"latitude": 35.689501, "longitude": 139.691722}}"#,
+            ),
+            (
+                ".chimei-ruiju.org/master.json",
+                r#"{"name": "", "cities": [], "coordinate": {"latitude": 0.0, "longitude": 0.0}}"#,
+            ),
+            (
+                "/千代田区/master.json",
+                r#"{"name": "千代田区", "towns": ["千代田", "丸の内一丁目"],
+                    "coordinate": {"latitude": 35.694003, "longitude": 139.753634}}"#,
+            ),
+            (
+                "/中央区/master.json",
+                r#"{"name": "中央区", "towns": ["銀座一丁目"],
+                    "coordinate": {"latitude": 35.670651, "longitude": 139.771861}}"#,
+            ),
+            (
+                "/千代田区/千代田/master.json",
+                r#"{"name": "千代田", "blocks": [],
+                    "coordinate": {"latitude": 35.68391, "longitude": 139.754282}}"#,
+            ),
+        ])
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "experimental")]
+    async fn from_chimei_ruiju_町名マスタが存在しない市区町村は代表点で代替する() {
+        let reverse_geocoder = ReverseGeocoder::from_chimei_ruiju(chimei_ruiju_client())
+            .await
+            .unwrap();
+        assert_eq!(reverse_geocoder.len(), 2);
+
+        let result = reverse_geocoder.nearest(35.68391, 139.754282, 2);
+        assert_eq!(result[0].address.city, "千代田区");
+        assert_eq!(result[0].address.town, "千代田");
+        assert_eq!(result[0].coordinate.precision, Precision::Town);
+        assert_eq!(result[1].address.city, "中央区");
+        assert_eq!(result[1].address.town, "");
+        assert_eq!(result[1].coordinate.precision, Precision::City);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "experimental")]
+    async fn from_chimei_ruiju_通信エラーの場合はエラーを返す() {
+        // 存在しない町名マスタを取得した場合に、404ではなく通信エラーを返す
+        let client = chimei_ruiju_client().network_error();
+        let result = ReverseGeocoder::from_chimei_ruiju(client).await;
+        assert!(matches!(result, Err(ApiError::Network { .. })));
+    }
+}
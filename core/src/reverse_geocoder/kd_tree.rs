@@ -0,0 +1,149 @@
+/// 3次元直交座標
+pub(crate) type Point = [f64; 3];
+
+/// 点とその点に紐づく値を格納する静的なk-d木
+///
+/// 構築時に各部分木の中央値が中央に来るよう並べ替えておくことで、木構造を配列上に暗黙的に表現する。
+pub(crate) struct KdTree<T> {
+    nodes: Vec<(Point, T)>,
+}
+
+impl<T> KdTree<T> {
+    pub(crate) fn new(mut nodes: Vec<(Point, T)>) -> Self {
+        build(&mut nodes, 0);
+        Self { nodes }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// `target`に近い順に最大`count`件の(距離の2乗, 値)を返す
+    pub(crate) fn nearest(&self, target: &Point, count: usize) -> Vec<(f64, &T)> {
+        let mut found: Vec<(f64, usize)> = Vec::with_capacity(count + 1);
+        if count > 0 {
+            self.search(0, self.nodes.len(), 0, target, count, &mut found);
+        }
+        found
+            .into_iter()
+            .map(|(squared_distance, index)| (squared_distance, &self.nodes[index].1))
+            .collect()
+    }
+
+    fn search(
+        &self,
+        start: usize,
+        end: usize,
+        depth: usize,
+        target: &Point,
+        count: usize,
+        found: &mut Vec<(f64, usize)>,
+    ) {
+        if start >= end {
+            return;
+        }
+        let middle = start + (end - start) / 2;
+        let (point, _) = &self.nodes[middle];
+        let distance = squared_distance(point, target);
+        if !is_full(found, count, distance) {
+            let position = found.partition_point(|(d, _)| *d <= distance);
+            found.insert(position, (distance, middle));
+            found.truncate(count);
+        }
+
+        let axis = depth % 3;
+        let diff = target[axis] - point[axis];
+        let (near, far) = if diff < 0.0 {
+            ((start, middle), (middle + 1, end))
+        } else {
+            ((middle + 1, end), (start, middle))
+        };
+        self.search(near.0, near.1, depth + 1, target, count, found);
+        // 分割面までの距離が現在の候補より近い場合のみ反対側の部分木を探索する
+        if !is_full(found, count, diff * diff) {
+            self.search(far.0, far.1, depth + 1, target, count, found);
+        }
+    }
+}
+
+fn build<T>(nodes: &mut [(Point, T)], depth: usize) {
+    if nodes.len() <= 1 {
+        return;
+    }
+    let axis = depth % 3;
+    let middle = nodes.len() / 2;
+    nodes.select_nth_unstable_by(middle, |(a, _), (b, _)| a[axis].total_cmp(&b[axis]));
+    let (left, right) = nodes.split_at_mut(middle);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+/// 候補が`count`件集まっており、かつ`distance`が最も遠い候補以上であるかどうか
+fn is_full(found: &[(f64, usize)], count: usize, distance: f64) -> bool {
+    found.len() >= count && found.last().is_some_and(|(d, _)| distance >= *d)
+}
+
+fn squared_distance(a: &Point, b: &Point) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reverse_geocoder::kd_tree::{squared_distance, KdTree, Point};
+
+    /// 再現性のある擬似乱数で点を生成する
+    fn points(count: usize) -> Vec<Point> {
+        let mut seed: u64 = 12345;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..count).map(|_| [next(), next(), next()]).collect()
+    }
+
+    #[test]
+    fn nearest_全探索と同じ結果を返す() {
+        let points = points(500);
+        let tree = KdTree::new(
+            points
+                .iter()
+                .copied()
+                .enumerate()
+                .map(|(i, p)| (p, i))
+                .collect(),
+        );
+        for target in [[0.5, 0.5, 0.5], [0.0, 1.0, 0.2], [1.5, -0.3, 0.9]] {
+            let mut expected: Vec<(f64, usize)> = points
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (squared_distance(p, &target), i))
+                .collect();
+            expected.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let actual: Vec<usize> = tree
+                .nearest(&target, 5)
+                .into_iter()
+                .map(|(_, index)| *index)
+                .collect();
+            let expected: Vec<usize> = expected.into_iter().take(5).map(|(_, i)| i).collect();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn nearest_件数が要素数より多い場合() {
+        let tree = KdTree::new(vec![([0.0, 0.0, 0.0], "a"), ([1.0, 0.0, 0.0], "b")]);
+        let result = tree.nearest(&[0.9, 0.0, 0.0], 10);
+        assert_eq!(result.len(), 2);
+        assert_eq!(*result[0].1, "b");
+        assert_eq!(*result[1].1, "a");
+    }
+
+    #[test]
+    fn nearest_空の場合() {
+        let tree: KdTree<()> = KdTree::new(vec![]);
+        assert!(tree.nearest(&[0.0, 0.0, 0.0], 3).is_empty());
+        assert_eq!(tree.len(), 0);
+    }
+}
@@ -0,0 +1,442 @@
+//! Download master data and save it as a snapshot for offline use
+//!
+//! 47都道府県すべての都道府県マスタ・市区町村マスタを取得し、ローカルディレクトリに保存します。
+//! 保存先のディレクトリは取得日時ごとに作成され、取得日時と各ファイルのチェックサムを記録した`manifest.json`が併せて出力されます。
+//! 保存したディレクトリは[LocalDirectoryApiClient](crate::http::local_directory_client::LocalDirectoryApiClient)でそのまま読み込むことができます。
+//!
+//! # Example
+//! ```no_run
+//! use japanese_address_parser::http::local_directory_client::LocalDirectoryApiClient;
+//! use japanese_address_parser::http::client::ApiClient;
+//! use japanese_address_parser::http::reqwest_client::ReqwestApiClient;
+//! use japanese_address_parser::parser::Parser;
+//! use japanese_address_parser::snapshot::create_geolonia_snapshot;
+//!
+//! async fn example() {
+//!     let snapshot = create_geolonia_snapshot(ReqwestApiClient::new(), "./snapshots")
+//!         .await
+//!         .unwrap();
+//!     let parser = Parser::with_api_client(LocalDirectoryApiClient::with_root(&snapshot.directory));
+//! }
+//! ```
+
+use crate::http::cache::{Revalidation, Validators};
+use crate::http::client::ApiClient;
+use crate::http::error::ApiClientError;
+use crate::http::local_directory_client::{url_to_path, url_to_relative_path};
+#[cfg(feature = "experimental")]
+use crate::interactor::chimei_ruiju::{ChimeiRuijuInteractor, ChimeiRuijuInteractorImpl};
+use crate::interactor::geolonia::{GeoloniaInteractor, GeoloniaInteractorImpl};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// A snapshot of master data saved in a local directory
+#[derive(Debug)]
+pub struct Snapshot {
+    /// スナップショットを保存したディレクトリ
+    pub directory: PathBuf,
+    /// スナップショットのマニフェスト
+    pub manifest: Manifest,
+}
+
+/// Manifest of a snapshot
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Manifest {
+    /// 使用した住所データ
+    pub data_source: String,
+    /// 住所マスタを取得した日時(UNIX時間、秒)
+    pub fetched_at: u64,
+    /// 保存したファイルのリスト
+    pub files: Vec<ManifestEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ManifestEntry {
+    /// スナップショットのディレクトリからの相対パス
+    pub path: String,
+    /// 取得元のURL
+    pub url: String,
+    /// 取得元から受け取ったレスポンスボディのSHA-256ハッシュ値
+    pub sha256: String,
+    /// ファイルサイズ(バイト)
+    pub size: usize,
+}
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("failed to fetch master data: {0}")]
+    Fetch(String),
+    #[error("failed to read or write snapshot: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to serialize or deserialize manifest: {0}")]
+    Manifest(#[from] serde_json::Error),
+    #[error("checksum mismatch: {path}")]
+    ChecksumMismatch { path: String },
+}
+
+impl Snapshot {
+    /// Opens a snapshot saved in the given directory
+    ///
+    /// 保存済みのスナップショットを`manifest.json`から読み込みます。
+    pub fn open<P: AsRef<Path>>(directory: P) -> Result<Self, SnapshotError> {
+        let directory = directory.as_ref().to_path_buf();
+        let bytes = std::fs::read(directory.join(MANIFEST_FILE_NAME))?;
+        let manifest = serde_json::from_slice::<Manifest>(&bytes)?;
+        Ok(Self {
+            directory,
+            manifest,
+        })
+    }
+
+    /// Verifies checksums of all files listed in the manifest
+    ///
+    /// マニフェストに記録されたチェックサムとファイルの内容が一致するか検証します。
+    pub fn verify(&self) -> Result<(), SnapshotError> {
+        for entry in &self.manifest.files {
+            let bytes = std::fs::read(url_to_path(&self.directory, &entry.path))?;
+            if entry.sha256 != sha256(&bytes) {
+                return Err(SnapshotError::ChecksumMismatch {
+                    path: entry.path.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Downloads all prefecture masters and city masters of Geolonia
+///
+/// Geolonia住所データの都道府県マスタ・市区町村マスタをすべて取得し、
+/// `{output_dir}/geolonia-{取得日時}`に保存します。
+///
+/// 保存する内容は`api_client`の[fetch_revalidate](crate::http::client::ApiClient::fetch_revalidate)が返すレスポンスボディです。
+/// 既定の実装を使用する`ApiClient`ではJSONが再シリアライズされ、キーの順序などが取得元と異なります。
+pub async fn create_geolonia_snapshot<C: ApiClient + Send + Sync, P: AsRef<Path>>(
+    api_client: C,
+    output_dir: P,
+) -> Result<Snapshot, SnapshotError> {
+    let fetched_at = now();
+    let directory = output_dir.as_ref().join(format!("geolonia-{}", fetched_at));
+    let client = RecordingApiClient::with_directory(api_client, &directory);
+    let interactor = GeoloniaInteractorImpl::new(client.clone());
+
+    for prefecture in jisx0401::Prefecture::values() {
+        let prefecture_master = interactor
+            .get_prefecture_master(prefecture.name_ja())
+            .await
+            .map_err(|error| SnapshotError::Fetch(error.error_message))?;
+        client.check()?;
+        for city_name in &prefecture_master.cities {
+            interactor
+                .get_city_master(prefecture.name_ja(), city_name)
+                .await
+                .map_err(|error| SnapshotError::Fetch(error.error_message))?;
+            client.check()?;
+        }
+        log::info!("{}の住所マスタを取得しました", prefecture.name_ja());
+    }
+
+    client.finish("geolonia", fetched_at)
+}
+
+/// Downloads all prefecture masters and city masters of ChimeiRuiju
+///
+/// ChimeiRuiju住所データの都道府県マスタ・市区町村マスタをすべて取得し、
+/// `{output_dir}/chimei-ruiju-{取得日時}`に保存します。
+/// 町名マスタは件数が膨大なため保存しません。
+///
+/// 保存する内容は`create_geolonia_snapshot`と同様に`fetch_revalidate`が返すレスポンスボディです。
+#[cfg(feature = "experimental")]
+#[cfg_attr(docsrs, doc(cfg(feature = "experimental")))]
+pub async fn create_chimei_ruiju_snapshot<C: ApiClient + Send + Sync, P: AsRef<Path>>(
+    api_client: C,
+    output_dir: P,
+) -> Result<Snapshot, SnapshotError> {
+    let fetched_at = now();
+    let directory = output_dir
+        .as_ref()
+        .join(format!("chimei-ruiju-{}", fetched_at));
+    let client = RecordingApiClient::with_directory(api_client, &directory);
+    let interactor = ChimeiRuijuInteractorImpl::new(client.clone());
+
+    for prefecture in jisx0401::Prefecture::values() {
+        let prefecture_master = interactor
+            .get_prefecture_master(prefecture)
+            .await
+            .map_err(|error| SnapshotError::Fetch(error.to_string()))?;
+        client.check()?;
+        for city_name in &prefecture_master.cities {
+            interactor
+                .get_city_master(prefecture, city_name)
+                .await
+                .map_err(|error| SnapshotError::Fetch(error.to_string()))?;
+            client.check()?;
+        }
+        log::info!("{}の住所マスタを取得しました", prefecture.name_ja());
+    }
+
+    client.finish("chimei-ruiju", fetched_at)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+struct Recorder {
+    /// 保存先のディレクトリ
+    directory: PathBuf,
+    /// 保存したファイルのリスト
+    entries: Vec<ManifestEntry>,
+    /// ファイルの保存中に発生したエラー
+    error: Option<std::io::Error>,
+}
+
+/// 取得したデータをディレクトリに書き出す`ApiClient`
+struct RecordingApiClient<C: ApiClient> {
+    client: Arc<C>,
+    recorder: Arc<Mutex<Recorder>>,
+}
+
+impl<C: ApiClient> Clone for RecordingApiClient<C> {
+    fn clone(&self) -> Self {
+        Self {
+            client: Arc::clone(&self.client),
+            recorder: Arc::clone(&self.recorder),
+        }
+    }
+}
+
+impl<C: ApiClient> RecordingApiClient<C> {
+    fn with_directory(client: C, directory: &Path) -> Self {
+        Self {
+            client: Arc::new(client),
+            recorder: Arc::new(Mutex::new(Recorder {
+                directory: directory.to_path_buf(),
+                entries: vec![],
+                error: None,
+            })),
+        }
+    }
+
+    /// 取得元から受け取ったレスポンスボディを加工せずに保存する
+    fn record(&self, url: &str, body: &[u8]) {
+        let mut recorder = self
+            .recorder
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if recorder.error.is_some() {
+            return;
+        }
+
+        let path = url_to_path(&recorder.directory, url);
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, body));
+        match result {
+            Ok(()) => recorder.entries.push(ManifestEntry {
+                path: url_to_relative_path(url),
+                url: url.to_string(),
+                sha256: sha256(body),
+                size: body.len(),
+            }),
+            Err(error) => recorder.error = Some(error),
+        }
+    }
+
+    /// ファイルの保存中にエラーが発生していないか確認する
+    fn check(&self) -> Result<(), SnapshotError> {
+        let mut recorder = self
+            .recorder
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match recorder.error.take() {
+            Some(error) => Err(error.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// マニフェストを書き出してスナップショットを完成させる
+    fn finish(&self, data_source: &str, fetched_at: u64) -> Result<Snapshot, SnapshotError> {
+        let mut recorder = self
+            .recorder
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let manifest = Manifest {
+            data_source: data_source.to_string(),
+            fetched_at,
+            files: std::mem::take(&mut recorder.entries),
+        };
+        std::fs::create_dir_all(&recorder.directory)?;
+        std::fs::write(
+            recorder.directory.join(MANIFEST_FILE_NAME),
+            serde_json::to_vec_pretty(&manifest)?,
+        )?;
+        Ok(Snapshot {
+            directory: recorder.directory.clone(),
+            manifest,
+        })
+    }
+}
+
+impl<C: ApiClient + Send + Sync> ApiClient for RecordingApiClient<C> {
+    fn new() -> Self {
+        Self::with_directory(C::new(), Path::new("."))
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
+        // チェックサムを取得元のファイルと一致させるため、デシリアライズ前のレスポンスボディを保存する
+        let revalidation = self
+            .client
+            .fetch_revalidate(url, &Validators::default())
+            .await?;
+        let body = into_body(url, revalidation)?;
+        self.record(url, &body);
+        deserialize(url, &body)
+    }
+
+    #[cfg(feature = "blocking")]
+    fn fetch_blocking<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApiClientError> {
+        let revalidation = self
+            .client
+            .fetch_revalidate_blocking(url, &Validators::default())?;
+        let body = into_body(url, revalidation)?;
+        self.record(url, &body);
+        deserialize(url, &body)
+    }
+}
+
+fn into_body(url: &str, revalidation: Revalidation) -> Result<Vec<u8>, ApiClientError> {
+    match revalidation {
+        Revalidation::Modified { body, .. } => Ok(body),
+        // 条件付きリクエストを行っていないため、変更がないという応答は返らないはず
+        Revalidation::NotModified => Err(ApiClientError::Request {
+            url: url.to_string(),
+            message: "unexpected 304 Not Modified".to_string(),
+        }),
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(url: &str, body: &[u8]) -> Result<T, ApiClientError> {
+    serde_json::from_slice::<T>(body).map_err(|e| ApiClientError::Deserialize {
+        url: url.to_string(),
+        message: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::http::local_directory_client::LocalDirectoryApiClient;
+    use crate::http::mock_client::MockApiClient;
+    use crate::parser::Parser;
+    use crate::snapshot::{create_geolonia_snapshot, sha256, Snapshot, SnapshotError};
+
+    /// 都道府県マスタのレスポンスボディ
+    ///
+    /// 整形や空白を含め、再シリアライズした場合とは異なるバイト列にしている。
+    const PREFECTURE_MASTER: &str = "{\n  \"name\": \"\",\n  \"cities\": [\"中央区\"]\n}\n";
+    const CITY_MASTER: &str =
+        r#"[{"town": "銀座一丁目", "koaza": "", "lat": 35.674845, "lng": 139.770348}]"#;
+
+    fn mock_client() -> MockApiClient {
+        MockApiClient::with_responses([("master.json", PREFECTURE_MASTER), (".json", CITY_MASTER)])
+    }
+
+    fn output_dir(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "japanese-address-parser-snapshot-{}-{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[tokio::test]
+    async fn 全都道府県の住所マスタを保存すること() {
+        let output_dir = output_dir("create");
+        let snapshot = create_geolonia_snapshot(mock_client(), &output_dir)
+            .await
+            .unwrap();
+        assert_eq!(snapshot.manifest.data_source, "geolonia");
+        // 47都道府県それぞれについて、都道府県マスタ1件と市区町村マスタ1件
+        assert_eq!(snapshot.manifest.files.len(), 47 * 2);
+        assert!(snapshot.directory.join("manifest.json").exists());
+
+        let opened = Snapshot::open(&snapshot.directory).unwrap();
+        assert_eq!(opened.manifest, snapshot.manifest);
+        assert!(opened.verify().is_ok());
+
+        std::fs::remove_dir_all(output_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn 取得元のレスポンスボディをそのまま保存すること() {
+        let output_dir = output_dir("raw");
+        let snapshot = create_geolonia_snapshot(mock_client(), &output_dir)
+            .await
+            .unwrap();
+
+        let entry = snapshot
+            .manifest
+            .files
+            .iter()
+            .find(|entry| entry.path.ends_with("東京都/master.json"))
+            .unwrap();
+        let bytes = std::fs::read(snapshot.directory.join(&entry.path)).unwrap();
+        assert_eq!(bytes, PREFECTURE_MASTER.as_bytes());
+        assert_eq!(entry.sha256, sha256(PREFECTURE_MASTER.as_bytes()));
+        assert_eq!(entry.size, PREFECTURE_MASTER.len());
+
+        std::fs::remove_dir_all(output_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn 保存したスナップショットでオフラインのパースができること() {
+        let output_dir = output_dir("parse");
+        let snapshot = create_geolonia_snapshot(mock_client(), &output_dir)
+            .await
+            .unwrap();
+
+        let parser =
+            Parser::with_api_client(LocalDirectoryApiClient::with_root(&snapshot.directory));
+        let result = parser.parse("東京都中央区銀座1-2-3").await;
+        assert_eq!(result.address.prefecture, "東京都");
+        assert_eq!(result.address.city, "中央区");
+        assert_eq!(result.address.town, "銀座一丁目");
+        assert_eq!(result.address.rest, "2-3");
+        assert_eq!(result.error, None);
+
+        std::fs::remove_dir_all(output_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn ファイルが改変された場合_検証に失敗すること() {
+        let output_dir = output_dir("verify");
+        let snapshot = create_geolonia_snapshot(mock_client(), &output_dir)
+            .await
+            .unwrap();
+        let tampered = &snapshot.manifest.files[0];
+        std::fs::write(snapshot.directory.join(&tampered.path), "{}").unwrap();
+
+        let result = snapshot.verify();
+        assert!(matches!(
+            result,
+            Err(SnapshotError::ChecksumMismatch { path }) if path == tampered.path
+        ));
+
+        std::fs::remove_dir_all(output_dir).unwrap();
+    }
+}
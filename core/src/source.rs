@@ -0,0 +1,167 @@
+pub mod embedded;
+
+use crate::domain::geolonia::entity::Town;
+use crate::domain::geolonia::error::Error;
+use crate::http::client::ApiClient;
+use crate::http::endpoints::Endpoints;
+use crate::interactor::geolonia::{GeoloniaInteractor, GeoloniaInteractorImpl};
+
+/// Source of the address master data used by `Parser`
+///
+/// `Parser`が参照する住所マスタの取得元はこのトレイトを実装する必要があります。
+/// `ApiClient`と異なりURLを介さずに都道府県名・市区町村名を受け取るため、
+/// SQLiteなどのデータベースやメモリ上のデータ、独自形式のファイルから住所マスタを読み込む場合に適しています。
+///
+/// 取得に失敗した場合は`ApiClientError`から変換した`Error`を返してください。
+/// `url`には取得元を識別できる文字列を設定します。
+///
+/// # Example
+/// ```
+/// use japanese_address_parser::domain::geolonia::entity::Town;
+/// use japanese_address_parser::domain::geolonia::error::Error;
+/// use japanese_address_parser::http::error::ApiClientError;
+/// use japanese_address_parser::parser::Parser;
+/// use japanese_address_parser::source::AddressMasterSource;
+/// use std::collections::HashMap;
+///
+/// struct InMemorySource {
+///     cities: HashMap<String, Vec<String>>,
+///     towns: HashMap<(String, String), Vec<Town>>,
+/// }
+///
+/// impl InMemorySource {
+///     fn not_found(key: &str) -> Error {
+///         ApiClientError::Request {
+///             url: format!("memory://{}", key),
+///             message: "not found".to_string(),
+///         }
+///         .into()
+///     }
+/// }
+///
+/// impl AddressMasterSource for InMemorySource {
+///     async fn prefecture(&self, prefecture_name: &str) -> Result<Vec<String>, Error> {
+///         self.cities
+///             .get(prefecture_name)
+///             .cloned()
+///             .ok_or_else(|| Self::not_found(prefecture_name))
+///     }
+///
+///     async fn city(&self, prefecture_name: &str, city_name: &str) -> Result<Vec<Town>, Error> {
+///         let key = (prefecture_name.to_string(), city_name.to_string());
+///         self.towns
+///             .get(&key)
+///             .cloned()
+///             .ok_or_else(|| Self::not_found(&format!("{}/{}", prefecture_name, city_name)))
+///     }
+/// #
+/// #   #[cfg(feature = "blocking")]
+/// #   fn prefecture_blocking(&self, prefecture_name: &str) -> Result<Vec<String>, Error> {
+/// #       unimplemented!()
+/// #   }
+/// #
+/// #   #[cfg(feature = "blocking")]
+/// #   fn city_blocking(&self, prefecture_name: &str, city_name: &str) -> Result<Vec<Town>, Error> {
+/// #       unimplemented!()
+/// #   }
+/// }
+///
+/// async fn example(source: InMemorySource) {
+///     let parser = Parser::with_source(source);
+///     let result = parser.parse("東京都千代田区千代田1-1").await;
+///     println!("{:?}", result);
+/// }
+/// ```
+#[cfg_attr(not(target_arch = "wasm32"), trait_variant::make(Send))]
+pub trait AddressMasterSource {
+    /// Returns the names of the cities in the given prefecture asynchronously
+    ///
+    /// 指定した都道府県に属する市区町村名のリストを非同期的に取得する処理を実装します。
+    async fn prefecture(&self, prefecture_name: &str) -> Result<Vec<String>, Error>;
+
+    /// Returns the towns in the given city asynchronously
+    ///
+    /// 指定した市区町村に属する町名マスタを非同期的に取得する処理を実装します。
+    async fn city(&self, prefecture_name: &str, city_name: &str) -> Result<Vec<Town>, Error>;
+
+    /// Returns the names of the cities in the given prefecture synchronously
+    ///
+    /// 指定した都道府県に属する市区町村名のリストを同期的に取得する処理を実装します。
+    #[cfg(feature = "blocking")]
+    fn prefecture_blocking(&self, prefecture_name: &str) -> Result<Vec<String>, Error>;
+
+    /// Returns the towns in the given city synchronously
+    ///
+    /// 指定した市区町村に属する町名マスタを同期的に取得する処理を実装します。
+    #[cfg(feature = "blocking")]
+    fn city_blocking(&self, prefecture_name: &str, city_name: &str) -> Result<Vec<Town>, Error>;
+}
+
+/// An implementation of `AddressMasterSource` that fetches master data through `ApiClient`
+///
+/// `Parser`が標準で使用する取得元です。Geoloniaの住所データをURLを指定して`ApiClient`から取得します。
+#[derive(Clone)]
+pub struct ApiClientSource<Client: ApiClient> {
+    pub(crate) interactor: GeoloniaInteractorImpl<Client>,
+}
+
+impl<Client: ApiClient> Default for ApiClientSource<Client> {
+    fn default() -> Self {
+        Self {
+            interactor: Default::default(),
+        }
+    }
+}
+
+impl<Client: ApiClient + Clone> ApiClientSource<Client> {
+    /// Constructs a new `ApiClientSource` with the given `ApiClient`.
+    pub fn new(api_client: Client) -> Self {
+        Self {
+            interactor: GeoloniaInteractorImpl::new(api_client),
+        }
+    }
+}
+
+impl<Client: ApiClient> ApiClientSource<Client> {
+    /// Sets the base URLs of the master data.
+    ///
+    /// `Endpoints`のうち、Geoloniaの住所マスタの取得先を使用します。
+    pub fn endpoints(mut self, endpoints: &Endpoints) -> Self {
+        self.interactor.set_endpoints(endpoints);
+        self
+    }
+}
+
+impl<Client: ApiClient + Sync> AddressMasterSource for ApiClientSource<Client> {
+    async fn prefecture(&self, prefecture_name: &str) -> Result<Vec<String>, Error> {
+        let prefecture = self
+            .interactor
+            .get_prefecture_master(prefecture_name)
+            .await?;
+        Ok(prefecture.cities)
+    }
+
+    async fn city(&self, prefecture_name: &str, city_name: &str) -> Result<Vec<Town>, Error> {
+        let city = self
+            .interactor
+            .get_city_master(prefecture_name, city_name)
+            .await?;
+        Ok(city.towns)
+    }
+
+    #[cfg(feature = "blocking")]
+    fn prefecture_blocking(&self, prefecture_name: &str) -> Result<Vec<String>, Error> {
+        let prefecture = self
+            .interactor
+            .get_blocking_prefecture_master(prefecture_name)?;
+        Ok(prefecture.cities)
+    }
+
+    #[cfg(feature = "blocking")]
+    fn city_blocking(&self, prefecture_name: &str, city_name: &str) -> Result<Vec<Town>, Error> {
+        let city = self
+            .interactor
+            .get_blocking_city_master(prefecture_name, city_name)?;
+        Ok(city.towns)
+    }
+}
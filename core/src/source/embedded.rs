@@ -0,0 +1,527 @@
+//! Compact master data that can be embedded into the binary
+//!
+//! 都道府県→市区町村→町名の住所マスタを、バイナリに埋め込める形式に変換して読み込みます。
+//! 埋め込んだ住所マスタを使用すると、通信を行わずに住所をパースできます。
+//!
+//! データは次の手順で用意します。
+//!
+//! 1. `snapshot::create_geolonia_snapshot`で住所マスタのスナップショットを保存する
+//! 2. `generate`でスナップショットから埋め込み用のデータを生成し、ファイルに書き出す
+//!    (`cargo run --example generate_embedded_master -- {スナップショット} {出力先}`でも生成できます)
+//! 3. 環境変数`JAPANESE_ADDRESS_PARSER_EMBEDDED_MASTER`に生成したファイルのパスを指定し、
+//!    `embedded-master`フィーチャーを有効にしてビルドする
+//!
+//! ビルドしたバイナリでは`Parser::embedded()`で埋め込んだ住所マスタを使用できます。
+//! 生成したデータを実行時に読み込む場合は`EmbeddedSource::from_bytes`を使用してください。
+//!
+//! # Format
+//! 町名は市区町村ごとに、直前の町名と共通する先頭部分を省いて(front coding)保存します。
+//! 市区町村ごとの町名の位置は先頭の索引に記録されるため、読み込み時には索引のみを展開し、
+//! 町名は市区町村の住所マスタを参照したときに展開します。
+
+use crate::domain::geolonia::entity::Town;
+use crate::domain::geolonia::error::Error;
+use crate::http::error::ApiClientError;
+use crate::source::AddressMasterSource;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// データの先頭に置く識別子
+const MAGIC: &[u8; 4] = b"JAPM";
+/// 形式のバージョン
+const VERSION: u8 = 1;
+
+const HAS_LAT: u8 = 0b01;
+const HAS_LNG: u8 = 0b10;
+
+/// 都道府県名と、市区町村名とその町名の一覧
+type Prefecture = (String, Vec<(String, Vec<Town>)>);
+
+/// Error while loading embedded master data
+#[derive(Error, Debug, PartialEq)]
+pub enum EmbeddedError {
+    /// 識別子またはバージョンが一致しない
+    #[error("unsupported embedded master data")]
+    UnsupportedFormat,
+    /// データが途中で切れている、または壊れている
+    #[error("corrupted embedded master data at byte {0}")]
+    Corrupted(usize),
+}
+
+/// Address master source backed by embedded master data
+///
+/// `generate`で生成したデータから住所マスタを読み込む`AddressMasterSource`です。
+///
+/// # Example
+/// ```
+/// use japanese_address_parser::parser::Parser;
+/// use japanese_address_parser::source::embedded::EmbeddedSource;
+///
+/// fn example(bytes: Vec<u8>) {
+///     let source = EmbeddedSource::from_bytes(bytes).unwrap();
+///     let parser = Parser::with_source(source);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct EmbeddedSource {
+    data: Cow<'static, [u8]>,
+    /// 都道府県名から、市区町村名と町名の位置のリストを引く索引
+    prefectures: HashMap<String, Vec<(String, usize)>>,
+    /// 町名が始まる位置
+    towns_start: usize,
+}
+
+impl EmbeddedSource {
+    /// Loads master data generated by `generate`.
+    ///
+    /// 索引のみを読み込み、町名は参照したときに展開します。
+    pub fn from_bytes<B: Into<Cow<'static, [u8]>>>(bytes: B) -> Result<Self, EmbeddedError> {
+        let data = bytes.into();
+        let mut reader = Reader::new(&data);
+        if reader.bytes(MAGIC.len())? != MAGIC || reader.byte()? != VERSION {
+            return Err(EmbeddedError::UnsupportedFormat);
+        }
+        let mut prefectures = HashMap::new();
+        for _ in 0..reader.varint()? {
+            let prefecture_name = reader.string()?;
+            let mut cities = vec![];
+            for _ in 0..reader.varint()? {
+                let city_name = reader.string()?;
+                cities.push((city_name, reader.varint()?));
+            }
+            prefectures.insert(prefecture_name, cities);
+        }
+        let towns_start = reader.position;
+        Ok(Self {
+            data,
+            prefectures,
+            towns_start,
+        })
+    }
+
+    /// Master data embedded at build time.
+    ///
+    /// `embedded-master`フィーチャーを有効にする場合は、ビルド時に環境変数`JAPANESE_ADDRESS_PARSER_EMBEDDED_MASTER`で
+    /// 埋め込む住所マスタを指定する必要があります。指定しなかった場合はビルドに失敗します。
+    #[cfg(feature = "embedded-master")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-master")))]
+    pub fn builtin() -> Self {
+        static BUILTIN: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/embedded-master.bin"));
+        Self::from_bytes(BUILTIN).expect("the embedded master data is corrupted")
+    }
+
+    fn city_offset(&self, prefecture_name: &str, city_name: &str) -> Option<usize> {
+        self.prefectures
+            .get(prefecture_name)?
+            .iter()
+            .find(|(name, _)| name == city_name)
+            .map(|(_, offset)| self.towns_start + offset)
+    }
+
+    fn towns(&self, offset: usize) -> Result<Vec<Town>, EmbeddedError> {
+        let mut reader = Reader::new(&self.data);
+        reader.position = offset;
+        let count = reader.varint()?;
+        let mut towns = Vec::with_capacity(count.min(self.data.len()));
+        let mut previous = String::new();
+        for _ in 0..count {
+            let shared = reader.varint()?;
+            let suffix = reader.string()?;
+            let prefix = previous
+                .get(..shared)
+                .ok_or(EmbeddedError::Corrupted(reader.position))?;
+            let name = format!("{}{}", prefix, suffix);
+            let koaza = reader.string()?;
+            let flags = reader.byte()?;
+            let lat = if flags & HAS_LAT != 0 {
+                Some(reader.f32()?)
+            } else {
+                None
+            };
+            let lng = if flags & HAS_LNG != 0 {
+                Some(reader.f32()?)
+            } else {
+                None
+            };
+            previous.clone_from(&name);
+            towns.push(Town {
+                name,
+                koaza,
+                lat,
+                lng,
+            });
+        }
+        Ok(towns)
+    }
+
+    fn prefecture_master(&self, prefecture_name: &str) -> Result<Vec<String>, Error> {
+        let cities = self
+            .prefectures
+            .get(prefecture_name)
+            .ok_or_else(|| not_found(&url(&[prefecture_name])))?;
+        Ok(cities.iter().map(|(name, _)| name.clone()).collect())
+    }
+
+    fn city_master(&self, prefecture_name: &str, city_name: &str) -> Result<Vec<Town>, Error> {
+        let url = url(&[prefecture_name, city_name]);
+        let offset = self
+            .city_offset(prefecture_name, city_name)
+            .ok_or_else(|| not_found(&url))?;
+        self.towns(offset).map_err(|e| {
+            ApiClientError::Deserialize {
+                url,
+                message: e.to_string(),
+            }
+            .into()
+        })
+    }
+}
+
+impl AddressMasterSource for EmbeddedSource {
+    async fn prefecture(&self, prefecture_name: &str) -> Result<Vec<String>, Error> {
+        self.prefecture_master(prefecture_name)
+    }
+
+    async fn city(&self, prefecture_name: &str, city_name: &str) -> Result<Vec<Town>, Error> {
+        self.city_master(prefecture_name, city_name)
+    }
+
+    #[cfg(feature = "blocking")]
+    fn prefecture_blocking(&self, prefecture_name: &str) -> Result<Vec<String>, Error> {
+        self.prefecture_master(prefecture_name)
+    }
+
+    #[cfg(feature = "blocking")]
+    fn city_blocking(&self, prefecture_name: &str, city_name: &str) -> Result<Vec<Town>, Error> {
+        self.city_master(prefecture_name, city_name)
+    }
+}
+
+/// Generates embedded master data from the given source
+///
+/// 47都道府県すべての住所マスタを`source`から読み込み、`EmbeddedSource`で読み込める形式に変換します。
+/// スナップショットから生成する場合は、`LocalDirectoryApiClient`を使用した`ApiClientSource`を渡してください。
+///
+/// # Example
+/// ```no_run
+/// use japanese_address_parser::http::local_directory_client::LocalDirectoryApiClient;
+/// use japanese_address_parser::source::embedded::generate;
+/// use japanese_address_parser::source::ApiClientSource;
+///
+/// async fn example() {
+///     let client = LocalDirectoryApiClient::with_root("./snapshots/geolonia-1700000000");
+///     let bytes = generate(&ApiClientSource::new(client)).await.unwrap();
+///     std::fs::write("./embedded-master.bin", bytes).unwrap();
+/// }
+/// ```
+pub async fn generate<S: AddressMasterSource>(source: &S) -> Result<Vec<u8>, Error> {
+    let mut prefectures = vec![];
+    for prefecture in jisx0401::Prefecture::values() {
+        let prefecture_name = prefecture.name_ja();
+        let mut cities = vec![];
+        for city_name in source.prefecture(prefecture_name).await? {
+            let towns = source.city(prefecture_name, &city_name).await?;
+            cities.push((city_name, towns));
+        }
+        log::info!("{}の住所マスタを変換しました", prefecture_name);
+        prefectures.push((prefecture_name.to_string(), cities));
+    }
+    Ok(encode(&prefectures))
+}
+
+/// 都道府県ごとの市区町村と町名のリストを変換する
+pub(crate) fn encode(prefectures: &[Prefecture]) -> Vec<u8> {
+    // 索引に町名の位置を記録するため、町名を先に書き出す
+    let mut towns_section = vec![];
+    let mut offsets = vec![];
+    for (_, cities) in prefectures {
+        for (_, towns) in cities {
+            offsets.push(towns_section.len());
+            write_varint(&mut towns_section, towns.len());
+            let mut previous = "";
+            for town in towns {
+                let shared = shared_prefix_len(previous, &town.name);
+                write_varint(&mut towns_section, shared);
+                write_string(&mut towns_section, &town.name[shared..]);
+                write_string(&mut towns_section, &town.koaza);
+                let flags = town.lat.map_or(0, |_| HAS_LAT) | town.lng.map_or(0, |_| HAS_LNG);
+                towns_section.push(flags);
+                for value in [town.lat, town.lng].into_iter().flatten() {
+                    towns_section.extend_from_slice(&value.to_le_bytes());
+                }
+                previous = &town.name;
+            }
+        }
+    }
+
+    let mut bytes = MAGIC.to_vec();
+    bytes.push(VERSION);
+    write_varint(&mut bytes, prefectures.len());
+    let mut offsets = offsets.into_iter();
+    for (prefecture_name, cities) in prefectures {
+        write_string(&mut bytes, prefecture_name);
+        write_varint(&mut bytes, cities.len());
+        for (city_name, _) in cities {
+            write_string(&mut bytes, city_name);
+            write_varint(&mut bytes, offsets.next().unwrap_or_default());
+        }
+    }
+    bytes.extend_from_slice(&towns_section);
+    bytes
+}
+
+/// 文字の境界で区切った、共通する先頭部分のバイト数
+fn shared_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or_else(|| a.len().min(b.len()), |((index, _), _)| index)
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn write_string(bytes: &mut Vec<u8>, value: &str) {
+    write_varint(bytes, value.len());
+    bytes.extend_from_slice(value.as_bytes());
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn bytes(&mut self, length: usize) -> Result<&'a [u8], EmbeddedError> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(EmbeddedError::Corrupted(self.position))?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, EmbeddedError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<usize, EmbeddedError> {
+        let start = self.position;
+        let mut value = 0usize;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(EmbeddedError::Corrupted(start))
+    }
+
+    fn string(&mut self) -> Result<String, EmbeddedError> {
+        let start = self.position;
+        let length = self.varint()?;
+        let bytes = self.bytes(length)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| EmbeddedError::Corrupted(start))
+    }
+
+    fn f32(&mut self) -> Result<f32, EmbeddedError> {
+        let bytes = self.bytes(4)?;
+        Ok(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+fn url(names: &[&str]) -> String {
+    format!("embedded://{}", names.join("/"))
+}
+
+fn not_found(url: &str) -> Error {
+    ApiClientError::Request {
+        url: url.to_string(),
+        message: "not found in the embedded master data".to_string(),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::geolonia::entity::Town;
+    use crate::domain::geolonia::error::Error;
+    use crate::domain::geolonia::error::ErrorKind;
+    use crate::parser::Parser;
+    use crate::source::embedded::{
+        encode, generate, shared_prefix_len, EmbeddedError, EmbeddedSource, Prefecture,
+    };
+    use crate::source::AddressMasterSource;
+
+    fn town(name: &str, koaza: &str, coordinate: Option<(f32, f32)>) -> Town {
+        Town {
+            name: name.to_string(),
+            koaza: koaza.to_string(),
+            lat: coordinate.map(|(lat, _)| lat),
+            lng: coordinate.map(|(_, lng)| lng),
+        }
+    }
+
+    fn tokyo() -> Vec<Prefecture> {
+        vec![(
+            "東京都".to_string(),
+            vec![
+                (
+                    "千代田区".to_string(),
+                    vec![
+                        town("丸の内一丁目", "", Some((35.68156, 139.7672))),
+                        town("丸の内二丁目", "", Some((35.680022, 139.76344))),
+                        town("千代田", "", Some((35.68391, 139.75429))),
+                        town("千代田", "皇居外苑", None),
+                    ],
+                ),
+                ("中央区".to_string(), vec![]),
+            ],
+        )]
+    }
+
+    #[tokio::test]
+    async fn 変換した住所マスタを読み込めること() {
+        let source = EmbeddedSource::from_bytes(encode(&tokyo())).unwrap();
+
+        assert_eq!(
+            source.prefecture("東京都").await.unwrap(),
+            vec!["千代田区", "中央区"]
+        );
+        assert_eq!(
+            source.city("東京都", "千代田区").await.unwrap(),
+            tokyo()[0].1[0].1
+        );
+        assert_eq!(source.city("東京都", "中央区").await.unwrap(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn 含まれない都道府県や市区町村の場合はエラーを返すこと() {
+        let source = EmbeddedSource::from_bytes(encode(&tokyo())).unwrap();
+
+        let error = source.prefecture("大阪府").await.unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::Fetch { url, .. } if url == "embedded://大阪府"));
+        let error = source.city("東京都", "港区").await.unwrap_err();
+        assert!(
+            matches!(error.kind, ErrorKind::Fetch { url, .. } if url == "embedded://東京都/港区")
+        );
+    }
+
+    /// 東京都の住所マスタのみを返す取得元
+    struct TokyoSource;
+
+    impl TokyoSource {
+        fn city_names(&self, prefecture_name: &str) -> Vec<String> {
+            tokyo()
+                .into_iter()
+                .filter(|(name, _)| name == prefecture_name)
+                .flat_map(|(_, cities)| cities.into_iter().map(|(name, _)| name))
+                .collect()
+        }
+
+        fn towns(&self, city_name: &str) -> Vec<Town> {
+            tokyo()[0]
+                .1
+                .iter()
+                .find(|(name, _)| name == city_name)
+                .map(|(_, towns)| towns.clone())
+                .unwrap_or_default()
+        }
+    }
+
+    impl AddressMasterSource for TokyoSource {
+        async fn prefecture(&self, prefecture_name: &str) -> Result<Vec<String>, Error> {
+            Ok(self.city_names(prefecture_name))
+        }
+
+        async fn city(&self, _: &str, city_name: &str) -> Result<Vec<Town>, Error> {
+            Ok(self.towns(city_name))
+        }
+
+        #[cfg(feature = "blocking")]
+        fn prefecture_blocking(&self, prefecture_name: &str) -> Result<Vec<String>, Error> {
+            Ok(self.city_names(prefecture_name))
+        }
+
+        #[cfg(feature = "blocking")]
+        fn city_blocking(&self, _: &str, city_name: &str) -> Result<Vec<Town>, Error> {
+            Ok(self.towns(city_name))
+        }
+    }
+
+    #[tokio::test]
+    async fn generate_全ての都道府県の住所マスタを変換すること() {
+        let bytes = generate(&TokyoSource).await.unwrap();
+        let source = EmbeddedSource::from_bytes(bytes).unwrap();
+
+        assert_eq!(source.prefectures.len(), 47);
+        assert_eq!(
+            source.prefecture("大阪府").await.unwrap(),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            source.city("東京都", "千代田区").await.unwrap(),
+            tokyo()[0].1[0].1
+        );
+    }
+
+    #[test]
+    fn 形式が異なるデータは読み込まないこと() {
+        assert_eq!(
+            EmbeddedSource::from_bytes(b"{}".to_vec()).unwrap_err(),
+            EmbeddedError::Corrupted(0)
+        );
+        assert_eq!(
+            EmbeddedSource::from_bytes(b"JAPM\x02".to_vec()).unwrap_err(),
+            EmbeddedError::UnsupportedFormat
+        );
+
+        let mut bytes = encode(&tokyo());
+        bytes.truncate(20);
+        assert!(EmbeddedSource::from_bytes(bytes).is_err());
+    }
+
+    #[tokio::test]
+    async fn 途中で切れたデータの町名を読み込む場合はエラーを返すこと() {
+        let mut bytes = encode(&tokyo());
+        bytes.truncate(bytes.len() - 10);
+        let source = EmbeddedSource::from_bytes(bytes).unwrap();
+
+        let error = source.city("東京都", "千代田区").await.unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::Deserialize { .. }));
+    }
+
+    #[test]
+    fn shared_prefix_len_文字の境界で区切ること() {
+        assert_eq!(shared_prefix_len("丸の内一丁目", "丸の内二丁目"), 9);
+        assert_eq!(shared_prefix_len("千代田", "千代田"), 9);
+        assert_eq!(shared_prefix_len("", "千代田"), 0);
+        // 「一」(E4 B8 80)と「七」(E4 B8 83)は先頭2バイトが共通する
+        assert_eq!(shared_prefix_len("一", "七"), 0);
+    }
+
+    #[tokio::test]
+    async fn 通信を行わずに住所をパースできること() {
+        let source = EmbeddedSource::from_bytes(encode(&tokyo())).unwrap();
+        let parser = Parser::with_source(source);
+
+        let result = parser.parse("東京都千代田区丸の内2-7-2").await;
+        assert_eq!(result.address.city, "千代田区");
+        assert_eq!(result.address.town, "丸の内二丁目");
+        assert_eq!(result.address.rest, "7-2");
+        assert_eq!(result.error, None);
+    }
+}
@@ -1,9 +1,13 @@
+pub(crate) mod offset;
 pub(crate) mod read_city;
 pub(crate) mod read_city_with_county_name_completion;
+pub(crate) mod read_koaza;
 pub(crate) mod read_prefecture;
 pub(crate) mod read_town;
 
 use crate::domain::common::token::{append_token, Token};
+use crate::domain::geolonia::entity::{AddressSpans, Correction};
+use crate::tokenizer::offset::Offsets;
 use std::marker::PhantomData;
 
 #[derive(Debug)]
@@ -23,9 +27,29 @@ pub(crate) struct End;
 pub struct Tokenizer<State> {
     pub(crate) tokens: Vec<Token>,
     rest: String,
+    /// 住所を読み取る過程で行った補正
+    pub(crate) corrections: Vec<Correction>,
+    /// 各トークンの元の入力中の位置
+    offsets: Offsets,
     _state: PhantomData<State>,
 }
 
+/// 解釈候補を列挙する際の候補の一つ
+#[derive(Debug)]
+pub(crate) struct Candidate<State> {
+    /// この段階で検出した名称
+    pub(crate) name: String,
+    pub(crate) tokenizer: Tokenizer<State>,
+    /// 候補の確からしさ(0.0〜1.0)
+    pub(crate) score: f64,
+}
+
+/// 読み取り前後の文字列から、読み取った部分を返す
+fn consumed_text(before: &str, after: &str) -> String {
+    let length = before.chars().count().saturating_sub(after.chars().count());
+    before.chars().take(length).collect()
+}
+
 impl<T> Tokenizer<T> {
     fn get_prefecture_name(&self) -> Option<&str> {
         for token in &self.tokens {
@@ -36,11 +60,25 @@ impl<T> Tokenizer<T> {
         None
     }
 
+    /// これまでに行った補正に新たな補正を加えたものを返す
+    fn append_corrections(&self, corrections: Vec<Correction>) -> Vec<Correction> {
+        [self.corrections.clone(), corrections].concat()
+    }
+
     pub(crate) fn finish(&self) -> Tokenizer<End> {
         Tokenizer {
             tokens: append_token(&self.tokens, Token::Rest(self.rest.clone())),
             rest: "".to_string(),
+            corrections: self.corrections.clone(),
+            offsets: self.offsets.push_token_end(""),
             _state: PhantomData::<End>,
         }
     }
 }
+
+impl Tokenizer<End> {
+    /// 各トークンの元の入力中の位置を返す
+    pub(crate) fn spans(&self) -> AddressSpans {
+        self.offsets.spans(&self.tokens)
+    }
+}
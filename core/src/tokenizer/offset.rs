@@ -0,0 +1,235 @@
+use crate::domain::common::token::Token;
+use crate::domain::geolonia::entity::{AddressSpans, Span};
+use crate::formatter::halfwidth_character::format_halfwidth_katakana;
+use crate::parser::ParseOptions;
+use crate::util::extension::CharExt;
+use std::sync::Arc;
+
+/// 正規化後の文字列中の位置を、元の入力中の位置に対応付けるためのもの
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Offsets {
+    /// 読み取りの各段階で共有する、正規化前後の対応
+    source: Arc<Source>,
+    /// 読み取った各トークンの終端(正規化後の文字位置)
+    token_ends: Vec<usize>,
+}
+
+#[derive(Debug, Default)]
+struct Source {
+    /// 元の入力
+    original: String,
+    /// 正規化後のi文字目が由来する元の入力の文字範囲
+    ranges: Vec<(usize, usize)>,
+}
+
+impl Offsets {
+    /// 入力を正規化し、正規化後の文字列と`Offsets`を返す
+    pub(crate) fn normalize(input: &str, options: &ParseOptions) -> (String, Self) {
+        let chars: Vec<char> = input.chars().collect();
+        let mut normalized = String::with_capacity(input.len());
+        let mut ranges = Vec::with_capacity(chars.len());
+        let mut index = 0;
+        while index < chars.len() {
+            let c = chars[index];
+            if c.is_variation_selector() || (options.eliminate_whitespaces && c.is_whitespace()) {
+                index += 1;
+                continue;
+            }
+            if options.fix_halfwidth_katakana && is_halfwidth_katakana(c) {
+                // 濁点・半濁点が続く場合はまとめて変換する
+                let length = match chars.get(index + 1) {
+                    Some('ﾞ' | 'ﾟ') => 2,
+                    _ => 1,
+                };
+                let unit: String = chars[index..index + length].iter().collect();
+                for converted in format_halfwidth_katakana(&unit).chars() {
+                    normalized.push(converted);
+                    ranges.push((index, index + length));
+                }
+                index += length;
+                continue;
+            }
+            normalized.push(c);
+            ranges.push((index, index + 1));
+            index += 1;
+        }
+        (
+            normalized,
+            Self {
+                source: Arc::new(Source {
+                    original: input.to_string(),
+                    ranges,
+                }),
+                token_ends: vec![],
+            },
+        )
+    }
+
+    /// トークンを読み取った後の残りの文字列から、トークンの終端を記録する
+    pub(crate) fn push_token_end(&self, rest: &str) -> Self {
+        let mut offsets = self.clone();
+        offsets.token_ends.push(
+            self.source
+                .ranges
+                .len()
+                .saturating_sub(rest.chars().count()),
+        );
+        offsets
+    }
+
+    /// 各トークンの元の入力中の位置を返す
+    pub(crate) fn spans(&self, tokens: &[Token]) -> AddressSpans {
+        let mut spans = AddressSpans::default();
+        let mut start = 0;
+        for (token, &end) in tokens.iter().zip(&self.token_ends) {
+            let span = Some(self.to_span(start, end));
+            match token {
+                Token::PostalCode(_) => spans.postal_code = span,
+                // 推定した都道府県名は入力中に存在しないため位置を持たない
+                Token::Prefecture(_) => spans.prefecture = span.filter(|_| start < end),
+                Token::City(_) => spans.city = span,
+                Token::Town(_) => spans.town = span,
+                Token::Koaza(_) => spans.koaza = span,
+                Token::Rest(_) => spans.rest = span,
+            }
+            start = end;
+        }
+        spans
+    }
+
+    /// 正規化後の文字範囲を元の入力中の範囲に変換する
+    fn to_span(&self, start: usize, end: usize) -> Span {
+        let original_length = self.source.original.chars().count();
+        let char_start = self
+            .source
+            .ranges
+            .get(start)
+            .map_or(original_length, |range| range.0);
+        let char_end = if end > start {
+            self.source
+                .ranges
+                .get(end - 1)
+                .map_or(original_length, |range| range.1)
+        } else {
+            char_start
+        };
+        Span {
+            byte_start: self.byte_offset(char_start),
+            byte_end: self.byte_offset(char_end),
+            char_start,
+            char_end,
+        }
+    }
+
+    fn byte_offset(&self, char_index: usize) -> usize {
+        self.source
+            .original
+            .char_indices()
+            .nth(char_index)
+            .map_or(self.source.original.len(), |(byte_index, _)| byte_index)
+    }
+}
+
+fn is_halfwidth_katakana(c: char) -> bool {
+    ('\u{FF61}'..='\u{FF9F}').contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::common::token::Token;
+    use crate::domain::geolonia::entity::Span;
+    use crate::parser::ParseOptions;
+    use crate::tokenizer::offset::Offsets;
+
+    #[test]
+    fn normalize_異字体セレクタを除去しても元の位置を保持する() {
+        let (normalized, offsets) = Offsets::normalize("葛\u{E0100}飾区", &Default::default());
+        assert_eq!(normalized, "葛飾区");
+        assert_eq!(offsets.source.ranges, [(0, 1), (2, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn normalize_逢坂() {
+        let normal = "\u{9022}\u{5742}"; // 逢坂
+        let variant = "\u{9022}\u{E0101}\u{5742}"; // 逢󠄁坂
+        assert_ne!(normal, variant);
+        assert_eq!(normal, Offsets::normalize(variant, &Default::default()).0);
+    }
+
+    #[test]
+    fn normalize_茨城() {
+        let normal = "\u{8328}\u{57CE}";
+        let variant = "\u{8328}\u{E0100}\u{57CE}";
+        assert_ne!(normal, variant);
+        assert_eq!(normal, Offsets::normalize(variant, &Default::default()).0);
+    }
+
+    #[test]
+    fn normalize_ホワイトスペースを除去する() {
+        let options = ParseOptions::default().eliminate_whitespaces(true);
+        assert_eq!(Offsets::normalize("四谷1丁目", &options).0, "四谷1丁目");
+        assert_eq!(Offsets::normalize("四谷 1丁目", &options).0, "四谷1丁目");
+        assert_eq!(Offsets::normalize("四谷  1丁目", &options).0, "四谷1丁目");
+        assert_eq!(Offsets::normalize("四谷 1 丁 目", &options).0, "四谷1丁目");
+        assert_eq!(Offsets::normalize("神田３丁目", &options).0, "神田３丁目");
+        assert_eq!(Offsets::normalize("神田　３丁目", &options).0, "神田３丁目");
+        assert_eq!(
+            Offsets::normalize("神田　　３丁目", &options).0,
+            "神田３丁目"
+        );
+        assert_eq!(
+            Offsets::normalize("神田　３　丁目", &options).0,
+            "神田３丁目"
+        );
+        let (_, offsets) = Offsets::normalize("四谷 1丁目", &options);
+        assert_eq!(offsets.source.ranges[2], (3, 4));
+    }
+
+    #[test]
+    fn normalize_ホワイトスペースを除去しない場合() {
+        let options = ParseOptions::default().eliminate_whitespaces(false);
+        assert_eq!(Offsets::normalize("四谷 1丁目", &options).0, "四谷 1丁目");
+    }
+
+    #[test]
+    fn spans_各トークンの位置を返す() {
+        let input = "東京都葛\u{E0100}飾区立石5-13-1";
+        let (normalized, offsets) = Offsets::normalize(input, &Default::default());
+        let offsets = offsets
+            .push_token_end(&normalized.replacen("東京都", "", 1))
+            .push_token_end("立石5-13-1")
+            .push_token_end("5-13-1")
+            .push_token_end("");
+        let spans = offsets.spans(&[
+            Token::Prefecture("東京都".to_string()),
+            Token::City("葛飾区".to_string()),
+            Token::Town("立石".to_string()),
+            Token::Rest("5-13-1".to_string()),
+        ]);
+        assert_eq!(
+            spans.prefecture,
+            Some(Span {
+                byte_start: 0,
+                byte_end: 9,
+                char_start: 0,
+                char_end: 3,
+            })
+        );
+        let city = spans.city.unwrap();
+        assert_eq!((city.char_start, city.char_end), (3, 7));
+        assert_eq!(&input[city.byte_start..city.byte_end], "葛\u{E0100}飾区");
+        let town = spans.town.unwrap();
+        assert_eq!(&input[town.byte_start..town.byte_end], "立石");
+        let rest = spans.rest.unwrap();
+        assert_eq!(&input[rest.byte_start..rest.byte_end], "5-13-1");
+    }
+
+    #[test]
+    fn normalize_半角カタカナの濁点をまとめて変換する() {
+        let options = ParseOptions::default().fix_halfwidth_katakana(true);
+        let (normalized, offsets) = Offsets::normalize("ｶﾞ", &options);
+        assert_eq!(normalized, "ガ");
+        assert_eq!(normalized.chars().count(), offsets.source.ranges.len());
+        assert!(offsets.source.ranges.iter().all(|range| *range == (0, 2)));
+    }
+}
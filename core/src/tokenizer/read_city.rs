@@ -2,7 +2,10 @@ use crate::adapter::orthographical_variant_adapter::{
     OrthographicalVariant, OrthographicalVariantAdapter,
 };
 use crate::domain::common::token::{append_token, Token};
-use crate::tokenizer::{CityNameFound, CityNameNotFound, PrefectureNameFound, Tokenizer};
+use crate::domain::geolonia::entity::{Correction, CorrectionKind};
+use crate::tokenizer::{
+    consumed_text, Candidate, CityNameFound, CityNameNotFound, PrefectureNameFound, Tokenizer,
+};
 use std::marker::PhantomData;
 
 impl Tokenizer<PrefectureNameFound> {
@@ -14,37 +17,21 @@ impl Tokenizer<PrefectureNameFound> {
             .iter()
             .find(|&candidate| self.rest.starts_with(candidate))
         {
+            let rest: String = self.rest.chars().skip(found.chars().count()).collect();
             return Ok((
                 found.to_string(),
                 Tokenizer {
                     tokens: append_token(&self.tokens, Token::City(found.to_string())),
-                    rest: self.rest.chars().skip(found.chars().count()).collect(),
+                    offsets: self.offsets.push_token_end(&rest),
+                    rest,
+                    corrections: self.corrections.clone(),
                     _state: PhantomData::<CityNameFound>,
                 },
             ));
         }
 
         // ここまでで市区町村名が読み取れない場合は、表記ゆれを含む可能性を検討する
-        use OrthographicalVariant::*;
-        let mut variant_list = vec![ケ];
-        if let Some(pref_name) = self.get_prefecture_name() {
-            variant_list.extend(match pref_name {
-                "青森県" => vec![舘, 鰺],
-                "宮城県" => vec![竈],
-                "茨城県" => vec![龍, 嶋],
-                "東京都" => vec![檜],
-                "岐阜県" => vec![驒],
-                "三重県" => vec![婁],
-                "大阪府" => vec![條],
-                "兵庫県" => vec![塚],
-                "奈良県" => vec![條],
-                "和歌山県" => vec![婁],
-                "高知県" => vec![梼],
-                "福岡県" => vec![恵],
-                "長崎県" => vec![諫],
-                _ => vec![],
-            });
-        }
+        let variant_list = self.city_variant_list();
         for candidate in candidates {
             let adapter = OrthographicalVariantAdapter {
                 variant_list: variant_list.clone(),
@@ -53,7 +40,13 @@ impl Tokenizer<PrefectureNameFound> {
                 return Ok((
                     city_name.clone(),
                     Tokenizer {
-                        tokens: append_token(&self.tokens, Token::City(city_name)),
+                        tokens: append_token(&self.tokens, Token::City(city_name.clone())),
+                        corrections: self.append_corrections(vec![Correction {
+                            kind: CorrectionKind::OrthographicalVariant,
+                            original: consumed_text(&self.rest, &rest),
+                            replaced: city_name,
+                        }]),
+                        offsets: self.offsets.push_token_end(&rest),
                         rest,
                         _state: PhantomData::<CityNameFound>,
                     },
@@ -64,14 +57,107 @@ impl Tokenizer<PrefectureNameFound> {
         Err(Tokenizer {
             tokens: self.tokens.clone(),
             rest: self.rest.clone(),
+            corrections: self.corrections.clone(),
+            offsets: self.offsets.clone(),
             _state: PhantomData::<CityNameNotFound>,
         })
     }
+
+    /// 市区町村名として解釈できる候補をすべて列挙する
+    ///
+    /// 前方一致する候補に加えて、表記ゆれを補正すれば一致する候補も返す。
+    pub(crate) fn read_city_candidates(
+        &self,
+        candidates: &[String],
+    ) -> Result<Vec<Candidate<CityNameFound>>, Tokenizer<CityNameNotFound>> {
+        let mut found: Vec<Candidate<CityNameFound>> = candidates
+            .iter()
+            .filter(|&candidate| self.rest.starts_with(candidate))
+            .map(|candidate| {
+                let rest: String = self.rest.chars().skip(candidate.chars().count()).collect();
+                Candidate {
+                    name: candidate.to_string(),
+                    tokenizer: Tokenizer {
+                        tokens: append_token(&self.tokens, Token::City(candidate.to_string())),
+                        offsets: self.offsets.push_token_end(&rest),
+                        rest,
+                        corrections: self.corrections.clone(),
+                        _state: PhantomData::<CityNameFound>,
+                    },
+                    score: 1.0,
+                }
+            })
+            .collect();
+
+        let adapter = OrthographicalVariantAdapter {
+            variant_list: self.city_variant_list(),
+        };
+        for candidate in candidates {
+            if self.rest.starts_with(candidate) {
+                continue;
+            }
+            if let Some((city_name, rest)) = adapter.apply(&self.rest, candidate) {
+                found.push(Candidate {
+                    name: city_name.clone(),
+                    tokenizer: Tokenizer {
+                        tokens: append_token(&self.tokens, Token::City(city_name.clone())),
+                        corrections: self.append_corrections(vec![Correction {
+                            kind: CorrectionKind::OrthographicalVariant,
+                            original: consumed_text(&self.rest, &rest),
+                            replaced: city_name,
+                        }]),
+                        offsets: self.offsets.push_token_end(&rest),
+                        rest,
+                        _state: PhantomData::<CityNameFound>,
+                    },
+                    score: CorrectionKind::OrthographicalVariant.weight(),
+                });
+            }
+        }
+
+        if found.is_empty() {
+            Err(Tokenizer {
+                tokens: self.tokens.clone(),
+                rest: self.rest.clone(),
+                corrections: self.corrections.clone(),
+                offsets: self.offsets.clone(),
+                _state: PhantomData::<CityNameNotFound>,
+            })
+        } else {
+            Ok(found)
+        }
+    }
+
+    /// 都道府県ごとに考慮すべき表記ゆれのパターン
+    fn city_variant_list(&self) -> Vec<OrthographicalVariant> {
+        use OrthographicalVariant::*;
+        let mut variant_list = vec![ケ];
+        if let Some(pref_name) = self.get_prefecture_name() {
+            variant_list.extend(match pref_name {
+                "青森県" => vec![舘, 鰺],
+                "宮城県" => vec![竈],
+                "茨城県" => vec![龍, 嶋],
+                "東京都" => vec![檜],
+                "岐阜県" => vec![驒],
+                "三重県" => vec![婁],
+                "大阪府" => vec![條],
+                "兵庫県" => vec![塚],
+                "奈良県" => vec![條],
+                "和歌山県" => vec![婁],
+                "高知県" => vec![梼],
+                "福岡県" => vec![恵],
+                "長崎県" => vec![諫],
+                _ => vec![],
+            });
+        }
+        variant_list
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::domain::common::token::Token;
+    use crate::domain::geolonia::entity::{Correction, CorrectionKind};
     use crate::tokenizer::{PrefectureNameFound, Tokenizer};
     use std::marker::PhantomData;
 
@@ -80,9 +166,11 @@ mod tests {
         let tokenizer = Tokenizer {
             tokens: vec![Token::Prefecture("神奈川県".to_string())],
             rest: "横浜市保土ケ谷区川辺町2番地9".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<PrefectureNameFound>,
         };
-        let result = tokenizer.read_city(&vec![
+        let result = tokenizer.read_city(&[
             "横浜市保土ケ谷区".to_string(),
             "横浜市鶴見区".to_string(),
             "横浜市西区".to_string(),
@@ -99,9 +187,11 @@ mod tests {
         let tokenizer = Tokenizer {
             tokens: vec![Token::Prefecture("神奈川県".to_string())],
             rest: "横浜市保土ヶ谷区川辺町2番地9".to_string(), // 「ヶ」と「ケ」の表記ゆれ
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<PrefectureNameFound>,
         };
-        let result = tokenizer.read_city(&vec![
+        let result = tokenizer.read_city(&[
             "横浜市保土ケ谷区".to_string(),
             "横浜市鶴見区".to_string(),
             "横浜市西区".to_string(),
@@ -113,14 +203,46 @@ mod tests {
         assert_eq!(tokenizer.rest, "川辺町2番地9");
     }
 
+    #[test]
+    fn read_city_candidates_表記ゆれを補正した候補を含む() {
+        let tokenizer = Tokenizer {
+            tokens: vec![Token::Prefecture("神奈川県".to_string())],
+            rest: "横浜市保土ヶ谷区川辺町2番地9".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
+            _state: PhantomData::<PrefectureNameFound>,
+        };
+        let result = tokenizer.read_city_candidates(&[
+            "横浜市保土ケ谷区".to_string(),
+            "横浜市鶴見区".to_string(),
+            "横浜市西区".to_string(),
+        ]);
+        assert!(result.is_ok());
+        let candidates = result.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].name, "横浜市保土ケ谷区");
+        assert_eq!(candidates[0].tokenizer.rest, "川辺町2番地9");
+        assert_eq!(
+            candidates[0].tokenizer.corrections,
+            vec![Correction {
+                kind: CorrectionKind::OrthographicalVariant,
+                original: "横浜市保土ヶ谷区".to_string(),
+                replaced: "横浜市保土ケ谷区".to_string(),
+            }]
+        );
+        assert!(candidates[0].score < 1.0);
+    }
+
     #[test]
     fn read_city_失敗() {
         let tokenizer = Tokenizer {
             tokens: vec![Token::Prefecture("神奈川県".to_string())],
             rest: "京都市上京区川辺町2番地9".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<PrefectureNameFound>,
         };
-        let result = tokenizer.read_city(&vec![
+        let result = tokenizer.read_city(&[
             "横浜市保土ケ谷区".to_string(),
             "横浜市鶴見区".to_string(),
             "横浜市西区".to_string(),
@@ -1,5 +1,6 @@
 use crate::domain::common::token::{append_token, Token};
-use crate::tokenizer::{CityNameFound, CityNameNotFound, End, Tokenizer};
+use crate::domain::geolonia::entity::{Correction, CorrectionKind};
+use crate::tokenizer::{consumed_text, Candidate, CityNameFound, CityNameNotFound, End, Tokenizer};
 use crate::util::sequence_matcher::SequenceMatcher;
 use std::marker::PhantomData;
 
@@ -12,14 +13,21 @@ impl Tokenizer<CityNameNotFound> {
             SequenceMatcher::get_most_similar_match(&self.rest, candidates, None)
         {
             if let Ok(complemented_address) = complement_county_name(&self.rest, &highest_match) {
+                let rest: String = complemented_address
+                    .chars()
+                    .skip(highest_match.chars().count())
+                    .collect();
                 return Ok((
                     highest_match.clone(),
                     Tokenizer {
                         tokens: append_token(&self.tokens, Token::City(highest_match.clone())),
-                        rest: complemented_address
-                            .chars()
-                            .skip(highest_match.chars().count())
-                            .collect(),
+                        corrections: self.append_corrections(vec![Correction {
+                            kind: CorrectionKind::CountyNameCompletion,
+                            original: consumed_text(&self.rest, &rest),
+                            replaced: highest_match,
+                        }]),
+                        offsets: self.offsets.push_token_end(&rest),
+                        rest,
                         _state: PhantomData::<CityNameFound>,
                     },
                 ));
@@ -27,6 +35,38 @@ impl Tokenizer<CityNameNotFound> {
         }
         Err(self.finish())
     }
+
+    /// 郡名を補うことで市区町村名として解釈できる候補を、類似度の高い順に列挙する
+    pub(crate) fn read_city_with_county_name_completion_candidates(
+        &self,
+        candidates: &[String],
+    ) -> Vec<Candidate<CityNameFound>> {
+        SequenceMatcher::get_similar_matches(&self.rest, candidates, None)
+            .into_iter()
+            .filter_map(|(city_name, similarity)| {
+                let complemented_address = complement_county_name(&self.rest, &city_name).ok()?;
+                let rest: String = complemented_address
+                    .chars()
+                    .skip(city_name.chars().count())
+                    .collect();
+                Some(Candidate {
+                    name: city_name.clone(),
+                    tokenizer: Tokenizer {
+                        tokens: append_token(&self.tokens, Token::City(city_name.clone())),
+                        corrections: self.append_corrections(vec![Correction {
+                            kind: CorrectionKind::CountyNameCompletion,
+                            original: consumed_text(&self.rest, &rest),
+                            replaced: city_name,
+                        }]),
+                        offsets: self.offsets.push_token_end(&rest),
+                        rest,
+                        _state: PhantomData::<CityNameFound>,
+                    },
+                    score: similarity * CorrectionKind::CountyNameCompletion.weight(),
+                })
+            })
+            .collect()
+    }
 }
 
 /// 郡名が抜けている住所に郡名を補う関数
@@ -44,6 +84,7 @@ fn complement_county_name(vague_address: &str, with: &str) -> Result<String, &'s
 mod tests {
     use crate::domain::common::token::Token;
     use crate::domain::geolonia;
+    use crate::domain::geolonia::entity::{Correction, CorrectionKind};
     use crate::tokenizer::read_city_with_county_name_completion::complement_county_name;
     use crate::tokenizer::{CityNameNotFound, Tokenizer};
     use std::marker::PhantomData;
@@ -74,9 +115,11 @@ mod tests {
         let tokenizer = Tokenizer {
             tokens: vec![Token::Prefecture("埼玉県".to_string())],
             rest: "東秩父村大字御堂634番地".to_string(), // 「秩父郡」が省略されている
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameNotFound>,
         };
-        let result = tokenizer.read_city_with_county_name_completion(&vec![
+        let result = tokenizer.read_city_with_county_name_completion(&[
             "秩父郡皆野町".to_string(),
             "秩父郡長瀞町".to_string(),
             "秩父郡小鹿野町".to_string(),
@@ -87,6 +130,42 @@ mod tests {
         assert_eq!(city_name, "秩父郡東秩父村");
         assert_eq!(tokenizer.tokens.len(), 2);
         assert_eq!(tokenizer.rest, "大字御堂634番地");
+        assert_eq!(
+            tokenizer.corrections,
+            vec![Correction {
+                kind: CorrectionKind::CountyNameCompletion,
+                original: "東秩父村".to_string(),
+                replaced: "秩父郡東秩父村".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn read_city_with_county_name_completion_candidates_類似度が同じ候補を両方返す() {
+        let tokenizer = Tokenizer {
+            tokens: vec![Token::Prefecture("北海道".to_string())],
+            rest: "森町字森1".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
+            _state: PhantomData::<CityNameNotFound>,
+        };
+        let candidates = tokenizer.read_city_with_county_name_completion_candidates(&[
+            "周智郡森町".to_string(),
+            "茅部郡森町".to_string(),
+        ]);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].name, "周智郡森町");
+        assert_eq!(candidates[1].name, "茅部郡森町");
+        assert_eq!(candidates[0].score, candidates[1].score);
+        assert_eq!(candidates[1].tokenizer.rest, "字森1");
+        assert_eq!(
+            candidates[1].tokenizer.corrections,
+            vec![Correction {
+                kind: CorrectionKind::CountyNameCompletion,
+                original: "森町".to_string(),
+                replaced: "茅部郡森町".to_string(),
+            }]
+        );
     }
 
     #[test]
@@ -94,6 +173,8 @@ mod tests {
         let tokenizer = Tokenizer {
             tokens: vec![Token::Prefecture("福井県".to_string())],
             rest: "永平寺町志比５－５".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameNotFound>,
         };
         let result = tokenizer
@@ -110,6 +191,8 @@ mod tests {
         let tokenizer = Tokenizer {
             tokens: vec![Token::Prefecture("福井県".to_string())],
             rest: "池田町稲荷２８－７".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameNotFound>,
         };
         let result = tokenizer
@@ -126,6 +209,8 @@ mod tests {
         let tokenizer = Tokenizer {
             tokens: vec![Token::Prefecture("福井県".to_string())],
             rest: "南越前町今庄７４－７－１".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameNotFound>,
         };
         let result = tokenizer
@@ -142,6 +227,8 @@ mod tests {
         let tokenizer = Tokenizer {
             tokens: vec![Token::Prefecture("山形県".to_string())],
             rest: "河北町大字吉田字馬場261".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameNotFound>,
         };
         let result = tokenizer.read_city_with_county_name_completion(
@@ -159,6 +246,8 @@ mod tests {
         let tokenizer = Tokenizer {
             tokens: vec![Token::Prefecture("佐賀県".to_string())],
             rest: "大町町大字大町5017番地".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameNotFound>,
         };
         let result = tokenizer
@@ -175,6 +264,8 @@ mod tests {
         let tokenizer = Tokenizer {
             tokens: vec![Token::Prefecture("山形県".to_string())],
             rest: "最上町法田2672-2".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameNotFound>,
         };
         let result = tokenizer.read_city_with_county_name_completion(
@@ -0,0 +1,141 @@
+use crate::domain::common::token::{append_token, Token};
+use crate::domain::geolonia::entity::{Correction, CorrectionKind};
+use crate::formatter::prepend_aza::prepend_aza;
+use crate::tokenizer::{consumed_text, Tokenizer, TownNameFound};
+use std::marker::PhantomData;
+
+impl Tokenizer<TownNameFound> {
+    /// 町名以降の文字列の先頭にある小字・通称名を読み取る
+    ///
+    /// 一致する小字・通称名がない場合は何も読み取らずにそのまま返す。
+    pub(crate) fn read_koaza(self, candidates: &[String]) -> Tokenizer<TownNameFound> {
+        let Some((koaza, koaza_rest, correction_kind)) = find_koaza(&self.rest, candidates) else {
+            return self;
+        };
+        let original = consumed_text(&self.rest, &koaza_rest);
+        let corrections = correction_kind
+            .map(|kind| Correction {
+                kind,
+                original,
+                replaced: koaza.clone(),
+            })
+            .into_iter()
+            .collect();
+        Tokenizer {
+            tokens: append_token(&self.tokens, Token::Koaza(koaza)),
+            offsets: self.offsets.push_token_end(&koaza_rest),
+            rest: koaza_rest,
+            corrections: self.append_corrections(corrections),
+            _state: PhantomData::<TownNameFound>,
+        }
+    }
+}
+
+/// 小字・通称名、それ以降の文字列、行った補正の組を返す
+///
+/// 長い小字・通称名から順に試すことで、前方が共通する候補のうち最も長く一致するものを優先する。
+fn find_koaza(
+    input: &str,
+    candidates: &[String],
+) -> Option<(String, String, Option<CorrectionKind>)> {
+    let mut candidates: Vec<&String> = candidates
+        .iter()
+        .filter(|candidate| !candidate.is_empty())
+        .collect();
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.chars().count()));
+    candidates.into_iter().find_map(|candidate| {
+        if let Some(rest) = input.strip_prefix(candidate.as_str()) {
+            return Some((candidate.clone(), rest.to_string(), None));
+        }
+        // 「字」が省略されている場合
+        if candidate.starts_with('字') {
+            let prepended = prepend_aza(input)?;
+            if let Some(rest) = prepended.strip_prefix(candidate.as_str()) {
+                return Some((
+                    candidate.clone(),
+                    rest.to_string(),
+                    Some(CorrectionKind::AzaInsertion),
+                ));
+            }
+        }
+        // 小字・通称名が「字」を含まない形で登録されている場合
+        input
+            .strip_prefix('字')
+            .and_then(|input| input.strip_prefix(candidate.as_str()))
+            .map(|rest| (candidate.clone(), rest.to_string(), None))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::common::token::Token;
+    use crate::domain::geolonia::entity::{Correction, CorrectionKind};
+    use crate::tokenizer::{Tokenizer, TownNameFound};
+    use std::marker::PhantomData;
+
+    fn tokenizer(rest: &str) -> Tokenizer<TownNameFound> {
+        Tokenizer {
+            tokens: vec![
+                Token::Prefecture("青森県".to_string()),
+                Token::City("西津軽郡鰺ヶ沢町".to_string()),
+                Token::Town("大字舞戸町".to_string()),
+            ],
+            rest: rest.to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
+            _state: PhantomData::<TownNameFound>,
+        }
+    }
+
+    fn candidates() -> Vec<String> {
+        vec![
+            "字鳴戸".to_string(),
+            "字鳴戸東".to_string(),
+            "字小夜".to_string(),
+        ]
+    }
+
+    #[test]
+    fn read_koaza_成功() {
+        let tokenizer = tokenizer("字鳴戸321番地").read_koaza(&candidates());
+        assert_eq!(tokenizer.tokens[3], Token::Koaza("字鳴戸".to_string()));
+        assert_eq!(tokenizer.rest, "321番地");
+        assert!(tokenizer.corrections.is_empty());
+    }
+
+    #[test]
+    fn read_koaza_最も長く一致する候補を優先する() {
+        let tokenizer = tokenizer("字鳴戸東1番地").read_koaza(&candidates());
+        assert_eq!(tokenizer.tokens[3], Token::Koaza("字鳴戸東".to_string()));
+        assert_eq!(tokenizer.rest, "1番地");
+    }
+
+    #[test]
+    fn read_koaza_字が省略されている場合() {
+        let tokenizer = tokenizer("鳴戸321番地").read_koaza(&candidates());
+        assert_eq!(tokenizer.tokens[3], Token::Koaza("字鳴戸".to_string()));
+        assert_eq!(tokenizer.rest, "321番地");
+        assert_eq!(
+            tokenizer.corrections,
+            vec![Correction {
+                kind: CorrectionKind::AzaInsertion,
+                original: "鳴戸".to_string(),
+                replaced: "字鳴戸".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn read_koaza_候補が字を含まない場合() {
+        let tokenizer = tokenizer("字鳴戸321番地").read_koaza(&["鳴戸".to_string()]);
+        assert_eq!(tokenizer.tokens[3], Token::Koaza("鳴戸".to_string()));
+        assert_eq!(tokenizer.rest, "321番地");
+    }
+
+    #[test]
+    fn read_koaza_一致する候補がない場合() {
+        let tokenizer = tokenizer("321番地").read_koaza(&candidates());
+        assert_eq!(tokenizer.tokens.len(), 3);
+        assert_eq!(tokenizer.rest, "321番地");
+    }
+}
@@ -1,24 +1,37 @@
-use crate::domain::common::token::Token;
-use crate::formatter::halfwidth_character::format_halfwidth_katakana;
+use crate::domain::common::token::{append_token, Token};
+use crate::domain::geolonia::entity::{Correction, CorrectionKind};
+use crate::formatter::postal_code::read_postal_code;
+use crate::parser::ParseOptions;
+use crate::tokenizer::offset::Offsets;
 use crate::tokenizer::{End, Init, PrefectureNameFound, Tokenizer};
-use crate::util::extension::StrExt;
 use std::marker::PhantomData;
 
 impl Tokenizer<Init> {
-    pub(crate) fn new(input: &str) -> Self {
-        let mut input = input.strip_variation_selectors();
-        if cfg!(feature = "eliminate-whitespaces") {
-            input = input.strip_whitespaces();
-        }
-        if cfg!(feature = "fix-halfwidth-katakana") {
-            input = format_halfwidth_katakana(&input);
-        }
+    pub(crate) fn new(input: &str, options: &ParseOptions) -> Self {
+        let (input, offsets) = Offsets::normalize(input, options);
 
         Self {
             tokens: vec![],
             rest: input,
+            corrections: vec![],
+            offsets,
             _state: PhantomData,
         }
+        .read_postal_code()
+    }
+
+    /// 先頭に郵便番号が記載されている場合は読み取る
+    fn read_postal_code(self) -> Self {
+        match read_postal_code(&self.rest) {
+            Some((postal_code, rest)) => Self {
+                tokens: append_token(&self.tokens, Token::PostalCode(postal_code)),
+                offsets: self.offsets.push_token_end(rest),
+                rest: rest.to_string(),
+                corrections: self.corrections,
+                _state: PhantomData,
+            },
+            None => self,
+        }
     }
 
     pub(crate) fn read_prefecture(
@@ -27,15 +40,21 @@ impl Tokenizer<Init> {
         match find_prefecture(&self.rest) {
             Some(prefecture) => {
                 let prefecture_name = prefecture.name_ja();
+                let rest = self
+                    .rest
+                    .chars()
+                    .skip(prefecture_name.chars().count())
+                    .collect::<String>();
                 Ok((
                     prefecture.clone(),
                     Tokenizer {
-                        tokens: vec![Token::Prefecture(prefecture_name.to_string())],
-                        rest: self
-                            .rest
-                            .chars()
-                            .skip(prefecture_name.chars().count())
-                            .collect::<String>(),
+                        tokens: append_token(
+                            &self.tokens,
+                            Token::Prefecture(prefecture_name.to_string()),
+                        ),
+                        offsets: self.offsets.push_token_end(&rest),
+                        rest,
+                        corrections: self.corrections.clone(),
                         _state: PhantomData::<PrefectureNameFound>,
                     },
                 ))
@@ -43,51 +62,89 @@ impl Tokenizer<Init> {
             None => Err(self.finish()),
         }
     }
+
+    /// 都道府県名が省略されているものとして、`prefecture_name`を補う
+    pub(crate) fn assume_prefecture(
+        &self,
+        prefecture_name: &str,
+    ) -> Tokenizer<PrefectureNameFound> {
+        Tokenizer {
+            tokens: append_token(&self.tokens, Token::Prefecture(prefecture_name.to_string())),
+            rest: self.rest.clone(),
+            corrections: self.append_corrections(vec![Correction {
+                kind: CorrectionKind::PrefectureCompletion,
+                original: "".to_string(),
+                replaced: prefecture_name.to_string(),
+            }]),
+            offsets: self.offsets.push_token_end(&self.rest),
+            _state: PhantomData::<PrefectureNameFound>,
+        }
+    }
 }
 
-fn find_prefecture(input: &str) -> Option<&jisx0401::Prefecture> {
+pub(crate) fn find_prefecture(input: &str) -> Option<&jisx0401::Prefecture> {
     jisx0401::Prefecture::values().find(|&prefecture| input.starts_with(prefecture.name_ja()))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::domain::common::token::Token;
+    use crate::domain::geolonia::entity::CorrectionKind;
+    use crate::parser::ParseOptions;
     use crate::tokenizer::Tokenizer;
     use jisx0401::Prefecture;
 
     #[test]
     fn new() {
-        let tokenizer = Tokenizer::new("東京都港区芝公園4丁目2-8");
+        let tokenizer = Tokenizer::new("東京都港区芝公園4丁目2-8", &Default::default());
         assert_eq!(tokenizer.tokens, vec![]);
         assert_eq!(tokenizer.rest, "東京都港区芝公園4丁目2-8");
     }
 
     #[test]
     fn new_異字体セレクタ除去() {
-        let tokenizer = Tokenizer::new("東京都葛\u{E0100}飾区立石5-13-1");
+        let tokenizer = Tokenizer::new("東京都葛\u{E0100}飾区立石5-13-1", &Default::default());
         assert_eq!(tokenizer.tokens, vec![]);
         assert_eq!(tokenizer.rest, "東京都葛飾区立石5-13-1")
     }
 
     #[test]
-    #[cfg(feature = "eliminate-whitespaces")]
     fn new_ホワイトスペース除却() {
-        let tokenizer = Tokenizer::new("東京都 目黒区 下目黒 4‐1‐1");
+        let options = ParseOptions::default().eliminate_whitespaces(true);
+        let tokenizer = Tokenizer::new("東京都 目黒区 下目黒 4‐1‐1", &options);
         assert_eq!(tokenizer.tokens, vec![]);
         assert_eq!(tokenizer.rest, "東京都目黒区下目黒4‐1‐1")
     }
 
     #[test]
-    #[cfg(feature = "fix-halfwidth-katakana")]
     fn new_半角カタカナを修正() {
-        let tokenizer = Tokenizer::new("東京都品川区旗ﾉ台２丁目");
+        let options = ParseOptions::default().fix_halfwidth_katakana(true);
+        let tokenizer = Tokenizer::new("東京都品川区旗ﾉ台２丁目", &options);
         assert_eq!(tokenizer.tokens, vec![]);
         assert_eq!(tokenizer.rest, "東京都品川区旗ノ台２丁目");
     }
 
+    #[test]
+    fn new_郵便番号を読み取る() {
+        let tokenizer = Tokenizer::new("〒105-0011 東京都港区芝公園4丁目2-8", &Default::default());
+        assert_eq!(
+            tokenizer.tokens,
+            vec![Token::PostalCode("105-0011".to_string())]
+        );
+        assert_eq!(tokenizer.rest, "東京都港区芝公園4丁目2-8");
+        let (_, tokenizer) = tokenizer.read_prefecture().unwrap();
+        assert_eq!(
+            tokenizer.tokens,
+            vec![
+                Token::PostalCode("105-0011".to_string()),
+                Token::Prefecture("東京都".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn read_prefecture_成功() {
-        let tokenizer = Tokenizer::new("東京都港区芝公園4丁目2-8");
+        let tokenizer = Tokenizer::new("東京都港区芝公園4丁目2-8", &Default::default());
         let result = tokenizer.read_prefecture();
         assert!(result.is_ok());
         let (prefecture, tokenizer) = result.unwrap();
@@ -96,9 +153,25 @@ mod tests {
         assert_eq!(tokenizer.rest, "港区芝公園4丁目2-8");
     }
 
+    #[test]
+    fn assume_prefecture_推定した都道府県名を補正として記録する() {
+        let tokenizer =
+            Tokenizer::new("横浜市中区本町1-1", &Default::default()).assume_prefecture("神奈川県");
+        assert_eq!(
+            tokenizer.tokens,
+            vec![Token::Prefecture("神奈川県".to_string())]
+        );
+        assert_eq!(tokenizer.rest, "横浜市中区本町1-1");
+        assert_eq!(
+            tokenizer.corrections[0].kind,
+            CorrectionKind::PrefectureCompletion
+        );
+        assert_eq!(tokenizer.finish().spans().prefecture, None);
+    }
+
     #[test]
     fn read_prefecture_失敗() {
-        let tokenizer = Tokenizer::new("東今日都港区芝公園4丁目2-8");
+        let tokenizer = Tokenizer::new("東今日都港区芝公園4丁目2-8", &Default::default());
         let result = tokenizer.read_prefecture();
         assert!(result.is_err());
         let tokenizer = result.unwrap_err();
@@ -2,6 +2,7 @@ use crate::adapter::orthographical_variant_adapter::{
     OrthographicalVariant, OrthographicalVariantAdapter,
 };
 use crate::domain::common::token::{append_token, Token};
+use crate::domain::geolonia::entity::{Correction, CorrectionKind};
 use crate::formatter::chome_with_arabic_numerals::format_chome_with_arabic_numerals;
 use crate::formatter::fullwidth_character::format_fullwidth_numerals;
 use crate::formatter::house_number::format_house_number;
@@ -9,91 +10,227 @@ use crate::formatter::informal_town_name_notation::format_informal_town_name_not
 use crate::formatter::prepend_aza::prepend_aza;
 use crate::formatter::prepend_oaza::prepend_oaza;
 use crate::formatter::{apply_all, Formatter};
-use crate::tokenizer::{CityNameFound, End, Tokenizer, TownNameFound};
+use crate::parser::ParseOptions;
+use crate::tokenizer::{consumed_text, Candidate, CityNameFound, End, Tokenizer, TownNameFound};
 use std::marker::PhantomData;
 
 impl Tokenizer<CityNameFound> {
     pub(crate) fn read_town(
         &self,
         candidates: Vec<String>,
+        options: &ParseOptions,
     ) -> Result<(String, Tokenizer<TownNameFound>), Tokenizer<End>> {
-        let mut rest = format_fullwidth_numerals(&self.rest);
+        let rest = self.normalized_rest();
+        for (pattern, correction_kinds) in formatter_sets_patterns() {
+            let Some(formatted) = apply_all(&rest, &pattern) else {
+                continue;
+            };
+            if let Some((town_name, town_rest, is_variant)) =
+                find_towns(&formatted, &candidates).next()
+            {
+                return Ok((
+                    town_name.clone(),
+                    self.town_name_found(
+                        town_name,
+                        town_rest,
+                        correction_kinds,
+                        is_variant,
+                        options,
+                    ),
+                ));
+            }
+        }
+        Err(self.finish())
+    }
+
+    /// 町名として解釈できる候補をすべて列挙する
+    ///
+    /// スコアの高い順に並べ、スコアが同じ場合はより長い町名を優先する。
+    pub(crate) fn read_town_candidates(
+        &self,
+        candidates: &[String],
+        options: &ParseOptions,
+    ) -> Vec<Candidate<TownNameFound>> {
+        let rest = self.normalized_rest();
+        let mut found: Vec<Candidate<TownNameFound>> = vec![];
+        for (pattern, correction_kinds) in formatter_sets_patterns() {
+            let Some(formatted) = apply_all(&rest, &pattern) else {
+                continue;
+            };
+            for (town_name, town_rest, is_variant) in find_towns(&formatted, candidates) {
+                // 補正の少ないパターンから順に試しているため、既に見つかった町名は無視する
+                if found.iter().any(|candidate| candidate.name == town_name) {
+                    continue;
+                }
+                let score = correction_kinds
+                    .iter()
+                    .map(|kind| kind.weight())
+                    .product::<f64>()
+                    * if is_variant {
+                        CorrectionKind::OrthographicalVariant.weight()
+                    } else {
+                        1.0
+                    };
+                found.push(Candidate {
+                    name: town_name.clone(),
+                    tokenizer: self.town_name_found(
+                        town_name,
+                        town_rest,
+                        correction_kinds.clone(),
+                        is_variant,
+                        options,
+                    ),
+                    score,
+                });
+            }
+        }
+        found.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap()
+                .then(b.name.chars().count().cmp(&a.name.chars().count()))
+        });
+        found
+    }
+
+    /// 町名を検出した後の`Tokenizer`を生成する
+    ///
+    /// 町名を検出するまでに行った補正を記録する。
+    /// 町名以降の部分は補正の影響を受けないため、補正前の文字列との長さの差から入力中の該当部分を求める。
+    fn town_name_found(
+        &self,
+        town_name: String,
+        town_rest: String,
+        mut correction_kinds: Vec<CorrectionKind>,
+        is_variant: bool,
+        options: &ParseOptions,
+    ) -> Tokenizer<TownNameFound> {
+        if is_variant {
+            correction_kinds.push(CorrectionKind::OrthographicalVariant);
+        }
+        let original = consumed_text(&self.rest, &town_rest);
+        Tokenizer {
+            tokens: append_token(&self.tokens, Token::Town(town_name.clone())),
+            offsets: self.offsets.push_token_end(&town_rest),
+            rest: format_rest(town_rest, options),
+            corrections: self.append_corrections(
+                correction_kinds
+                    .into_iter()
+                    .map(|kind| Correction {
+                        kind,
+                        original: original.clone(),
+                        replaced: town_name.clone(),
+                    })
+                    .collect(),
+            ),
+            _state: PhantomData::<TownNameFound>,
+        }
+    }
+
+    /// 数字を半角に揃え、「N丁目」のNを漢数字に変換する
+    fn normalized_rest(&self) -> String {
+        let rest = format_fullwidth_numerals(&self.rest);
         if rest.contains("丁目") {
-            rest = format_chome_with_arabic_numerals(&rest).unwrap_or(rest);
+            format_chome_with_arabic_numerals(&rest).unwrap_or(rest)
+        } else {
+            rest
         }
+    }
+}
 
-        let formatter_sets_patterns: Vec<Vec<Formatter>> = vec![
-            // ① 何も処理を行わない
-            vec![],
-            // ② 「〇〇町L-M-N」を「〇〇L丁目M-N」に変換する
+/// 町名を検出する際に試すformatterの組み合わせと、それぞれに対応する補正の種類
+fn formatter_sets_patterns() -> Vec<(Vec<Formatter>, Vec<CorrectionKind>)> {
+    use CorrectionKind::*;
+    vec![
+        // ① 何も処理を行わない
+        (vec![], vec![]),
+        // ② 「〇〇町L-M-N」を「〇〇L丁目M-N」に変換する
+        (
             vec![format_informal_town_name_notation],
-            // ③ 先頭に「大字」を補う
-            vec![prepend_oaza],
-            // ④ 先頭に「字」を補う
-            vec![prepend_aza],
-            // ⑤ 「〇〇L-M-N」を「〇〇L丁目M-N」に変換する かつ 先頭に「大字」を補う
+            vec![InformalTownNameNotation],
+        ),
+        // ③ 先頭に「大字」を補う
+        (vec![prepend_oaza], vec![OazaInsertion]),
+        // ④ 先頭に「字」を補う
+        (vec![prepend_aza], vec![AzaInsertion]),
+        // ⑤ 「〇〇L-M-N」を「〇〇L丁目M-N」に変換する かつ 先頭に「大字」を補う
+        (
             vec![format_informal_town_name_notation, prepend_oaza],
-            // ⑥ 「〇〇L-M-N」を「〇〇L丁目M-N」に変換する かつ 先頭に「字」を補う
+            vec![InformalTownNameNotation, OazaInsertion],
+        ),
+        // ⑥ 「〇〇L-M-N」を「〇〇L丁目M-N」に変換する かつ 先頭に「字」を補う
+        (
             vec![format_informal_town_name_notation, prepend_aza],
-        ];
-        for pattern in formatter_sets_patterns {
-            match apply_all(&rest, &pattern).and_then(|it| find_town(&it, &candidates)) {
-                None => continue,
-                Some((town_name, rest)) => {
-                    return Ok((
-                        town_name.clone(),
-                        Tokenizer {
-                            tokens: append_token(&self.tokens, Token::Town(town_name)),
-                            rest: if cfg!(feature = "format-house-number")
-                                && format_house_number(&rest).is_ok()
-                            {
-                                format_house_number(&rest).unwrap()
-                            } else {
-                                rest
-                            },
-                            _state: PhantomData::<TownNameFound>,
-                        },
-                    ))
-                }
-            }
-        }
-        Err(self.finish())
+            vec![InformalTownNameNotation, AzaInsertion],
+        ),
+    ]
+}
+
+/// 町名以降の文字列を整形する
+fn format_rest(rest: String, options: &ParseOptions) -> String {
+    if options.format_house_number && format_house_number(&rest).is_ok() {
+        format_house_number(&rest).unwrap()
+    } else {
+        rest
     }
 }
 
 /// Find out one of the most likely matches from the given candidates
+#[cfg(test)]
 fn find_town(input: &str, candidates: &[String]) -> Option<(String, String)> {
+    find_towns(input, candidates)
+        .next()
+        .map(|(town_name, rest, _)| (town_name, rest))
+}
+
+/// Find out all matches from the given candidates in order of likelihood
+///
+/// 町名、それ以降の文字列、表記ゆれの補正を行ったかどうかの組を返す。
+fn find_towns<'a>(
+    input: &'a str,
+    candidates: &'a [String],
+) -> impl Iterator<Item = (String, String, bool)> + 'a {
+    let adapter = town_variant_adapter();
+    sort_by_chome(candidates)
+        .into_iter()
+        .filter_map(move |candidate| {
+            if input.starts_with(candidate) {
+                return Some((
+                    candidate.to_string(),
+                    input.chars().skip(candidate.chars().count()).collect(),
+                    false,
+                ));
+            }
+            adapter
+                .apply(input, candidate)
+                .map(|(town_name, rest)| (town_name, rest, true))
+        })
+}
+
+/// 住居表示施行済みの候補から試すように並べ替える
+fn sort_by_chome(candidates: &[String]) -> Vec<&String> {
     // 候補の中から「丁目」を含むものとそれ以外のものを分類する
     let (contains_chome, not_contains): (Vec<&String>, Vec<&String>) = candidates
         .iter()
         .partition(|candidate| candidate.contains("丁目"));
-    // 住居表示施行済みの候補から試すようにする
-    let candidates = [contains_chome, not_contains].concat();
+    [contains_chome, not_contains].concat()
+}
 
-    for candidate in candidates {
-        if input.starts_with(candidate) {
-            return Some((
-                candidate.to_string(),
-                input.chars().skip(candidate.chars().count()).collect(),
-            ));
-        }
-        use OrthographicalVariant::*;
-        let adapter = OrthographicalVariantAdapter {
-            variant_list: vec![
-                の, ツ, ケ, 薮, 崎, 檜, 竈, 舘, 鰺, 脊, 渕, 己, 槇, 治, 佛, 澤, 恵, 穂, 梼, 蛍, 與,
-                瀧, 籠, 濱, 祗, 曾, 國, 鉋, 鷆, 斑, 櫻, 櫟, 冨, 鶯, 龍, 廣, 塚, 麴, 炮, 邇, 遙, 溪,
-            ],
-        };
-        if let Some(result) = adapter.apply(input, candidate) {
-            return Some(result);
-        };
+fn town_variant_adapter() -> OrthographicalVariantAdapter {
+    use OrthographicalVariant::*;
+    OrthographicalVariantAdapter {
+        variant_list: vec![
+            の, ツ, ケ, 薮, 崎, 檜, 竈, 舘, 鰺, 脊, 渕, 己, 槇, 治, 佛, 澤, 恵, 穂, 梼, 蛍, 與, 瀧,
+            籠, 濱, 祗, 曾, 國, 鉋, 鷆, 斑, 櫻, 櫟, 冨, 鶯, 龍, 廣, 塚, 麴, 炮, 邇, 遙, 溪,
+        ],
     }
-    None
 }
 
 #[cfg(test)]
 mod tests {
     use crate::domain::common::token::Token;
+    use crate::domain::geolonia::entity::{Correction, CorrectionKind};
+    use crate::parser::ParseOptions;
     use crate::tokenizer::read_town::find_town;
     use crate::tokenizer::{CityNameFound, Tokenizer};
     use std::marker::PhantomData;
@@ -130,6 +267,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn read_town_candidates_前方一致する候補をすべて返す() {
+        let tokenizer = Tokenizer {
+            tokens: vec![
+                Token::Prefecture("福岡県".to_string()),
+                Token::City("北九州市小倉北区".to_string()),
+            ],
+            rest: "下多良二丁目137".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
+            _state: PhantomData::<CityNameFound>,
+        };
+        let candidates = tokenizer.read_town_candidates(
+            &[
+                "下多良".to_string(),
+                "下多良一丁目".to_string(),
+                "下多良二丁目".to_string(),
+            ],
+            &Default::default(),
+        );
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].name, "下多良二丁目");
+        assert_eq!(candidates[0].tokenizer.rest, "137");
+        assert_eq!(candidates[1].name, "下多良");
+        assert_eq!(candidates[1].tokenizer.rest, "二丁目137");
+    }
+
+    #[test]
+    fn read_town_candidates_大字を補った場合() {
+        let tokenizer = Tokenizer {
+            tokens: vec![
+                Token::Prefecture("福島県".to_string()),
+                Token::City("西白河郡西郷村".to_string()),
+            ],
+            rest: "熊倉字折口原40番地".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
+            _state: PhantomData::<CityNameFound>,
+        };
+        let candidates = tokenizer.read_town_candidates(
+            &["大字熊倉".to_string(), "大字小田倉".to_string()],
+            &Default::default(),
+        );
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].name, "大字熊倉");
+        assert_eq!(candidates[0].score, 0.9);
+        assert_eq!(
+            candidates[0].tokenizer.corrections,
+            vec![Correction {
+                kind: CorrectionKind::OazaInsertion,
+                original: "熊倉".to_string(),
+                replaced: "大字熊倉".to_string(),
+            }]
+        );
+    }
+
     #[test]
     fn read_town_成功() {
         let tokenizer = Tokenizer {
@@ -138,15 +331,20 @@ mod tests {
                 Token::City("静岡市清水区".to_string()),
             ],
             rest: "旭町6番8号".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameFound>,
         };
-        let result = tokenizer.read_town(vec![
-            "下野緑町".to_string(),
-            "承元寺町".to_string(),
-            "旭町".to_string(),
-            "新丹谷".to_string(),
-            "三保松原町".to_string(),
-        ]);
+        let result = tokenizer.read_town(
+            vec![
+                "下野緑町".to_string(),
+                "承元寺町".to_string(),
+                "旭町".to_string(),
+                "新丹谷".to_string(),
+                "三保松原町".to_string(),
+            ],
+            &Default::default(),
+        );
         assert!(result.is_ok());
         let (town_name, tokenizer) = result.unwrap();
         assert_eq!(town_name, "旭町");
@@ -154,6 +352,27 @@ mod tests {
         assert_eq!(tokenizer.rest, "6番8号");
     }
 
+    #[test]
+    fn read_town_町名以降を整形する() {
+        for (format_house_number, expected_rest) in [(true, "6番8号"), (false, "6-8")] {
+            let tokenizer = Tokenizer {
+                tokens: vec![
+                    Token::Prefecture("静岡県".to_string()),
+                    Token::City("静岡市清水区".to_string()),
+                ],
+                rest: "旭町6-8".to_string(),
+                corrections: vec![],
+                offsets: Default::default(),
+                _state: PhantomData::<CityNameFound>,
+            };
+            let options = ParseOptions::default().format_house_number(format_house_number);
+            let (_, tokenizer) = tokenizer
+                .read_town(vec!["旭町".to_string()], &options)
+                .unwrap();
+            assert_eq!(tokenizer.rest, expected_rest);
+        }
+    }
+
     #[test]
     fn read_town_orthographical_variant_adapterで成功() {
         let tokenizer = Tokenizer {
@@ -162,20 +381,33 @@ mod tests {
                 Token::City("千代田区".to_string()),
             ],
             rest: "一ッ橋二丁目1番".to_string(), // 「ッ」と「ツ」の表記ゆれ
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameFound>,
         };
-        let result = tokenizer.read_town(vec![
-            "神田錦町一丁目".to_string(),
-            "神田錦町二丁目".to_string(),
-            "神田錦町三丁目".to_string(),
-            "一ツ橋一丁目".to_string(),
-            "一ツ橋二丁目".to_string(),
-        ]);
+        let result = tokenizer.read_town(
+            vec![
+                "神田錦町一丁目".to_string(),
+                "神田錦町二丁目".to_string(),
+                "神田錦町三丁目".to_string(),
+                "一ツ橋一丁目".to_string(),
+                "一ツ橋二丁目".to_string(),
+            ],
+            &Default::default(),
+        );
         assert!(result.is_ok());
         let (town_name, tokenizer) = result.unwrap();
         assert_eq!(town_name, "一ツ橋二丁目");
         assert_eq!(tokenizer.tokens.len(), 3);
         assert_eq!(tokenizer.rest, "1番");
+        assert_eq!(
+            tokenizer.corrections,
+            vec![Correction {
+                kind: CorrectionKind::OrthographicalVariant,
+                original: "一ッ橋二丁目".to_string(),
+                replaced: "一ツ橋二丁目".to_string(),
+            }]
+        );
     }
 
     #[test]
@@ -186,16 +418,21 @@ mod tests {
                 Token::City("京都市東山区".to_string()),
             ],
             rest: "本町22丁目489番".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameFound>,
         };
-        let result = tokenizer.read_town(vec![
-            "本町十九丁目".to_string(),
-            "本町二十丁目".to_string(),
-            "本町二十一丁目".to_string(),
-            "本町二十二丁目".to_string(),
-            "本町新五丁目".to_string(),
-            "本町新六丁目".to_string(),
-        ]);
+        let result = tokenizer.read_town(
+            vec![
+                "本町十九丁目".to_string(),
+                "本町二十丁目".to_string(),
+                "本町二十一丁目".to_string(),
+                "本町二十二丁目".to_string(),
+                "本町新五丁目".to_string(),
+                "本町新六丁目".to_string(),
+            ],
+            &Default::default(),
+        );
         assert!(result.is_ok());
         let (town_name, tokenizer) = result.unwrap();
         assert_eq!(town_name, "本町二十二丁目");
@@ -211,9 +448,14 @@ mod tests {
                 Token::City("西多摩郡日の出町".to_string()),
             ],
             rest: "平井2780番地".to_string(), // 「大字」が省略されている
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameFound>,
         };
-        let result = tokenizer.read_town(vec!["大字大久野".to_string(), "大字平井".to_string()]);
+        let result = tokenizer.read_town(
+            vec!["大字大久野".to_string(), "大字平井".to_string()],
+            &Default::default(),
+        );
         assert!(result.is_ok());
         let (town_name, tokenizer) = result.unwrap();
         assert_eq!(town_name, "大字平井");
@@ -229,14 +471,19 @@ mod tests {
                 Token::City("南埼玉郡宮代町".to_string()),
             ],
             rest: "東粂原111".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameFound>,
         };
-        let result = tokenizer.read_town(vec![
-            "東姫宮一丁目".to_string(),
-            "字東".to_string(),
-            "字宮東".to_string(),
-            "大字東粂原".to_string(),
-        ]);
+        let result = tokenizer.read_town(
+            vec![
+                "東姫宮一丁目".to_string(),
+                "字東".to_string(),
+                "字宮東".to_string(),
+                "大字東粂原".to_string(),
+            ],
+            &Default::default(),
+        );
         assert!(result.is_ok());
         let (town_name, tokenizer) = result.unwrap();
         assert_eq!(town_name, "大字東粂原");
@@ -252,15 +499,20 @@ mod tests {
                 Token::City("静岡市清水区".to_string()),
             ],
             rest: "".to_string(),
+            corrections: vec![],
+            offsets: Default::default(),
             _state: PhantomData::<CityNameFound>,
         };
-        let result = tokenizer.read_town(vec![
-            "下野緑町".to_string(),
-            "承元寺町".to_string(),
-            "旭町".to_string(),
-            "新丹谷".to_string(),
-            "三保松原町".to_string(),
-        ]);
+        let result = tokenizer.read_town(
+            vec![
+                "下野緑町".to_string(),
+                "承元寺町".to_string(),
+                "旭町".to_string(),
+                "新丹谷".to_string(),
+                "三保松原町".to_string(),
+            ],
+            &Default::default(),
+        );
         assert!(result.is_err());
         let tokenizer = result.unwrap_err();
         assert_eq!(tokenizer.tokens.len(), 3);
@@ -1,91 +1,187 @@
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use crate::http::cache::{CacheEntry, CacheStore};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use web_time::Instant;
 
 #[derive(Clone)]
-pub(crate) struct CacheEntry {
+struct Slot {
     /// データ
-    pub body: Vec<u8>,
+    entry: CacheEntry,
     /// キャッシュに登録した時刻
-    pub registered_at: Instant,
+    registered_at: Instant,
+    /// 最後に参照した順番
+    last_used: u64,
 }
 
-pub(crate) struct InMemoryCache {
+/// LRU(Least Recently Used)方式で管理するエントリの集合
+#[derive(Default)]
+struct Store {
+    slots: HashMap<String, Slot>,
+    /// 最後に参照した順番からキーを引くための索引
+    recency: BTreeMap<u64, String>,
+    /// 参照するたびに増える通し番号
+    clock: u64,
+}
+
+impl Store {
+    /// エントリを参照し、最も新しく使ったものとして記録する
+    fn touch(&mut self, key: &str) -> Option<&Slot> {
+        self.clock += 1;
+        let slot = self.slots.get_mut(key)?;
+        self.recency.remove(&slot.last_used);
+        self.recency.insert(self.clock, key.to_string());
+        slot.last_used = self.clock;
+        Some(slot)
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Slot> {
+        let slot = self.slots.remove(key)?;
+        self.recency.remove(&slot.last_used);
+        Some(slot)
+    }
+
+    /// 最も長く使われていないエントリを削除する
+    fn remove_least_recently_used(&mut self) -> Option<Slot> {
+        let (_, key) = self.recency.pop_first()?;
+        self.slots.remove(&key)
+    }
+}
+
+/// In-memory `CacheStore`
+///
+/// プロセス内でのみ有効なキャッシュです。保持期間の初期値は1時間、最大容量の初期値は100件です。
+/// 最大容量を超えた場合は、最も長く参照されていないエントリから削除します。
+pub struct InMemoryCache {
     /// キャッシュストア
-    store: Arc<RwLock<HashMap<String, CacheEntry>>>,
+    store: Arc<Mutex<Store>>,
     /// キャッシュの保持期間
     ttl: Duration,
     /// キャッシュの最大容量
     max_entries: usize,
+    /// 最大容量を超えたために削除したエントリの件数
+    evictions: AtomicU64,
+}
+
+impl Default for InMemoryCache {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl InMemoryCache {
     /// キャッシュの初期化
     pub fn new() -> Self {
-        Self {
-            store: Arc::new(RwLock::new(HashMap::new())),
-            ttl: Duration::from_secs(3600),
-            max_entries: 100,
-        }
+        Self::with_config(Duration::from_secs(3600), 100)
     }
 
     /// キャッシュの初期化(カスタム)
     pub fn with_config(ttl: Duration, max_entries: usize) -> Self {
         assert!(max_entries > 0, "max_entries must be greater than 0");
         Self {
-            store: Arc::new(RwLock::new(HashMap::new())),
+            store: Default::default(),
             ttl,
             max_entries,
+            evictions: AtomicU64::new(0),
         }
     }
 
+    /// キャッシュデータの登録
+    pub fn register(&self, key: &str, value: Vec<u8>) {
+        self.insert(
+            key,
+            CacheEntry {
+                body: value,
+                ..Default::default()
+            },
+        );
+    }
+
+    /// 登録されているエントリの件数
+    pub fn len(&self) -> usize {
+        self.lock().slots.len()
+    }
+
+    /// エントリが登録されていない場合は`true`を返します。
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Store> {
+        self.store
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn slot(&self, key: &str) -> Option<Slot> {
+        self.lock().touch(key).cloned()
+    }
+}
+
+impl CacheStore for InMemoryCache {
     /// キャッシュデータの取得
-    pub fn get(&self, key: &str) -> Option<CacheEntry> {
-        let store = self
-            .store
-            .read()
-            .unwrap_or_else(|poisoned| poisoned.into_inner());
-
-        let entry = store.get(key).cloned()?;
-        if entry.registered_at.elapsed() < self.ttl {
-            Some(entry)
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let slot = self.slot(key)?;
+        if slot.registered_at.elapsed() < self.ttl {
+            Some(slot.entry)
         } else {
             None
         }
     }
 
-    /// キャッシュデータの登録
-    pub fn register(&self, key: &str, value: Vec<u8>) {
-        let mut store = self
-            .store
-            .write()
-            .unwrap_or_else(|poisoned| poisoned.into_inner());
-
-        // まず、期限切れのデータを一掃する
-        store.retain(|_, entry| entry.registered_at.elapsed() < self.ttl);
-
-        // キャッシュの最大容量を超える場合は、キャッシュに登録した時刻が最も古いものが削除される
-        if store.len() >= self.max_entries && !store.contains_key(key) {
-            if let Some(oldest_key) = store
-                .iter()
-                .min_by_key(|(_, value)| value.registered_at)
-                .map(|(key, _)| key.to_string())
-            {
-                store.remove(&oldest_key);
+    fn get_stale(&self, key: &str) -> Option<CacheEntry> {
+        self.slot(key).map(|slot| slot.entry)
+    }
+
+    fn insert(&self, key: &str, entry: CacheEntry) {
+        let mut store = self.lock();
+
+        // キャッシュの最大容量を超える場合は、最も長く参照されていないものが削除される
+        // 期限切れのデータも再検証に使用するため、容量を超えるまでは残しておく
+        store.remove(key);
+        while store.slots.len() >= self.max_entries {
+            if store.remove_least_recently_used().is_none() {
+                break;
             }
+            self.evictions.fetch_add(1, Ordering::Relaxed);
         }
 
-        let entry = CacheEntry {
-            body: value,
-            registered_at: Instant::now(),
-        };
-        store.insert(key.to_string(), entry);
+        store.clock += 1;
+        let last_used = store.clock;
+        store.recency.insert(last_used, key.to_string());
+        store.slots.insert(
+            key.to_string(),
+            Slot {
+                entry,
+                registered_at: Instant::now(),
+                last_used,
+            },
+        );
+    }
+
+    fn remove(&self, key: &str) {
+        self.lock().remove(key);
+    }
+
+    fn clear(&self) {
+        let mut store = self.lock();
+        store.slots.clear();
+        store.recency.clear();
+    }
+
+    fn evictions(&self) -> u64 {
+        self.evictions.load(Ordering::Relaxed)
+    }
+
+    fn max_entries(&self) -> Option<usize> {
+        Some(self.max_entries)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::http::cache::CacheStore;
     use crate::util::inmemory_cache::InMemoryCache;
     use std::ops::Add;
     use std::time::Duration;
@@ -129,16 +225,51 @@ mod tests {
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
-    fn キャッシュ最大容量を超えた場合は最古のエントリが削除されること() {
+    fn キャッシュ最大容量を超えた場合は最も長く参照されていないエントリが削除されること() {
         let cache = InMemoryCache::with_config(Duration::from_secs(3600), 3);
         cache.register("key1", vec![1, 2, 3]);
         cache.register("key2", vec![4, 5, 6]);
         cache.register("key3", vec![7, 8, 9]);
 
+        // key1を参照したので、最も長く参照されていないのはkey2になる
         assert!(cache.get("key1").is_some());
 
         cache.register("key4", vec![0, 0, 0]);
-        assert!(cache.get("key1").is_none());
+        assert!(cache.get("key2").is_none());
+        assert!(cache.get("key1").is_some());
+        assert!(cache.get("key3").is_some());
         assert!(cache.get("key4").is_some());
+        assert_eq!(cache.evictions(), 1);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn 登録済みのキーを登録し直しても削除されないこと() {
+        let cache = InMemoryCache::with_config(Duration::from_secs(3600), 2);
+        cache.register("key1", vec![1]);
+        cache.register("key2", vec![2]);
+        cache.register("key1", vec![3]);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("key1").unwrap().body, vec![3]);
+        assert!(cache.get("key2").is_some());
+        assert_eq!(cache.evictions(), 0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn 指定したエントリと全てのエントリを削除できること() {
+        let cache = InMemoryCache::new();
+        cache.register("key1", vec![1]);
+        cache.register("key2", vec![2]);
+        cache.register("key3", vec![3]);
+
+        cache.remove("key1");
+        assert!(cache.get_stale("key1").is_none());
+        assert_eq!(cache.len(), 2);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(cache.get_stale("key2").is_none());
     }
 }
@@ -53,6 +53,37 @@ impl SequenceMatcher {
         }
     }
 
+    /// 類似度が閾値を上回る候補を、類似度の高い順にすべて返す
+    ///
+    /// 類似度の補正は`get_most_similar_match`と同様に行う。
+    pub fn get_similar_matches(
+        input: &str,
+        possibilities: &[String],
+        threshold: Option<f64>,
+    ) -> Vec<(String, f64)> {
+        let Some(length_of_longest_possibility) = Self::get_length_of_longest_one(possibilities)
+        else {
+            return vec![];
+        };
+        let cut_input = Self::cut_text(input, length_of_longest_possibility);
+        let mut matches: Vec<(String, f64)> = possibilities
+            .iter()
+            .filter_map(|possibility| {
+                let similarity = Self::evaluate_match_ratio(possibility, &cut_input);
+                if similarity <= threshold.unwrap_or(0.0) {
+                    return None;
+                }
+                if input.starts_with(&trim_city_name(possibility)) {
+                    Some((possibility.clone(), similarity))
+                } else {
+                    Some((possibility.clone(), similarity * 0.9))
+                }
+            })
+            .collect();
+        matches.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        matches
+    }
+
     fn get_most_similar_matches(
         input: &str,
         possibilities: &[String],
@@ -105,7 +136,7 @@ mod tests {
     #[test]
     fn get_length_of_longest_one() {
         let fukushima = Prefecture::fukushima();
-        assert_eq!(SequenceMatcher::get_length_of_longest_one(&vec![]), None);
+        assert_eq!(SequenceMatcher::get_length_of_longest_one(&[]), None);
         assert_eq!(
             SequenceMatcher::get_length_of_longest_one(&fukushima.cities),
             Some(8)
@@ -187,11 +218,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_similar_matches_類似度の高い順に返す() {
+        let possibilities = vec![
+            "周智郡森町".to_string(),
+            "茅部郡森町".to_string(),
+            "福島市".to_string(),
+        ];
+        let result = SequenceMatcher::get_similar_matches("森町", &possibilities, None);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, "周智郡森町");
+        assert_eq!(result[1].0, "茅部郡森町");
+        assert_eq!(result[0].1, result[1].1);
+        assert!(SequenceMatcher::get_similar_matches("森町", &[], None).is_empty());
+    }
+
     #[test]
     fn get_most_similar_match_マッチ候補が一つもない場合() {
         let result = SequenceMatcher::get_most_similar_match(
             "上町",
-            &vec!["上村".to_string(), "下町".to_string()],
+            &["上村".to_string(), "下町".to_string()],
             Some(0.9),
         );
         assert!(result.is_err());
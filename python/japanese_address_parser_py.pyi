@@ -7,9 +7,10 @@ class ParseResult:
 
     address: dict[str, str]
     """
-    都道府県名、市区町村名、町名、それ以降の文字列をそれぞれ格納する辞書型を返します。
+    都道府県名、市区町村名、町名、小字・通称名、それ以降の文字列、郵便番号をそれぞれ格納する辞書型を返します。
+    小字・通称名や郵便番号が記載されていない場合、koazaやpostal_codeは空文字になります。
     
-    {prefecture: str, city: str, town: str, rest: str}
+    {prefecture: str, city: str, town: str, koaza: str, rest: str, postal_code: str}
     """
 
     error: dict[str, str]
@@ -19,6 +20,25 @@ class ParseResult:
     {error_type: str, error_message: str}
     """
 
+    corrections: list[dict[str, str]]
+    """
+    住所を解釈する過程で入力に対して行った補正(郡名の補完、表記ゆれの補正など)を格納するリストを返します。
+    
+    [{kind: str, original: str, replaced: str}]
+    """
+
+    spans: dict[str, tuple[int, int] | None]
+    """
+    郵便番号、都道府県名、市区町村名、町名、小字・通称名、それ以降の文字列が入力中のどの位置にあたるかを(開始位置, 終了位置)の形で格納する辞書型を返します。
+    
+    {postal_code: (int, int) | None, prefecture: (int, int) | None, city: (int, int) | None, town: (int, int) | None, koaza: (int, int) | None, rest: (int, int) | None}
+    """
+
+    coordinate: tuple[float, float, str] | None
+    """
+    住所の代表点を(緯度, 経度, 精度)の形で返します。精度は"Town"、"City"、"Prefecture"のいずれかです。
+    """
+
 
 def parse(address: str) -> ParseResult:
     """
@@ -31,6 +51,17 @@ def parse(address: str) -> ParseResult:
     """
 
 
+def parse_many(addresses: list[str]) -> list[ParseResult]:
+    """
+    Format informal addresses into formal style in a batch
+
+    複数の住所をまとめて整形します。住所マスタは一度だけ取得され、結果は入力と同じ順に返されます。
+
+    :param addresses: 住所のリスト
+    :return: list[ParseResult]
+    """
+
+
 async def parse_async(address: str) -> ParseResult:
     """
     Format informal address into formal style (async)
@@ -43,12 +74,25 @@ async def parse_async(address: str) -> ParseResult:
 
 
 class Parser:
-    def __new__(cls) -> Parser:
+    def __new__(
+        cls,
+        *,
+        infer_prefecture: bool | None = None,
+        city_name_correction: bool | None = None,
+        format_house_number: bool | None = None,
+        eliminate_whitespaces: bool | None = None,
+        fix_halfwidth_katakana: bool | None = None,
+    ) -> Parser:
         """
         Construct a parser.
     
-        パーサーを生成します。
+        パーサーを生成します。省略したオプションはデフォルトの設定になります。
 
+        :param infer_prefecture: 都道府県名が省略されている場合に市区町村名から推定する
+        :param city_name_correction: 郡名が省略された市区町村名を補正する
+        :param format_house_number: 町名以降の「1-2」のような表記を「1番2号」の形に整形する
+        :param eliminate_whitespaces: 住所に含まれる空白文字を取り除く
+        :param fix_halfwidth_katakana: 住所に含まれる半角カタカナを全角カタカナに変換する
         :return: JapaneseAddressParser
         """
 
@@ -62,6 +106,16 @@ class Parser:
         :return: ParseResult
         """
 
+    def parse_many(self, addresses: list[str]) -> list[ParseResult]:
+        """
+        Format informal addresses into formal style in a batch
+
+        複数の住所をまとめて整形します。住所マスタは一度だけ取得され、結果は入力と同じ順に返されます。
+
+        :param addresses: 住所のリスト
+        :return: list[ParseResult]
+        """
+
     async def parse_async(self, address: str) -> ParseResult:
         """
         Format informal address into formal style (async)
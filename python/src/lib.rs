@@ -2,8 +2,9 @@ use pyo3::prelude::*;
 use std::collections::HashMap;
 use std::sync::{Arc, OnceLock};
 
+use japanese_address_parser::domain::geolonia::entity::Span;
 use japanese_address_parser::parser::ParseResult;
-use japanese_address_parser::parser::Parser;
+use japanese_address_parser::parser::{ParseOptions, Parser};
 
 #[pyclass(name = "ParseResult")]
 struct PyParseResult {
@@ -11,6 +12,12 @@ struct PyParseResult {
     address: HashMap<String, String>,
     #[pyo3(get)]
     error: HashMap<String, String>,
+    #[pyo3(get)]
+    corrections: Vec<HashMap<String, String>>,
+    #[pyo3(get)]
+    spans: HashMap<String, Option<(usize, usize)>>,
+    #[pyo3(get)]
+    coordinate: Option<(f64, f64, String)>,
 }
 
 impl From<ParseResult> for PyParseResult {
@@ -19,7 +26,12 @@ impl From<ParseResult> for PyParseResult {
             ("prefecture".to_string(), value.address.prefecture),
             ("city".to_string(), value.address.city),
             ("town".to_string(), value.address.town),
+            ("koaza".to_string(), value.address.koaza),
             ("rest".to_string(), value.address.rest),
+            (
+                "postal_code".to_string(),
+                value.address.postal_code.unwrap_or_default(),
+            ),
         ]);
         let error = value.error.map_or_else(HashMap::new, |err| {
             HashMap::from([
@@ -27,7 +39,43 @@ impl From<ParseResult> for PyParseResult {
                 ("error_message".to_string(), err.error_message),
             ])
         });
-        Self { address, error }
+        let corrections = value
+            .corrections
+            .into_iter()
+            .map(|correction| {
+                HashMap::from([
+                    ("kind".to_string(), format!("{:?}", correction.kind)),
+                    ("original".to_string(), correction.original),
+                    ("replaced".to_string(), correction.replaced),
+                ])
+            })
+            .collect();
+        // Pythonの文字列のインデックスに合わせて文字単位の位置を返す
+        let char_span = |span: Option<Span>| span.map(|span| (span.char_start, span.char_end));
+        let spans = HashMap::from([
+            (
+                "postal_code".to_string(),
+                char_span(value.spans.postal_code),
+            ),
+            ("prefecture".to_string(), char_span(value.spans.prefecture)),
+            ("city".to_string(), char_span(value.spans.city)),
+            ("town".to_string(), char_span(value.spans.town)),
+            ("koaza".to_string(), char_span(value.spans.koaza)),
+            ("rest".to_string(), char_span(value.spans.rest)),
+        ]);
+        Self {
+            address,
+            error,
+            corrections,
+            spans,
+            coordinate: value.coordinate.map(|coordinate| {
+                (
+                    coordinate.latitude,
+                    coordinate.longitude,
+                    format!("{:?}", coordinate.precision),
+                )
+            }),
+        }
     }
 }
 
@@ -39,9 +87,33 @@ struct PyParser {
 #[pymethods]
 impl PyParser {
     #[new]
-    fn default() -> Self {
+    #[pyo3(signature = (*, infer_prefecture=None, city_name_correction=None, format_house_number=None, eliminate_whitespaces=None, fix_halfwidth_katakana=None))]
+    fn new(
+        infer_prefecture: Option<bool>,
+        city_name_correction: Option<bool>,
+        format_house_number: Option<bool>,
+        eliminate_whitespaces: Option<bool>,
+        fix_halfwidth_katakana: Option<bool>,
+    ) -> Self {
+        // 指定されなかったオプションはデフォルトの値のままにする
+        let mut options = ParseOptions::default();
+        if let Some(enabled) = infer_prefecture {
+            options = options.infer_prefecture(enabled);
+        }
+        if let Some(enabled) = city_name_correction {
+            options = options.city_name_correction(enabled);
+        }
+        if let Some(enabled) = format_house_number {
+            options = options.format_house_number(enabled);
+        }
+        if let Some(enabled) = eliminate_whitespaces {
+            options = options.eliminate_whitespaces(enabled);
+        }
+        if let Some(enabled) = fix_halfwidth_katakana {
+            options = options.fix_halfwidth_katakana(enabled);
+        }
         PyParser {
-            parser: Arc::new(Default::default()),
+            parser: Arc::new(Parser::default().options(options)),
         }
     }
 
@@ -50,6 +122,16 @@ impl PyParser {
         py.detach(|| self.parser.parse_blocking(address)).into()
     }
 
+    fn parse_many(&self, py: Python<'_>, addresses: Vec<String>) -> Vec<PyParseResult> {
+        py.detach(|| {
+            self.parser
+                .parse_many_blocking(addresses.iter().map(String::as_str))
+        })
+        .into_iter()
+        .map(PyParseResult::from)
+        .collect()
+    }
+
     fn parse_async<'py>(&self, py: Python<'py>, address: String) -> PyResult<Bound<'py, PyAny>> {
         let parser = Arc::clone(&self.parser);
         pyo3_async_runtimes::tokio::future_into_py(py, async move {
@@ -71,6 +153,14 @@ fn parse(py: Python<'_>, address: &str) -> PyParseResult {
     py.detach(|| get_parser().parse_blocking(address)).into()
 }
 
+#[pyfunction]
+fn parse_many(py: Python<'_>, addresses: Vec<String>) -> Vec<PyParseResult> {
+    py.detach(|| get_parser().parse_many_blocking(addresses.iter().map(String::as_str)))
+        .into_iter()
+        .map(PyParseResult::from)
+        .collect()
+}
+
 #[pyfunction]
 fn parse_async<'py>(py: Python<'py>, address: String) -> PyResult<Bound<'py, PyAny>> {
     pyo3_async_runtimes::tokio::future_into_py(py, async move {
@@ -86,6 +176,7 @@ fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyParseResult>()?;
     m.add_class::<PyParser>()?;
     m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_many, m)?)?;
     m.add_function(wrap_pyfunction!(parse_async, m)?)?;
     Ok(())
 }
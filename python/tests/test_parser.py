@@ -19,3 +19,13 @@ def test_parser_class():
     assert result.address["town"] == "字円明寺"
     assert result.address["rest"] == "小字夏目3"
     assert result.error == {}
+
+
+def test_parser_class_with_options():
+    parser = Parser(eliminate_whitespaces=True, format_house_number=True)
+    result = parser.parse("東京都 目黒区 上目黒 2-19-15")
+    assert result.address["prefecture"] == "東京都"
+    assert result.address["city"] == "目黒区"
+    assert result.address["town"] == "上目黒二丁目"
+    assert result.address["rest"] == "19番15号"
+    assert result.error == {}
@@ -10,6 +10,7 @@ pub struct Record {
     pub prefecture: String,
     pub city: String,
     pub town: String,
+    pub koaza: String,
     pub rest: String,
 }
 
@@ -35,6 +36,7 @@ pub async fn run_data_driven_tests(file_path: &str) {
             assert_eq!(result.address.prefecture, record.prefecture);
             assert_eq!(result.address.city, record.city);
             assert_eq!(result.address.town, record.town);
+            assert_eq!(result.address.koaza, record.koaza);
             assert_eq!(result.address.rest, record.rest);
         });
         match test_result {
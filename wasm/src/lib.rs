@@ -1,9 +1,17 @@
 #[cfg(feature = "nightly")]
 mod nightly;
 
+#[cfg(feature = "embedded-master")]
+use japanese_address_parser::http::cached_client::CachedApiClient;
+#[cfg(feature = "embedded-master")]
+use japanese_address_parser::http::reqwest_client::ReqwestApiClient;
 use japanese_address_parser::parser;
+use japanese_address_parser::parser::ParseOptions;
+#[cfg(feature = "embedded-master")]
+use japanese_address_parser::source::embedded::EmbeddedSource;
+use serde::Deserialize;
 use wasm_bindgen::prelude::wasm_bindgen;
-use wasm_bindgen::JsValue;
+use wasm_bindgen::{JsError, JsValue};
 
 #[wasm_bindgen(start)]
 fn start() {
@@ -18,44 +26,182 @@ const TYPESCRIPT_TYPE: &'static str = r#"
 export interface ParseResult {
     address: Address;
     error: Error | undefined;
+    corrections: Correction[];
+    spans: AddressSpans;
+    coordinate: Coordinate | undefined;
 }
 export interface Address {
     prefecture: string;
     city: string;
     town: string;
+    koaza: string;
     rest: string;
+    rest_components: RestComponents;
+    postal_code: string | undefined;
+}
+export interface RestComponents {
+    block_number: string | undefined;
+    house_number: string | undefined;
+    building_name: string | undefined;
+    room_number: string | undefined;
 }
 export interface Error {
     error_type: string;
     error_message: string;
 }
+export interface Coordinate {
+    latitude: number;
+    longitude: number;
+    precision: "Prefecture" | "City" | "Town";
+}
+export interface Span {
+    byte_start: number;
+    byte_end: number;
+    char_start: number;
+    char_end: number;
+}
+export interface AddressSpans {
+    postal_code: Span | undefined;
+    prefecture: Span | undefined;
+    city: Span | undefined;
+    town: Span | undefined;
+    koaza: Span | undefined;
+    rest: Span | undefined;
+}
+export interface Correction {
+    kind: "PrefectureCompletion" | "CountyNameCompletion" | "OrthographicalVariant" | "InformalTownNameNotation" | "OazaInsertion" | "AzaInsertion";
+    original: string;
+    replaced: string;
+}
+export interface ParserOptions {
+    inferPrefecture?: boolean;
+    cityNameCorrection?: boolean;
+    formatHouseNumber?: boolean;
+    eliminateWhitespaces?: boolean;
+    fixHalfwidthKatakana?: boolean;
+}
 export class Parser {
   free(): void;
-  constructor();
+  /**
+  * @param {ParserOptions | undefined} options
+  * @throws {Error} if `options` is not a valid `ParserOptions`
+  */
+  constructor(options?: ParserOptions);
   /**
   * @param {string} address
   * @returns {Promise<ParseResult>}
   */
   parse(address: string): Promise<ParseResult>;
+  /**
+  * @param {string[]} addresses
+  * @returns {Promise<ParseResult[]>}
+  */
+  parse_many(addresses: string[]): Promise<ParseResult[]>;
 }"#;
 
+#[cfg(feature = "embedded-master")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPE_EMBEDDED: &'static str = r#"
+export namespace Parser {
+  /**
+  * Uses the master data embedded at build time and makes no network requests.
+  * @param {ParserOptions | undefined} options
+  * @throws {Error} if `options` is not a valid `ParserOptions`
+  */
+  function embedded(options?: ParserOptions): Parser;
+}"#;
+
+/// 省略されたオプションはデフォルトの値のままにする
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Options {
+    infer_prefecture: Option<bool>,
+    city_name_correction: Option<bool>,
+    format_house_number: Option<bool>,
+    eliminate_whitespaces: Option<bool>,
+    fix_halfwidth_katakana: Option<bool>,
+}
+
+impl From<Options> for ParseOptions {
+    fn from(value: Options) -> Self {
+        let mut options = ParseOptions::default();
+        if let Some(enabled) = value.infer_prefecture {
+            options = options.infer_prefecture(enabled);
+        }
+        if let Some(enabled) = value.city_name_correction {
+            options = options.city_name_correction(enabled);
+        }
+        if let Some(enabled) = value.format_house_number {
+            options = options.format_house_number(enabled);
+        }
+        if let Some(enabled) = value.eliminate_whitespaces {
+            options = options.eliminate_whitespaces(enabled);
+        }
+        if let Some(enabled) = value.fix_halfwidth_katakana {
+            options = options.fix_halfwidth_katakana(enabled);
+        }
+        options
+    }
+}
+
+impl Options {
+    /// オプションの形式が正しくない場合は、パニックせずにJavaScriptの例外を投げる
+    fn from_js(options: JsValue) -> Result<Self, JsError> {
+        if options.is_undefined() || options.is_null() {
+            return Ok(Options::default());
+        }
+        serde_wasm_bindgen::from_value(options)
+            .map_err(|error| JsError::new(&format!("invalid parser options: {}", error)))
+    }
+}
+
+/// 住所マスタの取得元ごとのパーサー
+enum Inner {
+    Api(parser::Parser),
+    #[cfg(feature = "embedded-master")]
+    Embedded(parser::Parser<CachedApiClient<ReqwestApiClient>, EmbeddedSource>),
+}
+
 #[wasm_bindgen(skip_typescript)]
 pub struct Parser {
-    parser: parser::Parser,
+    parser: Inner,
 }
 
-#[warn(clippy::new_without_default)]
 #[wasm_bindgen]
 impl Parser {
     #[wasm_bindgen(constructor)]
-    pub fn new() -> Self {
-        Parser {
-            parser: parser::Parser::default(),
-        }
+    pub fn new(options: JsValue) -> Result<Parser, JsError> {
+        let options = Options::from_js(options)?;
+        Ok(Parser {
+            parser: Inner::Api(parser::Parser::default().options(options.into())),
+        })
+    }
+
+    /// ビルド時に埋め込んだ住所マスタを使用し、通信を行わないパーサーを生成する
+    #[cfg(feature = "embedded-master")]
+    pub fn embedded(options: JsValue) -> Result<Parser, JsError> {
+        let options = Options::from_js(options)?;
+        Ok(Parser {
+            parser: Inner::Embedded(parser::Parser::embedded().options(options.into())),
+        })
     }
 
     pub async fn parse(&self, address: &str) -> JsValue {
-        let result = self.parser.parse(address).await;
+        let result = match &self.parser {
+            Inner::Api(parser) => parser.parse(address).await,
+            #[cfg(feature = "embedded-master")]
+            Inner::Embedded(parser) => parser.parse(address).await,
+        };
         serde_wasm_bindgen::to_value(&result).unwrap()
     }
+
+    pub async fn parse_many(&self, addresses: Vec<String>) -> JsValue {
+        let addresses = addresses.iter().map(String::as_str);
+        let results = match &self.parser {
+            Inner::Api(parser) => parser.parse_many(addresses).await,
+            #[cfg(feature = "embedded-master")]
+            Inner::Embedded(parser) => parser.parse_many(addresses).await,
+        };
+        serde_wasm_bindgen::to_value(&results).unwrap()
+    }
 }
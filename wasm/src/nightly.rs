@@ -23,6 +23,8 @@ export interface Metadata {
     latitude: number | undefined;
     longitude: number | undefined;
     depth: number;
+    addressing_system: "ResidentialAddressIndication" | "LotNumber" | undefined;
+    error: Error | undefined;
 }
 
 export interface ParsedAddress {
@@ -30,6 +32,7 @@ export interface ParsedAddress {
     city: string;
     town: string;
     rest: string;
+    rest_components: RestComponents;
     metadata: Metadata;
 }"#;
 
@@ -3,6 +3,7 @@ use japanese_address_parser::http::client::ApiClient;
 use japanese_address_parser::http::error::ApiClientError;
 use serde::de::DeserializeOwned;
 
+#[derive(Clone)]
 pub(crate) struct GlooNetClient {}
 
 impl ApiClient for GlooNetClient {